@@ -12,6 +12,25 @@ export interface NapiMarketDescriptorInput {
   boundsUpper?: number
   kind: string
   negRisk: boolean
+  /** Taker fee rate [YES, NO] in bps; omitted = fee-free. */
+  feeRateBps?: Array<number>
+}
+/** Neg-risk outcome market input from Node.js. */
+export interface NapiNegRiskOutcomeInput {
+  marketId: string
+  slug: string
+  /** [YES, NO] */
+  clobTokenIds: Array<string>
+  /** Taker fee rate [YES, NO] in bps; omitted = fee-free. */
+  feeRateBps?: Array<number>
+}
+/** Neg-risk event input from Node.js — must list every outcome of the event. */
+export interface NapiNegRiskEventInput {
+  /** Neg-risk market id shared by all outcomes. */
+  eventId: string
+  eventSlug: string
+  crypto?: string
+  outcomes: Array<NapiNegRiskOutcomeInput>
 }
 /** Group input from Node.js. */
 export interface NapiRangeGroupInput {
@@ -26,6 +45,73 @@ export interface NapiEngineConfigInput {
   minProfitBps?: number
   minProfitAbs?: number
   cooldownMs?: number
+  /** Re-emission of persistent opportunities: "cooldown" | "hysteresis". */
+  emissionPolicy?: string
+  /** Hysteresis: edge gain in bps over the last emission that re-emits before the cooldown. */
+  reemitImprovementBps?: number
+  /** Maximum leg quote age in ms (0 = disabled). */
+  maxQuoteAgeMs?: number
+  /** Per-strategy staleness overrides (negative = clear override). */
+  triangleMaxQuoteAgeMs?: number
+  complementMaxQuoteAgeMs?: number
+  unbundleMaxQuoteAgeMs?: number
+  bundleMaxQuoteAgeMs?: number
+  ladderBuyMaxQuoteAgeMs?: number
+  ladderSellMaxQuoteAgeMs?: number
+  monotoneMaxQuoteAgeMs?: number
+  parityBuyMaxQuoteAgeMs?: number
+  paritySellMaxQuoteAgeMs?: number
+  negRiskYesBuyMaxQuoteAgeMs?: number
+  negRiskNoBuyMaxQuoteAgeMs?: number
+  negRiskConvertMaxQuoteAgeMs?: number
+  /** Clock for staleness and cooldowns: "system" | "exchange". */
+  quoteClock?: string
+  /** Strategy switches (unset = unchanged). */
+  triangleEnabled?: boolean
+  complementEnabled?: boolean
+  unbundleEnabled?: boolean
+  bundleEnabled?: boolean
+  ladderBuyEnabled?: boolean
+  ladderSellEnabled?: boolean
+  monotoneEnabled?: boolean
+  parityBuyEnabled?: boolean
+  paritySellEnabled?: boolean
+  negRiskYesBuyEnabled?: boolean
+  negRiskNoBuyEnabled?: boolean
+  /** Info-only: CONVERT signals reach `onArbSignal` but never the executor. */
+  negRiskConvertEnabled?: boolean
+  /**
+   * Max range children per bracket (non-adjacent / multi-child parent pairs; 0 = off).
+   * Changing it rebuilds every group.
+   */
+  maxBracketChildren?: number
+}
+/**
+ * Per-crypto / event-slug / group config override from Node.js.
+ * Exactly one of `crypto`, `event_slug_pattern`, `group_key` selects the groups;
+ * unset fields inherit from the layer below.
+ */
+export interface NapiEngineConfigOverride {
+  crypto?: string
+  /** Glob on event slug (`*` = any characters). */
+  eventSlugPattern?: string
+  groupKey?: string
+  minProfitBps?: number
+  minProfitAbs?: number
+  cooldownMs?: number
+  maxQuoteAgeMs?: number
+  triangleEnabled?: boolean
+  complementEnabled?: boolean
+  unbundleEnabled?: boolean
+  bundleEnabled?: boolean
+  ladderBuyEnabled?: boolean
+  ladderSellEnabled?: boolean
+  monotoneEnabled?: boolean
+  parityBuyEnabled?: boolean
+  paritySellEnabled?: boolean
+  negRiskYesBuyEnabled?: boolean
+  negRiskNoBuyEnabled?: boolean
+  negRiskConvertEnabled?: boolean
 }
 /** Engine status output. */
 export interface NapiEngineStatus {
@@ -33,6 +119,148 @@ export interface NapiEngineStatus {
   totalTrios: number
   totalPriceSlots: number
   totalTokensIndexed: number
+  totalStaleSkips: number
+  totalNegRiskEvents: number
+}
+/**
+ * Delivery counters of the opt-in Node.js feeds (`onArbSignal` / `onPriceUpdate`).
+ * `*_dropped` counts calls rejected by a full callback queue.
+ */
+export interface NapiFeedStats {
+  arbSignalsSent: number
+  arbSignalsDropped: number
+  priceUpdatesSent: number
+  priceUpdatesDropped: number
+  priceFeedTokens: number
+  priceFeedThrottleMs: number
+}
+/** Live quote of one token (bid/ask absent until the first update). */
+export interface NapiQuoteInspection {
+  tokenId: string
+  bestBid?: number
+  bestAsk?: number
+  bestBidSize: number
+  bestAskSize: number
+  timestampMs: number
+}
+/** Market with live YES/NO quotes. */
+export interface NapiMarketInspection {
+  marketId: string
+  slug: string
+  kind: string
+  boundsLower?: number
+  boundsUpper?: number
+  negRisk: boolean
+  yes: NapiQuoteInspection
+  no: NapiQuoteInspection
+}
+/** Raw edge of one trio direction — reported even below threshold. */
+export interface NapiEdgeInspection {
+  totalAsk: number
+  totalBid: number
+  payout: number
+  profitAbs: number
+  profitBps: number
+  meetsThreshold: boolean
+}
+/** Trio edges + cooldown state. */
+export interface NapiTrioInspection {
+  trioIndex: number
+  parentLowerMarketId: string
+  parentUpperMarketId: string
+  rangeMarketId: string
+  /** Absent while any triangle leg is unquoted. */
+  triangle?: NapiEdgeInspection
+  /** Absent while any complement leg is unquoted. */
+  complement?: NapiEdgeInspection
+  lastEmittedBuyMs: number
+  lastEmittedComplementMs: number
+  lastEmittedUnbundleMs: number
+  lastEmittedBundleMs: number
+  staleSkipCount: number
+}
+/** Snapshot of one group as the engine sees it. */
+export interface NapiGroupInspection {
+  groupKey: string
+  eventSlug: string
+  crypto: string
+  children: Array<NapiMarketInspection>
+  parents: Array<NapiMarketInspection>
+  trios: Array<NapiTrioInspection>
+}
+/** Ranked structure edge from the near-miss scanner. */
+export interface NapiNearMiss {
+  /**
+   * "triangle" | "complement" | "unbundle" | "bundle" | "ladder_buy" | "ladder_sell" | "monotone"
+   * | "parity_buy" | "parity_sell" | "neg_risk_yes_buy" | "neg_risk_no_buy" | "neg_risk_convert"
+   */
+  strategy: string
+  groupKey: string
+  eventSlug: string
+  crypto: string
+  /** "trio" | "bracket" | "ladder" | "monotone" | "parity" | "neg_risk" */
+  structure: string
+  structureIndex: number
+  profitAbs: number
+  profitBps: number
+  lastUpdateMs: number
+  maxProfitAbs: number
+  maxProfitBps: number
+  maxAtMs: number
+  timeAboveZeroMs: number
+  samples: number
+}
+/** Market dropped from the structure, with the reason. */
+export interface NapiRejectedMarket {
+  marketId: string
+  /** "child" | "parent" */
+  role: string
+  reason: string
+}
+/** Gap or overlap between consecutive range children. */
+export interface NapiBoundIssue {
+  lowerMarketId: string
+  upperMarketId: string
+  /** Upper bound of the lower child. */
+  from: number
+  /** Lower bound of the upper child. */
+  to: number
+}
+/** Token id shared by several markets of a group. */
+export interface NapiDuplicateToken {
+  tokenId: string
+  marketIds: Array<string>
+}
+/** Parent that takes part in no trio. */
+export interface NapiOrphanParent {
+  marketId: string
+  reason: string
+}
+/** Structure diagnostics of one group. */
+export interface NapiGroupDiagnostics {
+  groupKey: string
+  trioCount: number
+  rejectedMarkets: Array<NapiRejectedMarket>
+  boundGaps: Array<NapiBoundIssue>
+  boundOverlaps: Array<NapiBoundIssue>
+  duplicateTokens: Array<NapiDuplicateToken>
+  parentsWithoutTrio: Array<NapiOrphanParent>
+  notes: Array<string>
+}
+/** Result of `updateMarketStructure`: trio count plus per-group diagnostics. */
+export interface NapiMarketStructureReport {
+  totalTrios: number
+  groups: Array<NapiGroupDiagnostics>
+}
+/** Result of restoring an engine snapshot. */
+export interface NapiRestoreResult {
+  groups: number
+  trios: number
+  negRiskEvents: number
+  pricesRestored: number
+  pricesDroppedStale: number
+  cooldownsRestored: number
+  savedAtMs: number
 }
 /**
  * Initialize the Rust socket engine with the given configuration.
@@ -53,14 +281,107 @@ export declare function shutdownSocket(): void
  * Update the market structure (groups, markets, trios).
  *
  * Called by Node.js when markets are discovered/refreshed.
- * Rebuilds PriceTable, groups, and trio indices.
- * Returns the total number of trios created.
+ * Reconciles groups incrementally: groups missing from the input are removed,
+ * the rest are upserted, and prices of unchanged tokens survive the refresh.
+ * Returns the total number of trios and a diagnostics report per group
+ * (rejected markets, bound gaps/overlaps, duplicate tokens, orphan parents).
+ */
+export declare function updateMarketStructure(groups: Array<NapiRangeGroupInput>): NapiMarketStructureReport
+/**
+ * Replace the set of neg-risk events the engine tracks.
+ *
+ * Each event must list every outcome; events with fewer than two outcomes or
+ * an outcome missing a token id are skipped. Outcome tokens still need to be
+ * subscribed on the socket. Returns the number of events tracked.
+ */
+export declare function updateNegRiskEvents(events: Array<NapiNegRiskEventInput>): number
+/**
+ * Insert or replace a single group by `group_key`.
+ *
+ * Only this group's trios and token indices are rebuilt; prices of tokens
+ * that remain in the group are kept. Returns the group's trio count.
+ */
+export declare function upsertMarketGroup(group: NapiRangeGroupInput): number
+/**
+ * Remove a group by `group_key`, reclaiming slots no other group uses.
+ * Returns false if the group was unknown.
  */
-export declare function updateMarketStructure(groups: Array<NapiRangeGroupInput>): number
-/** Update engine configuration (profit thresholds, cooldown). */
+export declare function removeMarketGroup(groupKey: string): boolean
+/**
+ * Add markets to an existing group (markets with a known `market_id` are replaced).
+ * Returns the group's new trio count.
+ */
+export declare function addGroupMarkets(groupKey: string, children: Array<NapiMarketDescriptorInput>, parents: Array<NapiMarketDescriptorInput>): number
+/**
+ * Retire markets from an existing group by `market_id`.
+ * Returns the group's new trio count.
+ */
+export declare function retireGroupMarkets(groupKey: string, marketIds: Array<string>): number
+/** Update engine configuration (profit thresholds, cooldown, quote staleness, strategy switches). */
 export declare function updateEngineConfig(config: NapiEngineConfigInput): void
+/**
+ * Replace all per-crypto / event-slug / group config overrides.
+ * Precedence: global < crypto < event slug pattern < group key.
+ */
+export declare function setEngineConfigOverrides(overrides: Array<NapiEngineConfigOverride>): void
 /** Get engine status (group/trio counts, price slot count). */
 export declare function getEngineStatus(): NapiEngineStatus
+/**
+ * Inspect what the engine currently believes: markets, live leg quotes, and each
+ * trio's triangle/complement edge (even below threshold) with cooldown timestamps.
+ * Pass a group key to restrict the result to one group.
+ */
+export declare function inspectEngine(groupKey?: string | undefined | null): Array<NapiGroupInspection>
+/**
+ * Top-N structures by current edge (negative included), per strategy.
+ * `strategy` = "triangle" | "complement" | "unbundle" | "bundle" | "ladder_buy" | "ladder_sell"
+ * | "monotone" | "parity_buy" | "parity_sell" | "neg_risk_yes_buy" | "neg_risk_no_buy"
+ * | "neg_risk_convert"; omit for all.
+ */
+export declare function getNearMisses(strategy?: string | undefined | null, topN?: number | undefined | null): Array<NapiNearMiss>
+/** Clear running edge statistics (max edge, time above zero) on every structure. */
+export declare function resetNearMissStats(): void
+/**
+ * Most recently closed opportunities, oldest first — open time, close time,
+ * peak edge and edge at close. `limit` keeps only the newest ones.
+ */
+export declare function getRecentOpportunities(limit?: number | undefined | null): Array<OpportunityEvent>
+/** Register callback for opportunity lifecycle events (opened / updated / closed). */
+export declare function onOpportunityEvent(callback: (event: OpportunityEvent) => void): void
+/**
+ * Register callback for every signal the engine emits, including ones the
+ * executor skips. Bounded queue: signals are dropped while JS falls behind.
+ */
+export declare function onArbSignal(callback: (signal: ArbSignal) => void): void
+/**
+ * Register callback for the throttled price feed (tokens chosen with
+ * `setPriceFeedTokens`). Bounded queue: updates are dropped while JS falls behind.
+ */
+export declare function onPriceUpdate(callback: (update: TopOfBookUpdate) => void): void
+/**
+ * Choose the tokens sent to `onPriceUpdate`, each at most once per `throttle_ms`
+ * (default 250) at its latest top of book. An empty list turns the feed off.
+ */
+export declare function setPriceFeedTokens(tokenIds: Array<string>, throttleMs?: number | undefined | null): void
+/** Delivery counters of the `onArbSignal` / `onPriceUpdate` feeds. */
+export declare function getFeedStats(): NapiFeedStats
+/** Log the top-N near misses per strategy every `interval_ms` (0 = stop). */
+export declare function setNearMissSnapshotInterval(intervalMs: number, topN?: number | undefined | null): void
+/** Save the engine state (structure, neg-risk events, quotes, cooldowns) to a versioned snapshot file. */
+export declare function saveEngineSnapshot(path: string): void
+/**
+ * Restore engine state from a snapshot file, replacing the current structure
+ * and neg-risk events.
+ *
+ * Quotes older than `max_price_age_ms` are dropped (default: the engine's
+ * `max_quote_age_ms`; 0 keeps all). Kept quotes retain their original timestamps.
+ */
+export declare function restoreEngineSnapshot(path: string, maxPriceAgeMs?: number | undefined | null): NapiRestoreResult
+/**
+ * Configure automatic engine snapshots: saved to `path` every `interval_ms`
+ * (0/unset = only on shutdown). A null path disables automatic snapshots.
+ */
+export declare function configureEngineSnapshots(path?: string | undefined | null, intervalMs?: number | undefined | null): void
 /**
  * Initialize the executor with trading configuration.
  *
@@ -81,6 +402,10 @@ export declare function setTradingEnabled(enabled: boolean): void
  * Used by executor to validate SELL leg sizes — can't sell more than minted.
  */
 export declare function updateMintedAssets(groupKey: string, assets: Array<NapiMintedAssetEntry>): void
+/** Paper-mode positions and PnL (None unless the executor runs in paper mode). */
+export declare function getPaperPortfolio(): NapiPaperPortfolio | null
+/** Clear paper positions and PnL. */
+export declare function resetPaperPortfolio(): void
 /** Minted asset entry input from Node.js. */
 export interface NapiMintedAssetEntry {
   tokenId: string
@@ -139,20 +464,40 @@ export interface SocketStatus {
   messagesReceived: number
   lastMessageAtMs?: number
 }
+/** Successful order detail — part of TradeResult. */
+export interface NapiSuccessOrder {
+  tokenId: string
+  marketSlug: string
+  side: string
+  /** Average matched price (the limit while nothing matched). */
+  price: number
+  /** Shares matched at submission (0 while the order rests on the book). */
+  size: number
+  negRisk: boolean
+  orderType: string
+  /** CLOB order status: `"matched"` | `"live"` | `"delayed"` | `"unmatched"`. */
+  status: string
+}
 /** Failed order detail — part of TradeResult. */
 export interface NapiFailedOrder {
   tokenId: string
+  marketSlug: string
   side: string
   price: number
+  orderType: string
   errorMsg: string
 }
 /** Trade result — emitted to Node.js via on_trade_result callback. */
 export interface TradeResult {
   success: boolean
   orderIds: Array<string>
+  successfulOrders: Array<NapiSuccessOrder>
   failedOrders: Array<NapiFailedOrder>
   totalCost: number
+  /** Executed size × the signal's profit per set. */
   expectedPnl: number
+  /** PnL of what filled at submission (resting orders count as unfilled). */
+  realizedPnl: number
   latencyUs: number
   signalGroupKey: string
   signalEventSlug: string
@@ -190,6 +535,10 @@ export interface TradeResult {
   signalTrianglePayout?: number
   signalTriangleMode?: string
   signalReason: string
+  /** Every leg of the signal, in order. */
+  signalLegs: Array<NapiSignalLeg>
+  /** Simulated by paper mode — nothing was posted to the CLOB. */
+  paper: boolean
 }
 /** Executor config input from Node.js (init_executor / update_executor_config). */
 export interface NapiExecutorConfigInput {
@@ -203,7 +552,46 @@ export interface NapiExecutorConfigInput {
   minPnlThresholdPercent: number
   defaultSize: number
   slippageEnabled: boolean
+  /** Per-group cooldown after an execution starts. */
   opportunityTimeoutMs?: number
+  /** Batches in flight at once across groups (default 4). */
+  maxConcurrentExecutions?: number
+  /** Default order type for executor orders: "GTC" | "GTD" | "FOK" | "FAK" (default GTC). */
+  orderType?: string
+  /** Per-strategy / per-leg order types — the most specific matching rule wins. */
+  orderTypeRules?: Array<NapiOrderTypeRule>
+  /** Lifetime of GTD orders after the exchange's 60s minimum (default 30s). */
+  gtdTtlSecs?: number
+  /** Smallest size worth trading; thinner opportunities are skipped (default 5). */
+  minTradeSize?: number
+  /** Simulate fills against the live book instead of POSTing (default false). */
+  paperTrading?: boolean
+  /** Delay before a paper order is matched (default 260ms). */
+  paperLatencyMs?: number
+}
+/** Order type for executor orders matching a strategy and/or leg role. */
+export interface NapiOrderTypeRule {
+  /** Strategy name (e.g. "ladder_buy"); None = any strategy. */
+  strategy?: string
+  /** Leg role: "parent" | "parent_upper" | "child"; None = any leg. */
+  legRole?: string
+  /** "GTC" | "GTD" | "FOK" | "FAK" */
+  orderType: string
+}
+/** Net paper holding of one token. */
+export interface NapiPaperPosition {
+  tokenId: string
+  marketSlug: string
+  size: number
+  cost: number
+}
+/** Paper-mode positions and PnL (get_paper_portfolio). */
+export interface NapiPaperPortfolio {
+  positions: Array<NapiPaperPosition>
+  pnl: number
+  feesPaid: number
+  tradeCount: number
+  fillCount: number
 }
 /** Input order from Node.js for the manual place-batch-orders-rust API. */
 export interface NapiBatchOrderInput {
@@ -242,20 +630,33 @@ export interface NapiBatchOrderConfig {
   clobUrl?: string
 }
 /**
- * Arbitrage signal emitted to Node.js via callback.
+ * Flat projection of an `ArbSignal` for Node.js.
  *
  * Flat layout for optimal N-API serialization.
  * Contains all fields needed by `RealExecutionService.buildOrderCandidates()`
- * and `calculateTotalCost()`.
+ * and `calculateTotalCost()`; `legs` carries the full N-leg form.
  */
 export interface ArbSignal {
   groupKey: string
   eventSlug: string
   crypto: string
+  /**
+   * `StrategyKind::signal_name`, e.g. "POLYMARKET_TRIANGLE_BUY" | "POLYMARKET_LADDER_SELL"
+   * | "POLYMARKET_NEG_RISK_CONVERT".
+   */
   strategy: string
+  /** Net of every leg's taker fee — engine thresholds apply to these. */
   profitAbs: number
   profitBps: number
+  /** Before taker fees. */
+  grossProfitAbs: number
+  grossProfitBps: number
   timestampMs: number
+  /**
+   * Size fillable on every leg at top of book: min of ask sizes on BUY legs
+   * and bid sizes on SELL legs (0 when any leg size is unknown).
+   */
+  maxExecutableSize: number
   parentAssetId: string
   parentMarketSlug: string
   parentBestBid?: number
@@ -263,6 +664,7 @@ export interface ArbSignal {
   parentBestBidSize?: number
   parentBestAskSize?: number
   parentNegRisk: boolean
+  parentFeeRateBps: number
   parentUpperAssetId: string
   parentUpperMarketSlug: string
   parentUpperBestBid?: number
@@ -270,6 +672,7 @@ export interface ArbSignal {
   parentUpperBestBidSize?: number
   parentUpperBestAskSize?: number
   parentUpperNegRisk: boolean
+  parentUpperFeeRateBps: number
   childAssetId: string
   childMarketSlug: string
   childBestBid?: number
@@ -277,7 +680,9 @@ export interface ArbSignal {
   childBestBidSize?: number
   childBestAskSize?: number
   childNegRisk: boolean
+  childFeeRateBps: number
   childIndex: number
+  extraChildLegs: Array<SignalLeg>
   childrenSumAsk: number
   childrenSumBid: number
   parentBestBidFlat?: number
@@ -289,4 +694,71 @@ export interface ArbSignal {
   trianglePayout?: number
   triangleMode?: string
   reason: string
+  legs: Array<NapiSignalLeg>
+}
+/** One additional range-child leg of a flat signal. */
+export interface SignalLeg {
+  assetId: string
+  marketSlug: string
+  bestBid?: number
+  bestAsk?: number
+  bestBidSize?: number
+  bestAskSize?: number
+  negRisk: boolean
+  feeRateBps: number
+  childIndex: number
+}
+/** One leg of a signal, as sent to Node.js. */
+export interface NapiSignalLeg {
+  role: string
+  side: string
+  assetId: string
+  marketSlug: string
+  price: number
+  sizeAvailable: number
+  bestBid: number
+  bestAsk: number
+  bestBidSize: number
+  bestAskSize: number
+  negRisk: boolean
+  feeRateBps: number
+  childIndex: number
+}
+/** Transition reported by an opportunity tracker — `"opened"` | `"updated"` | `"closed"` in JS. */
+export const enum OpportunityPhase {
+  /** The edge crossed the threshold. */
+  Opened = 'opened',
+  /** The open opportunity reached a new peak edge. */
+  Updated = 'updated',
+  /**
+   * The edge fell below threshold, a leg lost its quote, or the structure
+   * or strategy went away.
+   */
+  Closed = 'closed'
+}
+/**
+ * Opportunity lifecycle event — one per transition of a strategy's edge on
+ * one structure across the threshold, plus one per new peak while open.
+ *
+ * `timestamp_ms` is when the transition happened (the close time for
+ * `"closed"`); `profit_*` is the edge at that moment (NaN when the close was
+ * caused by a leg losing its quote, the last evaluated edge when the
+ * structure was dropped or its strategy disabled).
+ */
+export interface OpportunityEvent {
+  phase: OpportunityPhase
+  groupKey: string
+  eventSlug: string
+  crypto: string
+  strategy: string
+  structure: string
+  structureIndex: number
+  openedAtMs: number
+  timestampMs: number
+  durationMs: number
+  peakProfitAbs: number
+  peakProfitBps: number
+  peakAtMs: number
+  profitAbs: number
+  profitBps: number
 }
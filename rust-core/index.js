@@ -310,7 +310,7 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { initSocket, subscribeTokens, unsubscribeTokens, getSocketStatus, shutdownSocket, updateMarketStructure, updateEngineConfig, getEngineStatus, initExecutor, onTradeResult, updateBalance, setTradingEnabled, updateMintedAssets, placeBatchOrdersRust } = nativeBinding
+const { initSocket, subscribeTokens, unsubscribeTokens, getSocketStatus, shutdownSocket, updateMarketStructure, updateNegRiskEvents, upsertMarketGroup, removeMarketGroup, addGroupMarkets, retireGroupMarkets, updateEngineConfig, setEngineConfigOverrides, getEngineStatus, inspectEngine, getNearMisses, resetNearMissStats, OpportunityPhase, getRecentOpportunities, onOpportunityEvent, onArbSignal, onPriceUpdate, setPriceFeedTokens, getFeedStats, setNearMissSnapshotInterval, saveEngineSnapshot, restoreEngineSnapshot, configureEngineSnapshots, initExecutor, onTradeResult, updateBalance, setTradingEnabled, updateMintedAssets, getPaperPortfolio, resetPaperPortfolio, placeBatchOrdersRust } = nativeBinding
 
 module.exports.initSocket = initSocket
 module.exports.subscribeTokens = subscribeTokens
@@ -318,11 +318,33 @@ module.exports.unsubscribeTokens = unsubscribeTokens
 module.exports.getSocketStatus = getSocketStatus
 module.exports.shutdownSocket = shutdownSocket
 module.exports.updateMarketStructure = updateMarketStructure
+module.exports.updateNegRiskEvents = updateNegRiskEvents
+module.exports.upsertMarketGroup = upsertMarketGroup
+module.exports.removeMarketGroup = removeMarketGroup
+module.exports.addGroupMarkets = addGroupMarkets
+module.exports.retireGroupMarkets = retireGroupMarkets
 module.exports.updateEngineConfig = updateEngineConfig
+module.exports.setEngineConfigOverrides = setEngineConfigOverrides
 module.exports.getEngineStatus = getEngineStatus
+module.exports.inspectEngine = inspectEngine
+module.exports.getNearMisses = getNearMisses
+module.exports.resetNearMissStats = resetNearMissStats
+module.exports.OpportunityPhase = OpportunityPhase
+module.exports.getRecentOpportunities = getRecentOpportunities
+module.exports.onOpportunityEvent = onOpportunityEvent
+module.exports.onArbSignal = onArbSignal
+module.exports.onPriceUpdate = onPriceUpdate
+module.exports.setPriceFeedTokens = setPriceFeedTokens
+module.exports.getFeedStats = getFeedStats
+module.exports.setNearMissSnapshotInterval = setNearMissSnapshotInterval
+module.exports.saveEngineSnapshot = saveEngineSnapshot
+module.exports.restoreEngineSnapshot = restoreEngineSnapshot
+module.exports.configureEngineSnapshots = configureEngineSnapshots
 module.exports.initExecutor = initExecutor
 module.exports.onTradeResult = onTradeResult
 module.exports.updateBalance = updateBalance
 module.exports.setTradingEnabled = setTradingEnabled
 module.exports.updateMintedAssets = updateMintedAssets
+module.exports.getPaperPortfolio = getPaperPortfolio
+module.exports.resetPaperPortfolio = resetPaperPortfolio
 module.exports.placeBatchOrdersRust = placeBatchOrdersRust
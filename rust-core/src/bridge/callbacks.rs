@@ -1,29 +1,72 @@
 //! Callback helpers for Rust → Node.js communication.
 //!
-//! Only `on_trade_result` callback remains — invoked when the executor
-//! posts an order. All other data flows (socket, engine, signals) stay
+//! `on_trade_result` is invoked when the executor posts an order and
+//! `on_opportunity_event` for opportunity lifecycle events. The opt-in
+//! `on_arb_signal` and `on_price_update` feeds mirror engine signals and
+//! watched prices; every other data flow (socket, engine, executor) stays
 //! entirely within Rust.
 
+use crate::types::market::TopOfBookUpdate;
 use crate::types::order::TradeResult;
+use crate::types::signal::{ArbSignal, FlatArbSignal, OpportunityEvent};
 use napi::threadsafe_function::{
     ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode,
 };
+use napi::Status;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// Queue bound of the `on_arb_signal` / `on_price_update` callbacks. A call
+/// into a full queue is dropped (and counted) instead of blocking the engine.
+pub const FEED_QUEUE_CAP: usize = 1024;
+
+/// Sent / dropped counters of one bounded feed.
+#[derive(Default)]
+pub struct FeedCounters {
+    pub sent: AtomicU64,
+    pub dropped: AtomicU64,
+}
+
+impl FeedCounters {
+    fn record(&self, status: Status) {
+        match status {
+            Status::Ok => self.sent.fetch_add(1, Ordering::Relaxed),
+            _ => self.dropped.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+}
+
 /// Stores the registered Node.js callbacks.
 ///
-/// Only `on_trade_result` is kept — called when the Rust executor
-/// successfully posts an order, so NestJS can save to DB / send Telegram.
+/// `on_trade_result` is called when the Rust executor successfully posts an
+/// order, so NestJS can save to DB / send Telegram. `on_opportunity_event`
+/// receives opportunity lifecycle events from the engine thread, as do the
+/// bounded `on_arb_signal` and `on_price_update` feeds.
 pub struct CallbackRegistry {
     /// The registered on_trade_result callback (executor trade results).
     on_trade_result: Mutex<Option<ThreadsafeFunction<TradeResult, ErrorStrategy::Fatal>>>,
+    /// The registered on_opportunity_event callback. A std mutex: it is read from
+    /// the engine thread, which must not block on the async runtime.
+    on_opportunity_event:
+        std::sync::Mutex<Option<ThreadsafeFunction<OpportunityEvent, ErrorStrategy::Fatal>>>,
+    /// Opt-in feed of every engine signal (bounded queue, engine thread).
+    on_arb_signal: std::sync::Mutex<Option<ThreadsafeFunction<FlatArbSignal, ErrorStrategy::Fatal>>>,
+    /// Opt-in throttled price feed (bounded queue, engine thread).
+    on_price_update: std::sync::Mutex<Option<ThreadsafeFunction<TopOfBookUpdate, ErrorStrategy::Fatal>>>,
+    pub arb_signal_counters: FeedCounters,
+    pub price_update_counters: FeedCounters,
 }
 
 impl CallbackRegistry {
     pub fn new() -> Self {
         Self {
             on_trade_result: Mutex::new(None),
+            on_opportunity_event: std::sync::Mutex::new(None),
+            on_arb_signal: std::sync::Mutex::new(None),
+            on_price_update: std::sync::Mutex::new(None),
+            arb_signal_counters: FeedCounters::default(),
+            price_update_counters: FeedCounters::default(),
         }
     }
 
@@ -49,6 +92,69 @@ impl CallbackRegistry {
         let cb = self.on_trade_result.lock().await;
         cb.is_some()
     }
+
+    /// Register the on_opportunity_event callback for lifecycle events.
+    pub fn set_on_opportunity_event(
+        &self,
+        callback: ThreadsafeFunction<OpportunityEvent, ErrorStrategy::Fatal>,
+    ) {
+        let mut cb = self.on_opportunity_event.lock().unwrap_or_else(|e| e.into_inner());
+        *cb = Some(callback);
+    }
+
+    /// Invoke the on_opportunity_event callback once per event (no-op if unregistered).
+    pub fn emit_opportunity_events(&self, events: Vec<OpportunityEvent>) {
+        let cb = self.on_opportunity_event.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ref tsfn) = *cb {
+            for event in events {
+                tsfn.call(event, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    /// Register the on_arb_signal callback. `tsfn` must be created with a
+    /// bounded queue (`FEED_QUEUE_CAP`).
+    pub fn set_on_arb_signal(&self, callback: ThreadsafeFunction<FlatArbSignal, ErrorStrategy::Fatal>) {
+        let mut cb = self.on_arb_signal.lock().unwrap_or_else(|e| e.into_inner());
+        *cb = Some(callback);
+    }
+
+    /// Mirror engine signals to the on_arb_signal callback (no-op if unregistered).
+    /// Signals that do not fit in the queue are dropped.
+    pub fn emit_arb_signals(&self, signals: &[Arc<ArbSignal>]) {
+        let cb = self.on_arb_signal.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ref tsfn) = *cb {
+            for signal in signals {
+                let status = tsfn.call(FlatArbSignal::from(&**signal), ThreadsafeFunctionCallMode::NonBlocking);
+                self.arb_signal_counters.record(status);
+            }
+        }
+    }
+
+    /// Register the on_price_update callback. `tsfn` must be created with a
+    /// bounded queue (`FEED_QUEUE_CAP`).
+    pub fn set_on_price_update(&self, callback: ThreadsafeFunction<TopOfBookUpdate, ErrorStrategy::Fatal>) {
+        let mut cb = self.on_price_update.lock().unwrap_or_else(|e| e.into_inner());
+        *cb = Some(callback);
+    }
+
+    /// Invoke the on_price_update callback once per update (no-op if unregistered).
+    /// Updates that do not fit in the queue are dropped.
+    pub fn emit_price_updates(&self, updates: Vec<TopOfBookUpdate>) {
+        let cb = self.on_price_update.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ref tsfn) = *cb {
+            for update in updates {
+                let status = tsfn.call(update, ThreadsafeFunctionCallMode::NonBlocking);
+                self.price_update_counters.record(status);
+            }
+        }
+    }
+}
+
+impl Default for CallbackRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Get a reference to the global callback registry.
@@ -3,22 +3,37 @@
 //! These functions are callable from JavaScript via the native module.
 //! They manage the lifecycle of the Rust socket engine, arbitrage engine, and executor.
 //!
-//! Callback policy: Only `on_trade_result` calls back to Node.js.
-//! All other data flows (socket → engine → executor) stay entirely in Rust.
-
-use crate::bridge::callbacks::get_registry;
-use crate::engine::engine::{MarketDescriptorInput, RangeGroupInput};
-use crate::engine::state::{EngineConfig, EngineState};
+//! Callback policy: `on_trade_result` and `on_opportunity_event` call back to
+//! Node.js, plus the opt-in, bounded `on_arb_signal` / `on_price_update` feeds.
+//! The data flow itself (socket → engine → executor) stays entirely in Rust.
+
+use crate::bridge::callbacks::{get_registry, FEED_QUEUE_CAP};
+use crate::engine::engine::{
+    MarketDescriptorInput, NegRiskEventInput, NegRiskOutcomeInput, RangeGroupInput,
+};
+use crate::engine::diagnostics::BoundIssue;
+use crate::engine::inspect::{self, EdgeInspection, GroupInspection, MarketInspection};
+use crate::engine::owner::EngineHandle;
+use crate::engine::persistence;
+use crate::engine::scanner::{self, NearMiss};
+use crate::engine::state::{
+    ConfigOverride, EmissionPolicy, EngineClock, EngineConfig, EngineState, GroupState, OverrideSelector,
+    PriceSlot,
+};
 use crate::executor::api_client::ClobApiClient;
+use crate::executor::paper::QuoteSource;
 use crate::executor::{self, ExecutorState};
 use crate::socket::manager::SocketManager;
 use crate::types::config::SocketConfig;
 use crate::types::market::{SocketStatus, TopOfBookUpdate};
-use crate::types::order::{NapiExecutorConfigInput, TradeResult};
-use crate::types::signal::ArbSignal;
+use crate::types::order::{NapiExecutorConfigInput, NapiPaperPortfolio, TradeResult};
+use crate::types::signal::{ArbSignal, FlatArbSignal, OpportunityEvent};
+use crate::types::strategy::StrategyKind;
 use napi::bindgen_prelude::*;
 use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction};
 use napi_derive::napi;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::info;
@@ -35,12 +50,58 @@ fn get_manager() -> &'static Arc<tokio::sync::Mutex<Option<SocketManager>>> {
     MANAGER.get_or_init(|| Arc::new(tokio::sync::Mutex::new(None)))
 }
 
-/// Global engine state — owns PriceTable and all groups.
-static ENGINE_STATE: std::sync::OnceLock<Arc<std::sync::Mutex<EngineState>>> =
-    std::sync::OnceLock::new();
+/// Global engine — a dedicated thread owns EngineState (PriceTable and all groups).
+/// Mutations are sent as commands; reads use the published snapshot.
+static ENGINE: std::sync::OnceLock<EngineHandle> = std::sync::OnceLock::new();
+
+/// Max staleness of the engine read snapshot while updates are flowing.
+const ENGINE_SNAPSHOT_PUBLISH_MS: u64 = 50;
 
-fn get_engine() -> &'static Arc<std::sync::Mutex<EngineState>> {
-    ENGINE_STATE.get_or_init(|| Arc::new(std::sync::Mutex::new(EngineState::new(EngineConfig::default()))))
+/// Default per-token spacing of `onPriceUpdate` calls.
+const DEFAULT_PRICE_FEED_THROTTLE_MS: i64 = 250;
+
+fn get_engine() -> &'static EngineHandle {
+    ENGINE.get_or_init(|| {
+        EngineHandle::spawn(
+            EngineState::new(EngineConfig::default()),
+            ENGINE_SNAPSHOT_PUBLISH_MS,
+            dispatch_signals,
+            dispatch_opportunity_events,
+            dispatch_price_updates,
+        )
+    })
+}
+
+/// Run a mutation on the engine thread and wait for its result.
+fn with_engine<R, F>(f: F) -> Result<R>
+where
+    R: Send + 'static,
+    F: FnOnce(&mut EngineState) -> R + Send + 'static,
+{
+    get_engine().call(f).map_err(napi::Error::from_reason)
+}
+
+/// Forward executable signals from the engine thread to the executor (dropped
+/// if not initialized), then mirror every signal to Node.js (if subscribed) —
+/// the JS projection never delays the executor handoff.
+fn dispatch_signals(signals: Vec<ArbSignal>) {
+    let signals: Vec<Arc<ArbSignal>> = signals.into_iter().map(Arc::new).collect();
+    if let Some(tx) = EXECUTOR_TX.get() {
+        for signal in signals.iter().filter(|s| s.strategy.is_executable()) {
+            let _ = tx.try_send(Arc::clone(signal));
+        }
+    }
+    get_registry().emit_arb_signals(&signals);
+}
+
+/// Forward opportunity lifecycle events from the engine thread to Node.js (dropped if no callback).
+fn dispatch_opportunity_events(events: Vec<OpportunityEvent>) {
+    get_registry().emit_opportunity_events(events);
+}
+
+/// Forward throttled price feed updates from the engine thread to Node.js (dropped if no callback).
+fn dispatch_price_updates(updates: Vec<TopOfBookUpdate>) {
+    get_registry().emit_price_updates(updates);
 }
 
 /// Global tokio runtime for the socket engine.
@@ -49,7 +110,7 @@ static RUNTIME: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLo
 pub fn get_runtime() -> &'static tokio::runtime::Runtime {
     RUNTIME.get_or_init(|| {
         tokio::runtime::Builder::new_multi_thread()
-            .worker_threads(3) // Socket + Engine + Executor threads
+            .worker_threads(3) // Socket + Executor + periodic tasks (engine has its own thread)
             .thread_name("rust-core")
             .enable_all()
             .build()
@@ -61,7 +122,16 @@ pub fn get_runtime() -> &'static tokio::runtime::Runtime {
 static EXECUTOR_STATE: std::sync::OnceLock<Arc<ExecutorState>> = std::sync::OnceLock::new();
 
 /// Global executor signal sender (for dual-path dispatch).
-static EXECUTOR_TX: std::sync::OnceLock<mpsc::Sender<ArbSignal>> = std::sync::OnceLock::new();
+static EXECUTOR_TX: std::sync::OnceLock<mpsc::Sender<Arc<ArbSignal>>> = std::sync::OnceLock::new();
+
+/// Generation of the near-miss snapshot task — bumping it stops the running task.
+static NEAR_MISS_SNAPSHOT_GEN: AtomicU64 = AtomicU64::new(0);
+
+/// Engine snapshot file for warm restarts (written periodically and on shutdown).
+static ENGINE_SNAPSHOT_PATH: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+
+/// Generation of the periodic engine snapshot task.
+static ENGINE_SNAPSHOT_GEN: AtomicU64 = AtomicU64::new(0);
 
 // =============================================================================
 // N-API INPUT TYPES (JS → Rust)
@@ -78,6 +148,31 @@ pub struct NapiMarketDescriptorInput {
     pub bounds_upper: Option<f64>,
     pub kind: String,
     pub neg_risk: bool,
+    /// Taker fee rate [YES, NO] in bps; omitted = fee-free.
+    pub fee_rate_bps: Option<Vec<u32>>,
+}
+
+/// Neg-risk outcome market input from Node.js.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiNegRiskOutcomeInput {
+    pub market_id: String,
+    pub slug: String,
+    /// [YES, NO]
+    pub clob_token_ids: Vec<String>,
+    /// Taker fee rate [YES, NO] in bps; omitted = fee-free.
+    pub fee_rate_bps: Option<Vec<u32>>,
+}
+
+/// Neg-risk event input from Node.js — must list every outcome of the event.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiNegRiskEventInput {
+    /// Neg-risk market id shared by all outcomes.
+    pub event_id: String,
+    pub event_slug: String,
+    pub crypto: Option<String>,
+    pub outcomes: Vec<NapiNegRiskOutcomeInput>,
 }
 
 /// Group input from Node.js.
@@ -98,6 +193,72 @@ pub struct NapiEngineConfigInput {
     pub min_profit_bps: Option<f64>,
     pub min_profit_abs: Option<f64>,
     pub cooldown_ms: Option<i64>,
+    /// Re-emission of persistent opportunities: "cooldown" | "hysteresis".
+    pub emission_policy: Option<String>,
+    /// Hysteresis: edge gain in bps over the last emission that re-emits before the cooldown.
+    pub reemit_improvement_bps: Option<f64>,
+    /// Maximum leg quote age in ms (0 = disabled).
+    pub max_quote_age_ms: Option<i64>,
+    /// Per-strategy staleness overrides (negative = clear override).
+    pub triangle_max_quote_age_ms: Option<i64>,
+    pub complement_max_quote_age_ms: Option<i64>,
+    pub unbundle_max_quote_age_ms: Option<i64>,
+    pub bundle_max_quote_age_ms: Option<i64>,
+    pub ladder_buy_max_quote_age_ms: Option<i64>,
+    pub ladder_sell_max_quote_age_ms: Option<i64>,
+    pub monotone_max_quote_age_ms: Option<i64>,
+    pub parity_buy_max_quote_age_ms: Option<i64>,
+    pub parity_sell_max_quote_age_ms: Option<i64>,
+    pub neg_risk_yes_buy_max_quote_age_ms: Option<i64>,
+    pub neg_risk_no_buy_max_quote_age_ms: Option<i64>,
+    pub neg_risk_convert_max_quote_age_ms: Option<i64>,
+    /// Clock for staleness and cooldowns: "system" | "exchange".
+    pub quote_clock: Option<String>,
+    /// Strategy switches (unset = unchanged).
+    pub triangle_enabled: Option<bool>,
+    pub complement_enabled: Option<bool>,
+    pub unbundle_enabled: Option<bool>,
+    pub bundle_enabled: Option<bool>,
+    pub ladder_buy_enabled: Option<bool>,
+    pub ladder_sell_enabled: Option<bool>,
+    pub monotone_enabled: Option<bool>,
+    pub parity_buy_enabled: Option<bool>,
+    pub parity_sell_enabled: Option<bool>,
+    pub neg_risk_yes_buy_enabled: Option<bool>,
+    pub neg_risk_no_buy_enabled: Option<bool>,
+    /// Info-only: CONVERT signals reach `onArbSignal` but never the executor.
+    pub neg_risk_convert_enabled: Option<bool>,
+    /// Max range children per bracket (non-adjacent / multi-child parent pairs; 0 = off).
+    /// Changing it rebuilds every group.
+    pub max_bracket_children: Option<u32>,
+}
+
+/// Per-crypto / event-slug / group config override from Node.js.
+/// Exactly one of `crypto`, `event_slug_pattern`, `group_key` selects the groups;
+/// unset fields inherit from the layer below.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiEngineConfigOverride {
+    pub crypto: Option<String>,
+    /// Glob on event slug (`*` = any characters).
+    pub event_slug_pattern: Option<String>,
+    pub group_key: Option<String>,
+    pub min_profit_bps: Option<f64>,
+    pub min_profit_abs: Option<f64>,
+    pub cooldown_ms: Option<i64>,
+    pub max_quote_age_ms: Option<i64>,
+    pub triangle_enabled: Option<bool>,
+    pub complement_enabled: Option<bool>,
+    pub unbundle_enabled: Option<bool>,
+    pub bundle_enabled: Option<bool>,
+    pub ladder_buy_enabled: Option<bool>,
+    pub ladder_sell_enabled: Option<bool>,
+    pub monotone_enabled: Option<bool>,
+    pub parity_buy_enabled: Option<bool>,
+    pub parity_sell_enabled: Option<bool>,
+    pub neg_risk_yes_buy_enabled: Option<bool>,
+    pub neg_risk_no_buy_enabled: Option<bool>,
+    pub neg_risk_convert_enabled: Option<bool>,
 }
 
 /// Engine status output.
@@ -108,12 +269,356 @@ pub struct NapiEngineStatus {
     pub total_trios: i32,
     pub total_price_slots: i32,
     pub total_tokens_indexed: i32,
+    pub total_stale_skips: i64,
+    pub total_neg_risk_events: i32,
+}
+
+/// Delivery counters of the opt-in Node.js feeds (`onArbSignal` / `onPriceUpdate`).
+/// `*_dropped` counts calls rejected by a full callback queue.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiFeedStats {
+    pub arb_signals_sent: i64,
+    pub arb_signals_dropped: i64,
+    pub price_updates_sent: i64,
+    pub price_updates_dropped: i64,
+    pub price_feed_tokens: u32,
+    pub price_feed_throttle_ms: i64,
+}
+
+/// Live quote of one token (bid/ask absent until the first update).
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiQuoteInspection {
+    pub token_id: String,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub best_bid_size: f64,
+    pub best_ask_size: f64,
+    pub timestamp_ms: i64,
+}
+
+/// Market with live YES/NO quotes.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiMarketInspection {
+    pub market_id: String,
+    pub slug: String,
+    pub kind: String,
+    pub bounds_lower: Option<f64>,
+    pub bounds_upper: Option<f64>,
+    pub neg_risk: bool,
+    pub yes: NapiQuoteInspection,
+    pub no: NapiQuoteInspection,
+}
+
+/// Raw edge of one trio direction — reported even below threshold.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiEdgeInspection {
+    pub total_ask: f64,
+    pub total_bid: f64,
+    pub payout: f64,
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub meets_threshold: bool,
+}
+
+/// Trio edges + cooldown state.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiTrioInspection {
+    pub trio_index: i32,
+    pub parent_lower_market_id: String,
+    pub parent_upper_market_id: String,
+    pub range_market_id: String,
+    /// Absent while any triangle leg is unquoted.
+    pub triangle: Option<NapiEdgeInspection>,
+    /// Absent while any complement leg is unquoted.
+    pub complement: Option<NapiEdgeInspection>,
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_complement_ms: i64,
+    pub last_emitted_unbundle_ms: i64,
+    pub last_emitted_bundle_ms: i64,
+    pub stale_skip_count: i64,
+}
+
+/// Snapshot of one group as the engine sees it.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiGroupInspection {
+    pub group_key: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub children: Vec<NapiMarketInspection>,
+    pub parents: Vec<NapiMarketInspection>,
+    pub trios: Vec<NapiTrioInspection>,
+}
+
+/// Ranked structure edge from the near-miss scanner.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiNearMiss {
+    /// "triangle" | "complement" | "unbundle" | "bundle" | "ladder_buy" | "ladder_sell" | "monotone"
+    /// | "parity_buy" | "parity_sell" | "neg_risk_yes_buy" | "neg_risk_no_buy" | "neg_risk_convert"
+    pub strategy: String,
+    pub group_key: String,
+    pub event_slug: String,
+    pub crypto: String,
+    /// "trio" | "bracket" | "ladder" | "monotone" | "parity" | "neg_risk"
+    pub structure: String,
+    pub structure_index: i32,
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub last_update_ms: i64,
+    pub max_profit_abs: f64,
+    pub max_profit_bps: f64,
+    pub max_at_ms: i64,
+    pub time_above_zero_ms: i64,
+    pub samples: i64,
+}
+
+/// Market dropped from the structure, with the reason.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiRejectedMarket {
+    pub market_id: String,
+    /// "child" | "parent"
+    pub role: String,
+    pub reason: String,
+}
+
+/// Gap or overlap between consecutive range children.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiBoundIssue {
+    pub lower_market_id: String,
+    pub upper_market_id: String,
+    /// Upper bound of the lower child.
+    pub from: f64,
+    /// Lower bound of the upper child.
+    pub to: f64,
+}
+
+/// Token id shared by several markets of a group.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiDuplicateToken {
+    pub token_id: String,
+    pub market_ids: Vec<String>,
+}
+
+/// Parent that takes part in no trio.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiOrphanParent {
+    pub market_id: String,
+    pub reason: String,
+}
+
+/// Structure diagnostics of one group.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiGroupDiagnostics {
+    pub group_key: String,
+    pub trio_count: i32,
+    pub rejected_markets: Vec<NapiRejectedMarket>,
+    pub bound_gaps: Vec<NapiBoundIssue>,
+    pub bound_overlaps: Vec<NapiBoundIssue>,
+    pub duplicate_tokens: Vec<NapiDuplicateToken>,
+    pub parents_without_trio: Vec<NapiOrphanParent>,
+    pub notes: Vec<String>,
+}
+
+/// Result of `updateMarketStructure`: trio count plus per-group diagnostics.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiMarketStructureReport {
+    pub total_trios: i32,
+    pub groups: Vec<NapiGroupDiagnostics>,
+}
+
+/// Result of restoring an engine snapshot.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiRestoreResult {
+    pub groups: i32,
+    pub trios: i32,
+    pub neg_risk_events: i32,
+    pub prices_restored: i32,
+    pub prices_dropped_stale: i32,
+    pub cooldowns_restored: i32,
+    pub saved_at_ms: i64,
 }
 
 // =============================================================================
 // CONVERSION HELPERS
 // =============================================================================
 
+fn convert_config_override(input: NapiEngineConfigOverride) -> Result<ConfigOverride> {
+    let selector = match (input.crypto, input.event_slug_pattern, input.group_key) {
+        (Some(c), None, None) => OverrideSelector::Crypto(c),
+        (None, Some(p), None) => OverrideSelector::EventSlug(p),
+        (None, None, Some(k)) => OverrideSelector::GroupKey(k),
+        _ => {
+            return Err(napi::Error::from_reason(
+                "Config override needs exactly one of crypto, eventSlugPattern, groupKey",
+            ))
+        }
+    };
+
+    Ok(ConfigOverride {
+        selector,
+        min_profit_bps: input.min_profit_bps,
+        min_profit_abs: input.min_profit_abs,
+        cooldown_ms: input.cooldown_ms,
+        max_quote_age_ms: input.max_quote_age_ms,
+        strategy_enabled: [
+            input.triangle_enabled,
+            input.complement_enabled,
+            input.unbundle_enabled,
+            input.bundle_enabled,
+            input.ladder_buy_enabled,
+            input.ladder_sell_enabled,
+            input.monotone_enabled,
+            input.parity_buy_enabled,
+            input.parity_sell_enabled,
+            input.neg_risk_yes_buy_enabled,
+            input.neg_risk_no_buy_enabled,
+            input.neg_risk_convert_enabled,
+        ],
+    })
+}
+
+fn nan_to_none(v: f64) -> Option<f64> {
+    if v.is_nan() {
+        None
+    } else {
+        Some(v)
+    }
+}
+
+fn convert_quote_inspection(token_id: String, slot: &PriceSlot) -> NapiQuoteInspection {
+    NapiQuoteInspection {
+        token_id,
+        best_bid: nan_to_none(slot.best_bid),
+        best_ask: nan_to_none(slot.best_ask),
+        best_bid_size: slot.best_bid_size,
+        best_ask_size: slot.best_ask_size,
+        timestamp_ms: slot.timestamp_ms,
+    }
+}
+
+fn convert_edge_inspection(e: EdgeInspection) -> NapiEdgeInspection {
+    NapiEdgeInspection {
+        total_ask: e.total_ask,
+        total_bid: e.total_bid,
+        payout: e.payout,
+        profit_abs: e.profit_abs,
+        profit_bps: e.profit_bps,
+        meets_threshold: e.meets_threshold,
+    }
+}
+
+fn convert_group_inspection(g: GroupInspection) -> NapiGroupInspection {
+    let convert_market = |m: MarketInspection| NapiMarketInspection {
+        yes: convert_quote_inspection(m.yes_token, &m.yes),
+        no: convert_quote_inspection(m.no_token, &m.no),
+        market_id: m.market_id,
+        slug: m.slug,
+        kind: m.kind.to_string(),
+        bounds_lower: m.bounds_lower,
+        bounds_upper: m.bounds_upper,
+        neg_risk: m.neg_risk,
+    };
+
+    NapiGroupInspection {
+        group_key: g.group_key,
+        event_slug: g.event_slug,
+        crypto: g.crypto,
+        children: g.children.into_iter().map(convert_market).collect(),
+        parents: g.parents.into_iter().map(convert_market).collect(),
+        trios: g
+            .trios
+            .into_iter()
+            .map(|t| NapiTrioInspection {
+                trio_index: t.trio_index as i32,
+                parent_lower_market_id: t.parent_lower_market_id,
+                parent_upper_market_id: t.parent_upper_market_id,
+                range_market_id: t.range_market_id,
+                triangle: t.triangle.map(convert_edge_inspection),
+                complement: t.complement.map(convert_edge_inspection),
+                last_emitted_buy_ms: t.last_emitted_buy_ms,
+                last_emitted_complement_ms: t.last_emitted_complement_ms,
+                last_emitted_unbundle_ms: t.last_emitted_unbundle_ms,
+                last_emitted_bundle_ms: t.last_emitted_bundle_ms,
+                stale_skip_count: t.stale_skip_count as i64,
+            })
+            .collect(),
+    }
+}
+
+fn convert_near_miss(m: NearMiss) -> NapiNearMiss {
+    NapiNearMiss {
+        strategy: m.strategy.as_str().to_string(),
+        group_key: m.group_key,
+        event_slug: m.event_slug,
+        crypto: m.crypto,
+        structure: m.structure.to_string(),
+        structure_index: m.structure_index as i32,
+        profit_abs: m.profit_abs,
+        profit_bps: m.profit_bps,
+        last_update_ms: m.last_update_ms,
+        max_profit_abs: m.max_profit_abs,
+        max_profit_bps: m.max_profit_bps,
+        max_at_ms: m.max_at_ms,
+        time_above_zero_ms: m.time_above_zero_ms,
+        samples: m.samples as i64,
+    }
+}
+
+fn convert_group_diagnostics(group: &GroupState) -> NapiGroupDiagnostics {
+    let d = &group.diagnostics;
+    let bound_issue = |b: &BoundIssue| NapiBoundIssue {
+        lower_market_id: b.lower_market_id.clone(),
+        upper_market_id: b.upper_market_id.clone(),
+        from: b.from,
+        to: b.to,
+    };
+    NapiGroupDiagnostics {
+        group_key: group.group_key.clone(),
+        trio_count: group.trio_states.len() as i32,
+        rejected_markets: d
+            .rejected_markets
+            .iter()
+            .map(|r| NapiRejectedMarket {
+                market_id: r.market_id.clone(),
+                role: r.role.to_string(),
+                reason: r.reason.clone(),
+            })
+            .collect(),
+        bound_gaps: d.bound_gaps.iter().map(bound_issue).collect(),
+        bound_overlaps: d.bound_overlaps.iter().map(bound_issue).collect(),
+        duplicate_tokens: d
+            .duplicate_tokens
+            .iter()
+            .map(|t| NapiDuplicateToken {
+                token_id: t.token_id.clone(),
+                market_ids: t.market_ids.clone(),
+            })
+            .collect(),
+        parents_without_trio: d
+            .parents_without_trio
+            .iter()
+            .map(|p| NapiOrphanParent {
+                market_id: p.market_id.clone(),
+                reason: p.reason.clone(),
+            })
+            .collect(),
+        notes: d.notes.clone(),
+    }
+}
+
 fn convert_market_input(input: NapiMarketDescriptorInput) -> MarketDescriptorInput {
     MarketDescriptorInput {
         market_id: input.market_id,
@@ -123,6 +628,31 @@ fn convert_market_input(input: NapiMarketDescriptorInput) -> MarketDescriptorInp
         bounds_upper: input.bounds_upper,
         kind: input.kind,
         neg_risk: input.neg_risk,
+        fee_rate_bps: convert_fee_rates(input.fee_rate_bps),
+    }
+}
+
+/// [YES, NO] fee rates; missing entries are fee-free.
+fn convert_fee_rates(rates: Option<Vec<u32>>) -> [u32; 2] {
+    let rates = rates.unwrap_or_default();
+    [rates.first().copied().unwrap_or(0), rates.get(1).copied().unwrap_or(0)]
+}
+
+fn convert_neg_risk_event_input(input: NapiNegRiskEventInput) -> NegRiskEventInput {
+    NegRiskEventInput {
+        event_id: input.event_id,
+        event_slug: input.event_slug,
+        crypto: input.crypto.unwrap_or_default(),
+        outcomes: input
+            .outcomes
+            .into_iter()
+            .map(|o| NegRiskOutcomeInput {
+                market_id: o.market_id,
+                slug: o.slug,
+                clob_token_ids: o.clob_token_ids,
+                fee_rate_bps: convert_fee_rates(o.fee_rate_bps),
+            })
+            .collect(),
     }
 }
 
@@ -161,50 +691,23 @@ pub fn init_socket(config: SocketConfig) -> Result<()> {
     let runtime = get_runtime();
 
     runtime.block_on(async {
-        let (update_tx, mut update_rx) = mpsc::unbounded_channel::<TopOfBookUpdate>();
-
-        let manager = SocketManager::new(config, update_tx);
+        // All data stays in Rust: socket → engine thread → executor.
+        // The parser resolves token ids with the engine's interner and sends
+        // updates straight to the engine thread (no lock, no dispatcher task).
+        let engine = get_engine();
+        let manager = SocketManager::new(config, engine.update_sender(), engine.interner());
 
         // Store manager globally
         let mut mgr = get_manager().lock().await;
         *mgr = Some(manager);
-
-        // Spawn engine dispatcher task
-        // All data stays in Rust: socket → engine → executor
-        // No callbacks to Node.js on this path (only on_trade_result from executor)
-        let engine = get_engine().clone();
-
-        tokio::spawn(async move {
-            while let Some(update) = update_rx.recv().await {
-                let signals = {
-                    let mut eng = engine.lock().unwrap();
-                    eng.handle_top_of_book(
-                        &update.asset_id,
-                        update.best_bid,
-                        update.best_ask,
-                        update.best_bid_size,
-                        update.best_ask_size,
-                        update.timestamp_ms,
-                    )
-                };
-
-                // Dispatch signals to executor
-                if !signals.is_empty() {
-                    if let Some(tx) = EXECUTOR_TX.get() {
-                        for signal in signals {
-                            let _ = tx.try_send(signal);
-                        }
-                    }
-                }
-            }
-        });
     });
 
     info!("Rust socket engine initialized");
     Ok(())
 }
 
-// on_top_of_book removed — entire data flow stays in Rust.
+// on_top_of_book removed — entire data flow stays in Rust; watched prices
+// reach Node.js only through the throttled `on_price_update` feed.
 
 /// Subscribe to a list of token IDs.
 #[napi]
@@ -282,6 +785,14 @@ pub fn shutdown_socket() -> Result<()> {
         *mgr = None;
     });
 
+    // Final snapshot for warm restart
+    let snapshot_path = ENGINE_SNAPSHOT_PATH.lock().unwrap().clone();
+    if let Some(path) = snapshot_path {
+        if let Err(e) = save_engine_snapshot_to(&path) {
+            tracing::warn!("Engine snapshot on shutdown failed: {}", e);
+        }
+    }
+
     info!("Rust socket engine shut down");
     Ok(())
 }
@@ -293,49 +804,258 @@ pub fn shutdown_socket() -> Result<()> {
 /// Update the market structure (groups, markets, trios).
 ///
 /// Called by Node.js when markets are discovered/refreshed.
-/// Rebuilds PriceTable, groups, and trio indices.
-/// Returns the total number of trios created.
+/// Reconciles groups incrementally: groups missing from the input are removed,
+/// the rest are upserted, and prices of unchanged tokens survive the refresh.
+/// Returns the total number of trios and a diagnostics report per group
+/// (rejected markets, bound gaps/overlaps, duplicate tokens, orphan parents).
 #[napi]
-pub fn update_market_structure(groups: Vec<NapiRangeGroupInput>) -> Result<i32> {
+pub fn update_market_structure(
+    groups: Vec<NapiRangeGroupInput>,
+) -> Result<NapiMarketStructureReport> {
     let converted: Vec<RangeGroupInput> = groups.into_iter().map(convert_group_input).collect();
 
-    let trio_count = {
-        let mut engine = get_engine().lock().unwrap();
-        engine.update_market_structure(converted)
-    };
+    let (report, flagged, removed) = with_engine(move |engine| {
+        let before: Vec<String> = engine.group_key_index.keys().cloned().collect();
+        let total_trios = engine.update_market_structure(converted);
+        let report = NapiMarketStructureReport {
+            total_trios,
+            groups: engine.groups.iter().map(|g| convert_group_diagnostics(g)).collect(),
+        };
+        let flagged = engine.groups.iter().filter(|g| !g.diagnostics.is_clean()).count();
+        let removed: Vec<String> = before
+            .into_iter()
+            .filter(|key| !engine.group_key_index.contains_key(key))
+            .collect();
+        (report, flagged, removed)
+    })?;
+    for group_key in &removed {
+        forget_executor_group(group_key);
+    }
+
+    info!(
+        "Market structure updated: {} trios ({} groups with diagnostics)",
+        report.total_trios, flagged
+    );
+    Ok(report)
+}
+
+/// Replace the set of neg-risk events the engine tracks.
+///
+/// Each event must list every outcome; events with fewer than two outcomes or
+/// an outcome missing a token id are skipped. Outcome tokens still need to be
+/// subscribed on the socket. Returns the number of events tracked.
+#[napi]
+pub fn update_neg_risk_events(events: Vec<NapiNegRiskEventInput>) -> Result<i32> {
+    let requested = events.len();
+    let converted: Vec<NegRiskEventInput> =
+        events.into_iter().map(convert_neg_risk_event_input).collect();
+    let tracked = with_engine(move |engine| engine.update_neg_risk_events(converted))?;
+
+    info!(
+        "Neg-risk events updated: {} tracked ({} skipped)",
+        tracked,
+        requested - tracked as usize
+    );
+    Ok(tracked)
+}
+
+/// Insert or replace a single group by `group_key`.
+///
+/// Only this group's trios and token indices are rebuilt; prices of tokens
+/// that remain in the group are kept. Returns the group's trio count.
+#[napi]
+pub fn upsert_market_group(group: NapiRangeGroupInput) -> Result<i32> {
+    let group_key = group.group_key.clone();
+    let input = convert_group_input(group);
+    let trio_count = with_engine(move |engine| engine.upsert_group(input))?;
+
+    info!("Market group upserted: {} ({} trios)", group_key, trio_count);
+    Ok(trio_count)
+}
+
+/// Remove a group by `group_key`, reclaiming slots no other group uses.
+/// Returns false if the group was unknown.
+#[napi]
+pub fn remove_market_group(group_key: String) -> Result<bool> {
+    let key = group_key.clone();
+    let removed = with_engine(move |engine| engine.remove_group(&key))?;
+
+    if removed {
+        forget_executor_group(&group_key);
+        info!("Market group removed: {}", group_key);
+    }
+    Ok(removed)
+}
+
+/// Drop the executor's lock and cooldown for a group the engine no longer tracks.
+fn forget_executor_group(group_key: &str) {
+    if let Some(state) = EXECUTOR_STATE.get() {
+        state.validation.forget_group(group_key);
+    }
+}
+
+/// Add markets to an existing group (markets with a known `market_id` are replaced).
+/// Returns the group's new trio count.
+#[napi]
+pub fn add_group_markets(
+    group_key: String,
+    children: Vec<NapiMarketDescriptorInput>,
+    parents: Vec<NapiMarketDescriptorInput>,
+) -> Result<i32> {
+    let children = children.into_iter().map(convert_market_input).collect();
+    let parents = parents.into_iter().map(convert_market_input).collect();
+
+    let key = group_key.clone();
+    let trio_count = with_engine(move |engine| engine.add_markets(&key, children, parents))?
+        .ok_or_else(|| napi::Error::from_reason(format!("Unknown group: {}", group_key)))?;
+
+    info!("Markets added to {} ({} trios)", group_key, trio_count);
+    Ok(trio_count)
+}
+
+/// Retire markets from an existing group by `market_id`.
+/// Returns the group's new trio count.
+#[napi]
+pub fn retire_group_markets(group_key: String, market_ids: Vec<String>) -> Result<i32> {
+    let key = group_key.clone();
+    let trio_count = with_engine(move |engine| engine.retire_markets(&key, &market_ids))?
+        .ok_or_else(|| napi::Error::from_reason(format!("Unknown group: {}", group_key)))?;
 
-    info!("Market structure updated: {} trios", trio_count);
+    info!("Markets retired from {} ({} trios)", group_key, trio_count);
     Ok(trio_count)
 }
 
 // on_signal removed — signals are dispatched directly to Rust executor.
 
-/// Update engine configuration (profit thresholds, cooldown).
+/// Update engine configuration (profit thresholds, cooldown, quote staleness, strategy switches).
 #[napi]
 pub fn update_engine_config(config: NapiEngineConfigInput) -> Result<()> {
-    let mut engine = get_engine().lock().unwrap();
+    let clock = match config.quote_clock {
+        Some(ref clock) => Some(EngineClock::parse(clock).ok_or_else(|| {
+            napi::Error::from_reason(format!("Unknown quote clock: {}", clock))
+        })?),
+        None => None,
+    };
+    let emission_policy = match config.emission_policy {
+        Some(ref policy) => Some(EmissionPolicy::parse(policy).ok_or_else(|| {
+            napi::Error::from_reason(format!("Unknown emission policy: {}", policy))
+        })?),
+        None => None,
+    };
 
-    if let Some(v) = config.min_profit_bps {
-        engine.config.min_profit_bps = v;
-    }
-    if let Some(v) = config.min_profit_abs {
-        engine.config.min_profit_abs = v;
-    }
-    if let Some(v) = config.cooldown_ms {
-        engine.config.cooldown_ms = v;
-    }
+    let updated = with_engine(move |engine| {
+        if let Some(v) = config.min_profit_bps {
+            engine.config.min_profit_bps = v;
+        }
+        if let Some(v) = config.min_profit_abs {
+            engine.config.min_profit_abs = v;
+        }
+        if let Some(v) = config.cooldown_ms {
+            engine.config.cooldown_ms = v;
+        }
+        if let Some(policy) = emission_policy {
+            engine.config.emission_policy = policy;
+        }
+        if let Some(v) = config.reemit_improvement_bps {
+            engine.config.reemit_improvement_bps = v;
+        }
+        if let Some(v) = config.max_quote_age_ms {
+            engine.config.max_quote_age_ms = v;
+        }
+
+        let overrides = [
+            (StrategyKind::Triangle, config.triangle_max_quote_age_ms),
+            (StrategyKind::Complement, config.complement_max_quote_age_ms),
+            (StrategyKind::Unbundle, config.unbundle_max_quote_age_ms),
+            (StrategyKind::Bundle, config.bundle_max_quote_age_ms),
+            (StrategyKind::LadderBuy, config.ladder_buy_max_quote_age_ms),
+            (StrategyKind::LadderSell, config.ladder_sell_max_quote_age_ms),
+            (StrategyKind::Monotone, config.monotone_max_quote_age_ms),
+            (StrategyKind::ParityBuy, config.parity_buy_max_quote_age_ms),
+            (StrategyKind::ParitySell, config.parity_sell_max_quote_age_ms),
+            (StrategyKind::NegRiskYesBuy, config.neg_risk_yes_buy_max_quote_age_ms),
+            (StrategyKind::NegRiskNoBuy, config.neg_risk_no_buy_max_quote_age_ms),
+            (StrategyKind::NegRiskConvert, config.neg_risk_convert_max_quote_age_ms),
+        ];
+        for (strategy, value) in overrides {
+            if let Some(v) = value {
+                engine.config.strategy_max_quote_age_ms[strategy.index()] =
+                    if v < 0 { None } else { Some(v) };
+            }
+        }
+
+        let switches = [
+            (StrategyKind::Triangle, config.triangle_enabled),
+            (StrategyKind::Complement, config.complement_enabled),
+            (StrategyKind::Unbundle, config.unbundle_enabled),
+            (StrategyKind::Bundle, config.bundle_enabled),
+            (StrategyKind::LadderBuy, config.ladder_buy_enabled),
+            (StrategyKind::LadderSell, config.ladder_sell_enabled),
+            (StrategyKind::Monotone, config.monotone_enabled),
+            (StrategyKind::ParityBuy, config.parity_buy_enabled),
+            (StrategyKind::ParitySell, config.parity_sell_enabled),
+            (StrategyKind::NegRiskYesBuy, config.neg_risk_yes_buy_enabled),
+            (StrategyKind::NegRiskNoBuy, config.neg_risk_no_buy_enabled),
+            (StrategyKind::NegRiskConvert, config.neg_risk_convert_enabled),
+        ];
+        for (strategy, value) in switches {
+            if let Some(enabled) = value {
+                engine.config.strategy_enabled[strategy.index()] = enabled;
+            }
+        }
+
+        if let Some(clock) = clock {
+            engine.config.clock = clock;
+        }
+
+        let previous_brackets = engine.config.max_bracket_children;
+        if let Some(v) = config.max_bracket_children {
+            engine.config.max_bracket_children = v.min(u16::MAX as u32) as u16;
+        }
+
+        engine.refresh_group_configs();
+        if engine.config.max_bracket_children != previous_brackets {
+            engine.rebuild_groups();
+        }
+        engine.config
+    })?;
 
     info!(
-        "Engine config updated: bps={}, abs={}, cooldown={}ms",
-        engine.config.min_profit_bps, engine.config.min_profit_abs, engine.config.cooldown_ms
+        "Engine config updated: bps={}, abs={}, cooldown={}ms, emission={:?} (+{}bps), max_quote_age={}ms, clock={:?}, enabled={:?}, max_bracket_children={}",
+        updated.min_profit_bps,
+        updated.min_profit_abs,
+        updated.cooldown_ms,
+        updated.emission_policy,
+        updated.reemit_improvement_bps,
+        updated.max_quote_age_ms,
+        updated.clock,
+        updated.strategy_enabled,
+        updated.max_bracket_children
     );
     Ok(())
 }
 
+/// Replace all per-crypto / event-slug / group config overrides.
+/// Precedence: global < crypto < event slug pattern < group key.
+#[napi]
+pub fn set_engine_config_overrides(overrides: Vec<NapiEngineConfigOverride>) -> Result<()> {
+    let overrides = overrides
+        .into_iter()
+        .map(convert_config_override)
+        .collect::<Result<Vec<_>>>()?;
+
+    let count = overrides.len();
+    with_engine(move |engine| engine.set_config_overrides(overrides))?;
+
+    info!("Engine config overrides set: {}", count);
+    Ok(())
+}
+
 /// Get engine status (group/trio counts, price slot count).
+/// Reads the published snapshot — never waits on the engine thread.
 #[napi]
 pub fn get_engine_status() -> Result<NapiEngineStatus> {
-    let engine = get_engine().lock().unwrap();
+    let engine = get_engine().snapshot();
 
     let total_trios: i32 = engine
         .groups
@@ -343,15 +1063,278 @@ pub fn get_engine_status() -> Result<NapiEngineStatus> {
         .map(|g| g.trio_states.len() as i32)
         .sum();
 
+    let total_stale_skips: u64 = engine
+        .groups
+        .iter()
+        .map(|g| {
+            g.trio_states.iter().map(|t| t.stale_skip_count).sum::<u64>()
+                + g.bracket_states.iter().map(|b| b.stale_skip_count).sum::<u64>()
+                + g.ladder.as_ref().map_or(0, |l| l.stale_skip_count)
+                + g.monotone_pairs.iter().map(|p| p.stale_skip_count).sum::<u64>()
+                + g.parity_states.iter().map(|p| p.stale_skip_count).sum::<u64>()
+        })
+        .chain(engine.neg_risk_events.iter().map(|e| e.stale_skip_count))
+        .sum();
+
     Ok(NapiEngineStatus {
         total_groups: engine.groups.len() as i32,
         total_trios,
-        total_price_slots: engine.price_table.slots.len() as i32,
-        total_tokens_indexed: engine.token_index.len() as i32,
+        total_price_slots: engine.price_table.active_slots() as i32,
+        total_tokens_indexed: engine.indexed_token_count() as i32,
+        total_stale_skips: total_stale_skips as i64,
+        total_neg_risk_events: engine.neg_risk_events.len() as i32,
     })
 }
 
 
+/// Inspect what the engine currently believes: markets, live leg quotes, and each
+/// trio's triangle/complement edge (even below threshold) with cooldown timestamps.
+/// Pass a group key to restrict the result to one group.
+#[napi]
+pub fn inspect_engine(group_key: Option<String>) -> Result<Vec<NapiGroupInspection>> {
+    let groups = inspect::inspect_engine(&get_engine().snapshot(), group_key.as_deref());
+    Ok(groups.into_iter().map(convert_group_inspection).collect())
+}
+
+/// Top-N structures by current edge (negative included), per strategy.
+/// `strategy` = "triangle" | "complement" | "unbundle" | "bundle" | "ladder_buy" | "ladder_sell"
+/// | "monotone" | "parity_buy" | "parity_sell" | "neg_risk_yes_buy" | "neg_risk_no_buy"
+/// | "neg_risk_convert"; omit for all.
+#[napi]
+pub fn get_near_misses(strategy: Option<String>, top_n: Option<u32>) -> Result<Vec<NapiNearMiss>> {
+    let n = top_n.unwrap_or(10) as usize;
+    let strategy = match strategy {
+        Some(ref s) => Some(
+            StrategyKind::parse(s)
+                .ok_or_else(|| napi::Error::from_reason(format!("Unknown strategy: {}", s)))?,
+        ),
+        None => None,
+    };
+
+    let engine = get_engine().snapshot();
+    let now_ms = engine.now_ms();
+    let ranked = match strategy {
+        Some(kind) => scanner::top_near_misses(&engine, kind, n, now_ms),
+        None => scanner::top_near_misses_all(&engine, n, now_ms),
+    };
+    Ok(ranked.into_iter().map(convert_near_miss).collect())
+}
+
+/// Clear running edge statistics (max edge, time above zero) on every structure.
+#[napi]
+pub fn reset_near_miss_stats() -> Result<()> {
+    with_engine(scanner::reset_edge_stats)
+}
+
+/// Most recently closed opportunities, oldest first — open time, close time,
+/// peak edge and edge at close. `limit` keeps only the newest ones.
+#[napi]
+pub fn get_recent_opportunities(limit: Option<u32>) -> Result<Vec<OpportunityEvent>> {
+    let engine = get_engine().snapshot();
+    let log = &engine.closed_opportunities;
+    let skip = limit.map_or(0, |n| log.len().saturating_sub(n as usize));
+    Ok(log.iter().skip(skip).cloned().collect())
+}
+
+/// Register callback for opportunity lifecycle events (opened / updated / closed).
+#[napi(ts_args_type = "callback: (event: OpportunityEvent) => void")]
+pub fn on_opportunity_event(callback: JsFunction) -> Result<()> {
+    let tsfn: ThreadsafeFunction<OpportunityEvent, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+    get_registry().set_on_opportunity_event(tsfn);
+
+    info!("onOpportunityEvent callback registered");
+    Ok(())
+}
+
+/// Register callback for every signal the engine emits, including ones the
+/// executor skips. Bounded queue: signals are dropped while JS falls behind.
+#[napi(ts_args_type = "callback: (signal: ArbSignal) => void")]
+pub fn on_arb_signal(callback: JsFunction) -> Result<()> {
+    let tsfn: ThreadsafeFunction<FlatArbSignal, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(FEED_QUEUE_CAP, |ctx| Ok(vec![ctx.value]))?;
+    get_registry().set_on_arb_signal(tsfn);
+
+    info!("onArbSignal callback registered");
+    Ok(())
+}
+
+/// Register callback for the throttled price feed (tokens chosen with
+/// `setPriceFeedTokens`). Bounded queue: updates are dropped while JS falls behind.
+#[napi(ts_args_type = "callback: (update: TopOfBookUpdate) => void")]
+pub fn on_price_update(callback: JsFunction) -> Result<()> {
+    let tsfn: ThreadsafeFunction<TopOfBookUpdate, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(FEED_QUEUE_CAP, |ctx| Ok(vec![ctx.value]))?;
+    get_registry().set_on_price_update(tsfn);
+
+    info!("onPriceUpdate callback registered");
+    Ok(())
+}
+
+/// Choose the tokens sent to `onPriceUpdate`, each at most once per `throttle_ms`
+/// (default 250) at its latest top of book. An empty list turns the feed off.
+#[napi]
+pub fn set_price_feed_tokens(token_ids: Vec<String>, throttle_ms: Option<i64>) -> Result<()> {
+    let throttle_ms = throttle_ms.unwrap_or(DEFAULT_PRICE_FEED_THROTTLE_MS);
+    if throttle_ms < 0 {
+        return Err(napi::Error::from_reason("throttle_ms must be >= 0"));
+    }
+    let count = token_ids.len();
+    with_engine(move |engine| engine.set_price_feed(&token_ids, throttle_ms))?;
+
+    info!("Price feed: {} tokens, throttle {}ms", count, throttle_ms);
+    Ok(())
+}
+
+/// Delivery counters of the `onArbSignal` / `onPriceUpdate` feeds.
+#[napi]
+pub fn get_feed_stats() -> Result<NapiFeedStats> {
+    let registry = get_registry();
+    let engine = get_engine().snapshot();
+    let load = |c: &AtomicU64| c.load(Ordering::Relaxed) as i64;
+    Ok(NapiFeedStats {
+        arb_signals_sent: load(&registry.arb_signal_counters.sent),
+        arb_signals_dropped: load(&registry.arb_signal_counters.dropped),
+        price_updates_sent: load(&registry.price_update_counters.sent),
+        price_updates_dropped: load(&registry.price_update_counters.dropped),
+        price_feed_tokens: engine.price_feed.len() as u32,
+        price_feed_throttle_ms: engine.price_feed.throttle_ms,
+    })
+}
+
+/// Log the top-N near misses per strategy every `interval_ms` (0 = stop).
+#[napi]
+pub fn set_near_miss_snapshot_interval(interval_ms: i64, top_n: Option<u32>) -> Result<()> {
+    let gen = NEAR_MISS_SNAPSHOT_GEN.fetch_add(1, Ordering::SeqCst) + 1;
+    if interval_ms <= 0 {
+        info!("Near-miss snapshots disabled");
+        return Ok(());
+    }
+
+    let n = top_n.unwrap_or(5) as usize;
+    let interval = std::time::Duration::from_millis(interval_ms as u64);
+    get_runtime().spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await; // first tick fires immediately
+        loop {
+            ticker.tick().await;
+            if NEAR_MISS_SNAPSHOT_GEN.load(Ordering::SeqCst) != gen {
+                break;
+            }
+            let ranked = {
+                let engine = get_engine().snapshot();
+                scanner::top_near_misses_all(&engine, n, engine.now_ms())
+            };
+            for m in ranked {
+                info!(
+                    "[NearMiss] {} {} {}={} edge={:.1}bps (${:.4}) max={:.1}bps above0={}ms",
+                    m.strategy.as_str(),
+                    m.group_key,
+                    m.structure,
+                    m.structure_index,
+                    m.profit_bps,
+                    m.profit_abs,
+                    m.max_profit_bps,
+                    m.time_above_zero_ms
+                );
+            }
+        }
+    });
+
+    info!("Near-miss snapshots every {}ms (top {})", interval_ms, n);
+    Ok(())
+}
+
+fn save_engine_snapshot_to(path: &Path) -> std::result::Result<(), String> {
+    let snapshot = {
+        let engine = get_engine().snapshot();
+        persistence::capture(&engine, engine.now_ms())
+    };
+    persistence::save_to_file(path, &snapshot)
+}
+
+/// Save the engine state (structure, neg-risk events, quotes, cooldowns) to a versioned snapshot file.
+#[napi]
+pub fn save_engine_snapshot(path: String) -> Result<()> {
+    save_engine_snapshot_to(Path::new(&path)).map_err(napi::Error::from_reason)?;
+    info!("Engine snapshot saved: {}", path);
+    Ok(())
+}
+
+/// Restore engine state from a snapshot file, replacing the current structure
+/// and neg-risk events.
+///
+/// Quotes older than `max_price_age_ms` are dropped (default: the engine's
+/// `max_quote_age_ms`; 0 keeps all). Kept quotes retain their original timestamps.
+#[napi]
+pub fn restore_engine_snapshot(
+    path: String,
+    max_price_age_ms: Option<i64>,
+) -> Result<NapiRestoreResult> {
+    let snapshot =
+        persistence::load_from_file(Path::new(&path)).map_err(napi::Error::from_reason)?;
+    let saved_at_ms = snapshot.saved_at_ms;
+
+    let stats = with_engine(move |engine| {
+        let now_ms = engine.now_ms();
+        let max_age = max_price_age_ms.unwrap_or(engine.config.max_quote_age_ms);
+        persistence::restore(engine, snapshot, now_ms, max_age)
+    })?;
+
+    info!(
+        "Engine snapshot restored: {} groups, {} trios, {} neg-risk events, {} prices ({} stale dropped), {} cooldowns",
+        stats.groups,
+        stats.trios,
+        stats.neg_risk_events,
+        stats.prices_restored,
+        stats.prices_dropped_stale,
+        stats.cooldowns_restored
+    );
+    Ok(NapiRestoreResult {
+        groups: stats.groups as i32,
+        trios: stats.trios as i32,
+        neg_risk_events: stats.neg_risk_events as i32,
+        prices_restored: stats.prices_restored as i32,
+        prices_dropped_stale: stats.prices_dropped_stale as i32,
+        cooldowns_restored: stats.cooldowns_restored as i32,
+        saved_at_ms,
+    })
+}
+
+/// Configure automatic engine snapshots: saved to `path` every `interval_ms`
+/// (0/unset = only on shutdown). A null path disables automatic snapshots.
+#[napi]
+pub fn configure_engine_snapshots(path: Option<String>, interval_ms: Option<i64>) -> Result<()> {
+    let gen = ENGINE_SNAPSHOT_GEN.fetch_add(1, Ordering::SeqCst) + 1;
+    let path = path.map(PathBuf::from);
+    *ENGINE_SNAPSHOT_PATH.lock().unwrap() = path.clone();
+
+    let (path, interval_ms) = match (path, interval_ms) {
+        (Some(p), Some(ms)) if ms > 0 => (p, ms),
+        (p, _) => {
+            info!("Engine snapshots: path={:?}, periodic=off", p);
+            return Ok(());
+        }
+    };
+
+    let interval = std::time::Duration::from_millis(interval_ms as u64);
+    get_runtime().spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await; // first tick fires immediately
+        loop {
+            ticker.tick().await;
+            if ENGINE_SNAPSHOT_GEN.load(Ordering::SeqCst) != gen {
+                break;
+            }
+            if let Err(e) = save_engine_snapshot_to(&path) {
+                tracing::warn!("Periodic engine snapshot failed: {}", e);
+            }
+        }
+    });
+
+    info!("Engine snapshots every {}ms", interval_ms);
+    Ok(())
+}
 
 // =============================================================================
 // EXECUTOR N-API FUNCTIONS
@@ -365,7 +1348,9 @@ pub fn get_engine_status() -> Result<NapiEngineStatus> {
 pub fn init_executor(config: NapiExecutorConfigInput) -> Result<()> {
     info!("Initializing Rust executor...");
 
-    let state = ExecutorState::from_config(&config)
+    // Paper fills read the latest engine snapshot
+    let quotes: QuoteSource = Arc::new(|token_id: &str| get_engine().snapshot().quote(token_id));
+    let state = ExecutorState::from_config(&config, quotes)
         .map_err(|e| napi::Error::from_reason(format!("Failed to init executor: {}", e)))?;
 
     let state = Arc::new(state);
@@ -381,7 +1366,10 @@ pub fn init_executor(config: NapiExecutorConfigInput) -> Result<()> {
         .set(tx)
         .map_err(|_| napi::Error::from_reason("Executor channel already created"))?;
 
-    info!("Rust executor initialized (wallet cached, HTTP client ready)");
+    info!(
+        "Rust executor initialized (wallet cached, HTTP client ready{})",
+        if config.paper_trading.unwrap_or(false) { ", PAPER MODE" } else { "" }
+    );
     Ok(())
 }
 
@@ -440,6 +1428,25 @@ pub fn update_minted_assets(group_key: String, assets: Vec<NapiMintedAssetEntry>
     Ok(())
 }
 
+/// Paper-mode positions and PnL (None unless the executor runs in paper mode).
+#[napi]
+pub fn get_paper_portfolio() -> Result<Option<NapiPaperPortfolio>> {
+    Ok(EXECUTOR_STATE
+        .get()
+        .and_then(|state| state.paper.as_ref())
+        .map(|paper| paper.portfolio()))
+}
+
+/// Clear paper positions and PnL.
+#[napi]
+pub fn reset_paper_portfolio() -> Result<()> {
+    if let Some(paper) = EXECUTOR_STATE.get().and_then(|state| state.paper.as_ref()) {
+        paper.reset();
+        info!("Paper portfolio reset");
+    }
+    Ok(())
+}
+
 /// Minted asset entry input from Node.js.
 #[napi(object)]
 #[derive(Debug, Clone)]
@@ -452,7 +1459,7 @@ pub struct NapiMintedAssetEntry {
 // BATCH ORDER API — Manual order placement via Rust Core
 // =============================================================================
 
-use crate::types::order::{NapiBatchOrderInput, NapiBatchOrderResult, NapiBatchOrderResultItem, NapiBatchOrderConfig, OrderToSign};
+use crate::types::order::{NapiBatchOrderInput, NapiBatchOrderResult, NapiBatchOrderResultItem, NapiBatchOrderConfig, OrderToSign, OrderType};
 
 /// Place batch orders using Rust Core (signer + API client).
 ///
@@ -471,7 +1478,7 @@ pub fn place_batch_orders_rust(
 
     runtime.block_on(async {
         place_batch_orders_rust_inner(config, orders).await
-    }).map_err(|e| napi::Error::from_reason(e))
+    }).map_err(napi::Error::from_reason)
 }
 
 async fn place_batch_orders_rust_inner(
@@ -517,6 +1524,10 @@ async fn place_batch_orders_rust_inner(
     let decimals: f64 = 1_000_000.0;
     let mut orders_to_sign = Vec::with_capacity(orders.len());
     let mut order_types = Vec::with_capacity(orders.len());
+    let now_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
 
     for input in &orders {
         let side: u8 = if input.side == "SELL" { 1 } else { 0 };
@@ -562,6 +1573,9 @@ async fn place_batch_orders_rust_inner(
             side,
             neg_risk,
             fee_rate_bps,
+            // GTD lifetime follows the executor's order type policy
+            expiration: OrderType::parse(&order_type)
+                .map_or(0, |t| state.config.order_types.expiration(t, now_secs)),
         });
 
         order_types.push(order_type);
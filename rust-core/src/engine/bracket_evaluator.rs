@@ -0,0 +1,334 @@
+//! Bracket evaluator — N-leg Triangle + Complement over multi-child brackets.
+//!
+//! Triangle BUY:   Parent[lower] YES + Parent[upper] NO + every child NO. Payout = children + 1.
+//! Complement BUY: Parent[lower] NO  + every child YES + Parent[upper] YES. Payout = 1.
+//! Emits the same `POLYMARKET_TRIANGLE_BUY` / `POLYMARKET_COMPLEMENT_BUY` strategies as
+//! trios, with one leg per child.
+//! Profit is net of each leg's taker fee.
+
+use crate::engine::state::{
+    taker_fee, BracketState, EngineConfig, GroupState, OpportunitySite, PriceTable,
+};
+use crate::engine::trio_evaluator::meets_threshold;
+use crate::types::order::OrderSide;
+use crate::types::signal::{ArbSignal, LegRole};
+use crate::types::strategy::StrategyKind;
+
+/// Internal profit result from bracket calculation.
+pub struct BracketProfit {
+    pub total_ask: f64,
+    pub total_bid: f64,
+    /// Net of taker fees.
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
+    pub payout: f64,
+}
+
+/// Leg layout of a bracket for `strategy`: (parent lower slot, parent upper slot,
+/// child side), where the side indexes each child's `clob_token_ids` (0 = YES, 1 = NO).
+#[inline(always)]
+fn leg_layout(group: &GroupState, b: &BracketState, strategy: StrategyKind) -> Option<(u32, u32, usize)> {
+    let lower = &group.parent_metas[b.parent_lower_idx as usize];
+    let upper = &group.parent_metas[b.parent_upper_idx as usize];
+    match strategy {
+        StrategyKind::Triangle => Some((lower.yes_slot, upper.no_slot, 1)),
+        StrategyKind::Complement => Some((lower.no_slot, upper.yes_slot, 0)),
+        _ => None,
+    }
+}
+
+/// Taker fee rates of the parent lower / upper legs for child side `side`:
+/// the lower parent trades the opposite token, the upper parent the same one.
+#[inline(always)]
+fn parent_fee_rates(group: &GroupState, b: &BracketState, side: usize) -> (u32, u32) {
+    (
+        group.parent_metas[b.parent_lower_idx as usize].fee_rate_bps[1 - side],
+        group.parent_metas[b.parent_upper_idx as usize].fee_rate_bps[side],
+    )
+}
+
+#[inline(always)]
+fn child_slot(group: &GroupState, child_idx: u16, side: usize) -> u32 {
+    let meta = &group.child_metas[child_idx as usize];
+    if side == 0 {
+        meta.yes_slot
+    } else {
+        meta.no_slot
+    }
+}
+
+/// Raw bracket edge — no threshold applied. `None` when a leg has no quote
+/// or `strategy` is not Triangle / Complement.
+pub fn calc_bracket_edge(
+    group: &GroupState,
+    b: &BracketState,
+    pt: &PriceTable,
+    strategy: StrategyKind,
+) -> Option<BracketProfit> {
+    let (lower_slot, upper_slot, side) = leg_layout(group, b, strategy)?;
+    let (lower_fee, upper_fee) = parent_fee_rates(group, b, side);
+
+    let mut total_ask = 0.0;
+    let mut total_bid = 0.0;
+    let mut fees = 0.0;
+    let legs = [(lower_slot, lower_fee), (upper_slot, upper_fee)].into_iter().chain(
+        b.child_idxs
+            .iter()
+            .map(|&c| (child_slot(group, c, side), group.child_metas[c as usize].fee_rate_bps[side])),
+    );
+    for (slot, fee_rate_bps) in legs {
+        let q = pt.get(slot);
+        // Any NaN → not enough data
+        if q.best_ask.is_nan() || q.best_bid.is_nan() {
+            return None;
+        }
+        total_ask += q.best_ask;
+        total_bid += q.best_bid;
+        fees += taker_fee(q.best_ask, fee_rate_bps);
+    }
+
+    let payout = match strategy {
+        StrategyKind::Triangle => b.child_idxs.len() as f64 + 1.0,
+        _ => 1.0,
+    };
+    let gross_profit_abs = payout - total_ask;
+    let profit_abs = gross_profit_abs - fees;
+
+    Some(BracketProfit {
+        total_ask,
+        total_bid,
+        profit_abs,
+        profit_bps: (profit_abs / total_ask) * 10000.0,
+        gross_profit_abs,
+        gross_profit_bps: (gross_profit_abs / total_ask) * 10000.0,
+        payout,
+    })
+}
+
+/// Evaluate one bracket: calc profit → check staleness → check cooldown → build signal.
+pub fn evaluate_bracket(
+    group: &mut GroupState,
+    bracket_idx: usize,
+    pt: &PriceTable,
+    cfg: &EngineConfig,
+    now_ms: i64,
+    strategy: StrategyKind,
+) -> Option<ArbSignal> {
+    let bracket = &group.bracket_states[bracket_idx];
+    if bracket.child_idxs.len() > cfg.max_bracket_children as usize {
+        return None;
+    }
+
+    let site = OpportunitySite::Bracket(bracket_idx);
+    let Some(calc) = calc_bracket_edge(group, bracket, pt, strategy) else {
+        group.track_opportunity(site, strategy, false, f64::NAN, f64::NAN, now_ms);
+        return None;
+    };
+    group.bracket_states[bracket_idx].edge_stats[strategy.index()]
+        .record(calc.profit_abs, calc.profit_bps, now_ms);
+    let above = meets_threshold(calc.profit_abs, calc.profit_bps, cfg);
+    group.track_opportunity(site, strategy, above, calc.profit_abs, calc.profit_bps, now_ms);
+    if !above {
+        group.bracket_states[bracket_idx].emission[strategy.index()].close();
+        return None;
+    }
+
+    // Staleness check — leg slots go through the group's reused buffer (no allocation)
+    let bracket = &group.bracket_states[bracket_idx];
+    let (lower_slot, upper_slot, side) = leg_layout(group, bracket, strategy)?;
+    let mut slots = std::mem::take(&mut group.scratch_slots);
+    slots.clear();
+    slots.extend([lower_slot, upper_slot]);
+    slots.extend(bracket.child_idxs.iter().map(|&c| child_slot(group, c, side)));
+    let fresh = cfg.slots_fresh(strategy, pt, &slots, now_ms);
+    group.scratch_slots = slots;
+    if !fresh {
+        group.bracket_states[bracket_idx].stale_skip_count += 1;
+        return None;
+    }
+    let bracket = &group.bracket_states[bracket_idx];
+
+    // Emission check (cooldown / hysteresis)
+    let last_emitted_ms = match strategy {
+        StrategyKind::Triangle => bracket.last_emitted_buy_ms,
+        _ => bracket.last_emitted_complement_ms,
+    };
+    if !cfg.should_emit(&bracket.emission[strategy.index()], last_emitted_ms, calc.profit_bps, now_ms) {
+        return None;
+    }
+
+    let signal = build_bracket_signal(group, bracket, strategy, &calc, pt, side, now_ms);
+
+    // Mark cooldown
+    let bracket = &mut group.bracket_states[bracket_idx];
+    match strategy {
+        StrategyKind::Triangle => bracket.last_emitted_buy_ms = now_ms,
+        _ => bracket.last_emitted_complement_ms = now_ms,
+    }
+    bracket.emission[strategy.index()].emitted(calc.profit_bps);
+
+    Some(signal)
+}
+
+fn build_bracket_signal(
+    group: &GroupState,
+    bracket: &BracketState,
+    strategy: StrategyKind,
+    calc: &BracketProfit,
+    pt: &PriceTable,
+    side: usize,
+    now_ms: i64,
+) -> ArbSignal {
+    let lower_meta = &group.parent_metas[bracket.parent_lower_idx as usize];
+    let upper_meta = &group.parent_metas[bracket.parent_upper_idx as usize];
+    let (lower_slot, upper_slot, _) =
+        leg_layout(group, bracket, strategy).expect("bracket signal for a non-bracket strategy");
+
+    // BUY every leg. Triangle: lower YES + upper NO + children NO;
+    // Complement: lower NO + upper YES + children YES.
+    let mut legs = Vec::with_capacity(bracket.child_idxs.len() + 2);
+    legs.push(lower_meta.leg(1 - side, LegRole::Parent, OrderSide::Buy, pt.get(lower_slot), -1));
+    legs.push(upper_meta.leg(side, LegRole::ParentUpper, OrderSide::Buy, pt.get(upper_slot), -1));
+    legs.extend(bracket.child_idxs.iter().map(|&c| {
+        let q = pt.get(child_slot(group, c, side));
+        group.child_metas[c as usize].leg(side, LegRole::Child, OrderSide::Buy, q, c as i32)
+    }));
+
+    let reason = match strategy {
+        StrategyKind::Triangle => "POLYMARKET_TRIANGLE_BUY_BRACKET_COST_LT_PAYOUT",
+        _ => "POLYMARKET_COMPLEMENT_BUY_BRACKET_COST_LT_PAYOUT",
+    };
+
+    ArbSignal {
+        group_key: group.group_key.clone(),
+        event_slug: group.event_slug.clone(),
+        crypto: group.crypto.clone(),
+        strategy,
+
+        profit_abs: calc.profit_abs,
+        profit_bps: calc.profit_bps,
+        gross_profit_abs: calc.gross_profit_abs,
+        gross_profit_bps: calc.gross_profit_bps,
+        timestamp_ms: now_ms,
+
+        legs,
+        child_index: bracket.child_idxs[0] as i32,
+
+        // Triangle context
+        total_cost: Some(calc.total_ask),
+        total_bid: Some(calc.total_bid),
+        payout: Some(calc.payout),
+        mode: Some("BUY"),
+
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::engine::RangeGroupInput;
+    use crate::engine::state::EngineState;
+    use crate::engine::test_support::{market, quote};
+    use std::sync::Arc;
+
+    /// Parents above-2800 / above-3000 around two range children → one 2-child bracket.
+    fn make_bracket_engine(fee_rate_bps: u32) -> EngineState {
+        let mut engine = EngineState::new(EngineConfig {
+            max_bracket_children: 2,
+            ..EngineConfig::default()
+        });
+        let mut input = RangeGroupInput {
+            group_key: "eth".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![
+                market("r2800", "r1_yes", "r1_no", Some(2800.0), Some(2900.0), "range"),
+                market("r2900", "r2_yes", "r2_no", Some(2900.0), Some(3000.0), "range"),
+            ],
+            parents: vec![
+                market("above-2800", "ly", "ln", Some(2800.0), None, "above"),
+                market("above-3000", "uy", "un", Some(3000.0), None, "above"),
+            ],
+        };
+        for m in input.children.iter_mut().chain(input.parents.iter_mut()) {
+            m.fee_rate_bps = [fee_rate_bps; 2];
+        }
+        engine.update_market_structure(vec![input]);
+        assert_eq!(engine.groups[0].bracket_states.len(), 1);
+        engine
+    }
+
+    fn make_cfg(min_profit_abs: f64) -> EngineConfig {
+        EngineConfig {
+            min_profit_abs,
+            min_profit_bps: 0.0,
+            cooldown_ms: 0,
+            max_bracket_children: 2,
+            ..EngineConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_bracket_triangle_pays_children_plus_one() {
+        // Payout = 2 children + 1 = 3
+        // totalAsk = 0.60 + 0.50 + 0.85 + 0.90 = 2.85 → profit 0.15
+        let mut engine = make_bracket_engine(0);
+        for (token, ask) in [("ly", 0.60), ("un", 0.50), ("r1_no", 0.85), ("r2_no", 0.90)] {
+            quote(&mut engine, token, ask - 0.02, ask);
+        }
+        let group = &engine.groups[0];
+        let r = calc_bracket_edge(group, &group.bracket_states[0], &engine.price_table, StrategyKind::Triangle).unwrap();
+        assert!((r.payout - 3.0).abs() < 1e-10);
+        assert!((r.total_ask - 2.85).abs() < 1e-10);
+        assert!((r.profit_abs - 0.15).abs() < 1e-10);
+        assert!((r.profit_bps - 0.15 / 2.85 * 10000.0).abs() < 1e-6);
+        assert_eq!(r.profit_abs, r.gross_profit_abs);
+    }
+
+    #[test]
+    fn test_bracket_complement_profit_net_of_fees() {
+        // Payout = 1; totalAsk = 0.40 + 0.30 + 0.15 + 0.10 = 0.95 → gross 0.05
+        // Fees at 100 bps: 0.01 × (0.40 + 0.30 + 0.15 + 0.10) = 0.0095 → net 0.0405
+        let mut engine = make_bracket_engine(100);
+        for (token, ask) in [("ln", 0.40), ("uy", 0.30), ("r1_yes", 0.15), ("r2_yes", 0.10)] {
+            quote(&mut engine, token, ask - 0.02, ask);
+        }
+        let group = &engine.groups[0];
+        let r = calc_bracket_edge(group, &group.bracket_states[0], &engine.price_table, StrategyKind::Complement).unwrap();
+        assert!((r.payout - 1.0).abs() < 1e-10);
+        assert!((r.gross_profit_abs - 0.05).abs() < 1e-10);
+        assert!((r.profit_abs - 0.0405).abs() < 1e-10);
+        assert!((r.gross_profit_bps - 0.05 / 0.95 * 10000.0).abs() < 1e-6);
+
+        // Unquoted leg → no edge; non-bracket strategy → no edge
+        quote(&mut engine, "r2_yes", f64::NAN, f64::NAN);
+        let group = &engine.groups[0];
+        assert!(calc_bracket_edge(group, &group.bracket_states[0], &engine.price_table, StrategyKind::Complement).is_none());
+        assert!(calc_bracket_edge(group, &group.bracket_states[0], &engine.price_table, StrategyKind::LadderBuy).is_none());
+    }
+
+    #[test]
+    fn test_bracket_threshold_and_child_cap() {
+        let mut engine = make_bracket_engine(100);
+        for (token, ask) in [("ln", 0.40), ("uy", 0.30), ("r1_yes", 0.15), ("r2_yes", 0.10)] {
+            quote(&mut engine, token, ask - 0.02, ask);
+        }
+        let group = Arc::make_mut(&mut engine.groups[0]);
+
+        // Gross 0.05 clears 0.045, net 0.0405 does not — the threshold is on the net edge
+        assert!(evaluate_bracket(group, 0, &engine.price_table, &make_cfg(0.045), 1, StrategyKind::Complement).is_none());
+        assert_eq!(group.bracket_states[0].edge_stats[StrategyKind::Complement.index()].samples, 1);
+
+        // Brackets wider than the configured cap are skipped
+        let capped = EngineConfig { max_bracket_children: 1, ..make_cfg(0.04) };
+        assert!(evaluate_bracket(group, 0, &engine.price_table, &capped, 2, StrategyKind::Complement).is_none());
+
+        let sig = evaluate_bracket(group, 0, &engine.price_table, &make_cfg(0.04), 3, StrategyKind::Complement).unwrap();
+        assert_eq!(sig.legs.len(), 4);
+        assert!((sig.profit_abs - 0.0405).abs() < 1e-10);
+        assert_eq!(group.bracket_states[0].last_emitted_complement_ms, 3);
+    }
+}
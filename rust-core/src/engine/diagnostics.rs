@@ -0,0 +1,215 @@
+//! Market structure diagnostics — what is wrong with a group's input.
+//!
+//! Built alongside each group in `build_group` and returned to the JS market
+//! builder from `update_market_structure`: markets dropped and why, gaps and
+//! overlaps between range children, tokens shared across markets, parents that
+//! ended up in no trio, and free-form notes (e.g. mixed above/below parents).
+
+use std::collections::HashMap;
+
+use crate::engine::state::{BracketState, MarketKind, MarketMeta, TrioState};
+
+/// A market dropped from the structure.
+#[derive(Debug, Clone)]
+pub struct RejectedMarket {
+    pub market_id: String,
+    /// "child" | "parent"
+    pub role: &'static str,
+    pub reason: String,
+}
+
+/// Gap or overlap between two consecutive range children (sorted by lower bound).
+#[derive(Debug, Clone)]
+pub struct BoundIssue {
+    pub lower_market_id: String,
+    pub upper_market_id: String,
+    /// Upper bound of the lower child.
+    pub from: f64,
+    /// Lower bound of the upper child.
+    pub to: f64,
+}
+
+/// A token id used by more than one market slot in the group.
+#[derive(Debug, Clone)]
+pub struct DuplicateToken {
+    pub token_id: String,
+    pub market_ids: Vec<String>,
+}
+
+/// A parent that takes part in no trio.
+#[derive(Debug, Clone)]
+pub struct OrphanParent {
+    pub market_id: String,
+    pub reason: String,
+}
+
+/// Structure report for one group.
+#[derive(Debug, Clone, Default)]
+pub struct StructureDiagnostics {
+    pub rejected_markets: Vec<RejectedMarket>,
+    pub bound_gaps: Vec<BoundIssue>,
+    pub bound_overlaps: Vec<BoundIssue>,
+    pub duplicate_tokens: Vec<DuplicateToken>,
+    pub parents_without_trio: Vec<OrphanParent>,
+    pub notes: Vec<String>,
+}
+
+impl StructureDiagnostics {
+    /// True when the input produced no findings at all.
+    pub fn is_clean(&self) -> bool {
+        self.rejected_markets.is_empty()
+            && self.bound_gaps.is_empty()
+            && self.bound_overlaps.is_empty()
+            && self.duplicate_tokens.is_empty()
+            && self.parents_without_trio.is_empty()
+            && self.notes.is_empty()
+    }
+}
+
+/// Reason a market descriptor cannot be built, if any.
+pub fn rejection_reason(clob_token_ids: &[String]) -> Option<String> {
+    if clob_token_ids.len() < 2 {
+        return Some(format!(
+            "expected 2 clob_token_ids (YES, NO), got {}",
+            clob_token_ids.len()
+        ));
+    }
+    None
+}
+
+/// Diagnose a built group. `rejected` are the markets dropped while building it.
+pub fn diagnose_group(
+    rejected: Vec<RejectedMarket>,
+    child_metas: &[MarketMeta],
+    parent_metas: &[MarketMeta],
+    trio_states: &[TrioState],
+    bracket_states: &[BracketState],
+) -> StructureDiagnostics {
+    let mut diag = StructureDiagnostics {
+        rejected_markets: rejected,
+        ..Default::default()
+    };
+
+    check_child_bounds(child_metas, &mut diag);
+    check_duplicate_tokens(child_metas, parent_metas, &mut diag);
+    check_orphan_parents(parent_metas, trio_states, bracket_states, &mut diag);
+
+    let above = parent_metas.iter().filter(|m| m.kind == MarketKind::Above).count();
+    let below = parent_metas.iter().filter(|m| m.kind == MarketKind::Below).count();
+    if above > 0 && below > 0 {
+        diag.notes.push(format!(
+            "mixed parent kinds: {} above, {} below (only above parents form trios)",
+            above, below
+        ));
+    }
+
+    diag
+}
+
+// =============================================================================
+// CHECKS
+// =============================================================================
+
+/// Gaps and overlaps between consecutive range children; children without
+/// finite bounds or of a non-range kind are noted.
+fn check_child_bounds(child_metas: &[MarketMeta], diag: &mut StructureDiagnostics) {
+    let mut ranges: Vec<(&MarketMeta, f64, f64)> = Vec::new();
+    for meta in child_metas {
+        if meta.kind != MarketKind::Range {
+            diag.notes.push(format!(
+                "child {} has kind {} (only range children form trios)",
+                meta.market_id,
+                meta.kind.as_str()
+            ));
+            continue;
+        }
+        match (meta.bounds_lower, meta.bounds_upper) {
+            (Some(lo), Some(hi)) if lo.is_finite() && hi.is_finite() => ranges.push((meta, lo, hi)),
+            _ => diag
+                .notes
+                .push(format!("range child {} is missing a finite bound", meta.market_id)),
+        }
+    }
+
+    ranges.sort_by(|a, b| a.1.total_cmp(&b.1));
+    for pair in ranges.windows(2) {
+        let (lower, _, from) = pair[0];
+        let (upper, to, _) = pair[1];
+        if from == to {
+            continue;
+        }
+        let issue = BoundIssue {
+            lower_market_id: lower.market_id.clone(),
+            upper_market_id: upper.market_id.clone(),
+            from,
+            to,
+        };
+        if from < to {
+            diag.bound_gaps.push(issue);
+        } else {
+            diag.bound_overlaps.push(issue);
+        }
+    }
+}
+
+/// Tokens that appear in more than one market (or as both YES and NO of one market).
+fn check_duplicate_tokens(
+    child_metas: &[MarketMeta],
+    parent_metas: &[MarketMeta],
+    diag: &mut StructureDiagnostics,
+) {
+    let mut uses: HashMap<&str, Vec<&str>> = HashMap::new();
+    for meta in child_metas.iter().chain(parent_metas.iter()) {
+        for token in &meta.clob_token_ids {
+            uses.entry(token.as_str()).or_default().push(meta.market_id.as_str());
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateToken> = uses
+        .into_iter()
+        .filter(|(_, markets)| markets.len() > 1)
+        .map(|(token, markets)| DuplicateToken {
+            token_id: token.to_string(),
+            market_ids: markets.into_iter().map(str::to_string).collect(),
+        })
+        .collect();
+    duplicates.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+    diag.duplicate_tokens = duplicates;
+}
+
+/// Parents referenced by no trio or bracket, with the most likely reason.
+fn check_orphan_parents(
+    parent_metas: &[MarketMeta],
+    trio_states: &[TrioState],
+    bracket_states: &[BracketState],
+    diag: &mut StructureDiagnostics,
+) {
+    let mut used = vec![false; parent_metas.len()];
+    let pairs = trio_states
+        .iter()
+        .map(|t| (t.parent_lower_idx, t.parent_upper_idx))
+        .chain(bracket_states.iter().map(|b| (b.parent_lower_idx, b.parent_upper_idx)));
+    for (lower, upper) in pairs {
+        used[lower as usize] = true;
+        used[upper as usize] = true;
+    }
+
+    for (i, meta) in parent_metas.iter().enumerate() {
+        if used[i] {
+            continue;
+        }
+        let reason = if meta.kind != MarketKind::Above {
+            format!("kind is {}, not above", meta.kind.as_str())
+        } else if !meta.bounds_lower.is_some_and(f64::is_finite) {
+            "missing a finite lower bound".to_string()
+        } else if parent_metas.len() < 2 {
+            "no other parent to pair with".to_string()
+        } else {
+            "no range child spans from it to an adjacent parent".to_string()
+        };
+        diag.parents_without_trio.push(OrphanParent {
+            market_id: meta.market_id.clone(),
+            reason,
+        });
+    }
+}
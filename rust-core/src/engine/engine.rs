@@ -1,14 +1,25 @@
 //! Main engine — handle_top_of_book dispatch + market structure builder.
 //!
-//! Receives TopOfBookUpdate from socket, dispatches to trio and range evaluators.
-//! Also builds groups/trios from RangeGroupInput.
-
-use std::collections::HashMap;
-
-// use crate::engine::range_evaluator;
+//! Receives TopOfBookUpdate from socket, dispatches to trio, range, bracket, ladder,
+//! monotonicity, parity and neg-risk evaluators.
+//! Also builds groups/trios from RangeGroupInput, incrementally per group, and
+//! neg-risk events from NegRiskEventInput.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::engine::bracket_evaluator;
+use crate::engine::diagnostics::{self, RejectedMarket};
+use crate::engine::intern::TokenId;
+use crate::engine::ladder_evaluator;
+use crate::engine::monotone_evaluator;
+use crate::engine::neg_risk_evaluator;
+use crate::engine::parity_evaluator;
+use crate::engine::range_evaluator;
 use crate::engine::state::*;
 use crate::engine::trio_evaluator;
 use crate::types::signal::ArbSignal;
+use crate::types::strategy::StrategyKind;
 
 // =============================================================================
 // MARKET STRUCTURE BUILDER — Called from update_market_structure()
@@ -23,6 +34,8 @@ pub struct MarketDescriptorInput {
     pub bounds_upper: Option<f64>,
     pub kind: String,
     pub neg_risk: bool,
+    /// Taker fee rate [YES, NO].
+    pub fee_rate_bps: [u32; 2],
 }
 
 /// Input from Node.js for a group.
@@ -34,152 +47,445 @@ pub struct RangeGroupInput {
     pub parents: Vec<MarketDescriptorInput>,
 }
 
+/// Input from Node.js for one outcome market of a neg-risk event.
+pub struct NegRiskOutcomeInput {
+    pub market_id: String,
+    pub slug: String,
+    pub clob_token_ids: Vec<String>,
+    /// Taker fee rate [YES, NO].
+    pub fee_rate_bps: [u32; 2],
+}
+
+/// Input from Node.js for a neg-risk event — must list every outcome.
+pub struct NegRiskEventInput {
+    pub event_id: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub outcomes: Vec<NegRiskOutcomeInput>,
+}
+
+impl From<&MarketMeta> for MarketDescriptorInput {
+    fn from(meta: &MarketMeta) -> Self {
+        Self {
+            market_id: meta.market_id.clone(),
+            slug: meta.slug.clone(),
+            clob_token_ids: meta.clob_token_ids.to_vec(),
+            bounds_lower: meta.bounds_lower,
+            bounds_upper: meta.bounds_upper,
+            kind: meta.kind.as_str().to_string(),
+            neg_risk: meta.neg_risk,
+            fee_rate_bps: meta.fee_rate_bps,
+        }
+    }
+}
+
 impl EngineState {
-    /// Rebuild all groups from market structure (called by N-API).
-    /// Clears previous state and recreates PriceTable, groups, trios, indices.
+    /// Reconcile all groups with the given market structure (called by N-API).
+    ///
+    /// Groups missing from the input are removed, all others are upserted.
+    /// Prices of tokens that survive the refresh are kept.
+    /// Returns the total number of trios across all groups.
     pub fn update_market_structure(&mut self, groups_input: Vec<RangeGroupInput>) -> i32 {
-        // Clear old state
-        self.groups.clear();
-        self.group_key_index.clear();
-        self.token_index.clear();
-        self.last_price_cache.clear();
-        self.price_table = PriceTable::new();
+        let keep: HashSet<&str> = groups_input.iter().map(|g| g.group_key.as_str()).collect();
+        let stale: Vec<String> = self
+            .groups
+            .iter()
+            .filter(|g| !keep.contains(g.group_key.as_str()))
+            .map(|g| g.group_key.clone())
+            .collect();
 
-        let mut total_trios = 0i32;
+        for group_key in &stale {
+            self.remove_group(group_key);
+        }
+        for g in groups_input {
+            self.upsert_group(g);
+        }
 
-        for (group_raw_idx, g) in groups_input.into_iter().enumerate() {
-            let group_idx = group_raw_idx as u16;
+        self.groups.iter().map(|g| g.trio_states.len() as i32).sum()
+    }
 
-            // Build child metas (range markets)
-            let child_metas: Vec<MarketMeta> = g
-                .children
-                .into_iter()
-                .filter(|c| c.clob_token_ids.len() >= 2)
-                .map(|c| {
-                    let yes_slot = self.price_table.alloc_slot(&c.clob_token_ids[0]);
-                    let no_slot = self.price_table.alloc_slot(&c.clob_token_ids[1]);
-                    MarketMeta {
-                        market_id: c.market_id,
-                        slug: c.slug,
-                        clob_token_ids: [
-                            c.clob_token_ids[0].clone(),
-                            c.clob_token_ids[1].clone(),
-                        ],
-                        bounds_lower: c.bounds_lower,
-                        bounds_upper: c.bounds_upper,
-                        kind: MarketKind::from_str(&c.kind),
-                        neg_risk: c.neg_risk,
-                        yes_slot,
-                        no_slot,
-                    }
-                })
-                .collect();
+    /// Insert a new group or replace an existing one with the same `group_key`.
+    ///
+    /// Only this group's trios and token roles are rebuilt. Slots of tokens that
+    /// remain in the group keep their prices, and trios that survive keep their
+    /// cooldown timestamps. Returns the group's trio count.
+    pub fn upsert_group(&mut self, g: RangeGroupInput) -> i32 {
+        // Allocate new slots before releasing old ones so shared tokens never drop to zero refs
+        let mut group = self.build_group(g);
+        let trio_count = group.trio_states.len() as i32;
+
+        match self.group_key_index.get(&group.group_key).copied() {
+            Some(group_idx) => {
+                let gi = group_idx as usize;
+                carry_over_cooldowns(Arc::make_mut(&mut self.groups[gi]), &mut group);
+                let token_index = Arc::make_mut(&mut self.token_index);
+                unindex_group(token_index, &self.groups[gi], group_idx);
+                let old = std::mem::replace(&mut self.groups[gi], Arc::new(group));
+                index_group(token_index, &self.groups[gi], group_idx);
+                self.reclaim_parity(&old);
+                self.release_group_slots(&old);
+                self.close_group_opportunities(Arc::unwrap_or_clone(old));
+            }
+            None => {
+                let group_idx = self.groups.len() as u16;
+                Arc::make_mut(&mut self.group_key_index).insert(group.group_key.clone(), group_idx);
+                index_group(Arc::make_mut(&mut self.token_index), &group, group_idx);
+                self.groups.push(Arc::new(group));
+            }
+        }
+
+        trio_count
+    }
+
+    /// Remove a group and reclaim the slots no other group uses.
+    /// Returns false if the group is unknown.
+    pub fn remove_group(&mut self, group_key: &str) -> bool {
+        let group_key_index = Arc::make_mut(&mut self.group_key_index);
+        let group_idx = match group_key_index.remove(group_key) {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let gi = group_idx as usize;
+
+        let token_index = Arc::make_mut(&mut self.token_index);
+        unindex_group(token_index, &self.groups[gi], group_idx);
+        let old = self.groups.swap_remove(gi);
+
+        // The last group moved into the hole — retarget its token roles
+        if gi < self.groups.len() {
+            let moved_from = self.groups.len() as u16;
+            unindex_group(token_index, &self.groups[gi], moved_from);
+            index_group(token_index, &self.groups[gi], group_idx);
+            group_key_index.insert(self.groups[gi].group_key.clone(), group_idx);
+        }
 
-            // Build parent metas
-            let parent_metas: Vec<MarketMeta> = g
-                .parents
+        self.reclaim_parity(&old);
+        self.release_group_slots(&old);
+        self.close_group_opportunities(Arc::unwrap_or_clone(old));
+        true
+    }
+
+    /// Add markets to an existing group (replacing any with the same `market_id`).
+    /// Parents are kept sorted by lower bound so adjacent pairs stay valid.
+    /// Returns the group's new trio count, or None if the group is unknown.
+    pub fn add_markets(
+        &mut self,
+        group_key: &str,
+        children: Vec<MarketDescriptorInput>,
+        parents: Vec<MarketDescriptorInput>,
+    ) -> Option<i32> {
+        let mut input = self.group_input(group_key)?;
+
+        let added: HashSet<String> = children
+            .iter()
+            .chain(parents.iter())
+            .map(|m| m.market_id.clone())
+            .collect();
+        input.children.retain(|m| !added.contains(&m.market_id));
+        input.parents.retain(|m| !added.contains(&m.market_id));
+        input.children.extend(children);
+        input.parents.extend(parents);
+        input.parents.sort_by(|a, b| {
+            let a = a.bounds_lower.unwrap_or(f64::INFINITY);
+            let b = b.bounds_lower.unwrap_or(f64::INFINITY);
+            a.total_cmp(&b)
+        });
+
+        Some(self.upsert_group(input))
+    }
+
+    /// Retire markets (children or parents) from an existing group by `market_id`.
+    /// Returns the group's new trio count, or None if the group is unknown.
+    pub fn retire_markets(&mut self, group_key: &str, market_ids: &[String]) -> Option<i32> {
+        let mut input = self.group_input(group_key)?;
+
+        let retired: HashSet<&str> = market_ids.iter().map(String::as_str).collect();
+        input.children.retain(|m| !retired.contains(m.market_id.as_str()));
+        input.parents.retain(|m| !retired.contains(m.market_id.as_str()));
+
+        Some(self.upsert_group(input))
+    }
+
+    /// Rebuild every group from its own markets (e.g. after a structural config
+    /// change such as `max_bracket_children`). Prices and cooldowns survive.
+    pub fn rebuild_groups(&mut self) {
+        let keys: Vec<String> = self.groups.iter().map(|g| g.group_key.clone()).collect();
+        for key in keys {
+            if let Some(input) = self.group_input(&key) {
+                self.upsert_group(input);
+            }
+        }
+    }
+
+    /// Replace all neg-risk events. Events with fewer than two outcomes or an
+    /// outcome without both token ids are skipped — a partial outcome list would
+    /// make the sums meaningless. Prices of tokens already tracked survive, and
+    /// events that keep the same outcomes keep their cooldowns.
+    /// Returns the number of events tracked.
+    pub fn update_neg_risk_events(&mut self, inputs: Vec<NegRiskEventInput>) -> i32 {
+        let mut old = std::mem::take(&mut self.neg_risk_events);
+        let token_index = Arc::make_mut(&mut self.token_index);
+        for (event_idx, event) in old.iter().enumerate() {
+            unindex_neg_risk_event(token_index, event, event_idx as u16);
+        }
+
+        // Build new events first so shared tokens never drop to zero references
+        let mut events = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let complete = input.outcomes.len() >= 2
+                && input
+                    .outcomes
+                    .iter()
+                    .all(|o| diagnostics::rejection_reason(&o.clob_token_ids).is_none());
+            if !complete {
+                continue;
+            }
+
+            let outcomes = input
+                .outcomes
                 .into_iter()
-                .filter(|p| p.clob_token_ids.len() >= 2)
-                .map(|p| {
-                    let yes_slot = self.price_table.alloc_slot(&p.clob_token_ids[0]);
-                    let no_slot = self.price_table.alloc_slot(&p.clob_token_ids[1]);
-                    MarketMeta {
-                        market_id: p.market_id,
-                        slug: p.slug,
-                        clob_token_ids: [
-                            p.clob_token_ids[0].clone(),
-                            p.clob_token_ids[1].clone(),
-                        ],
-                        bounds_lower: p.bounds_lower,
-                        bounds_upper: p.bounds_upper,
-                        kind: MarketKind::from_str(&p.kind),
-                        neg_risk: p.neg_risk,
-                        yes_slot,
-                        no_slot,
+                .map(|o| {
+                    let token_ids = [
+                        self.interner.intern(&o.clob_token_ids[0]),
+                        self.interner.intern(&o.clob_token_ids[1]),
+                    ];
+                    NegRiskOutcome {
+                        yes_slot: self.price_table.alloc_slot(&o.clob_token_ids[0], token_ids[0]),
+                        no_slot: self.price_table.alloc_slot(&o.clob_token_ids[1], token_ids[1]),
+                        market_id: o.market_id,
+                        slug: o.slug,
+                        clob_token_ids: [o.clob_token_ids[0].clone(), o.clob_token_ids[1].clone()],
+                        token_ids,
+                        fee_rate_bps: o.fee_rate_bps,
                     }
                 })
                 .collect();
+            let config = self.resolve_config(&input.event_id, &input.event_slug, &input.crypto);
+            let mut event = NegRiskEventState {
+                event_id: input.event_id,
+                event_slug: input.event_slug,
+                crypto: input.crypto,
+                outcomes,
+                config,
+                last_emitted_yes_buy_ms: 0,
+                last_emitted_no_buy_ms: 0,
+                last_emitted_convert_ms: 0,
+                stale_skip_count: 0,
+                edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+                emission: [EmissionLatch::default(); StrategyKind::COUNT],
+                opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
+                opportunity_events: Vec::new(),
+            };
+
+            let same_outcomes = |prev: &NegRiskEventState| {
+                prev.outcomes.len() == event.outcomes.len()
+                    && prev
+                        .outcomes
+                        .iter()
+                        .zip(&event.outcomes)
+                        .all(|(a, b)| a.market_id == b.market_id)
+            };
+            if let Some(prev) = old
+                .iter_mut()
+                .find(|p| p.event_id == event.event_id && same_outcomes(p))
+                .map(Arc::make_mut)
+            {
+                event.last_emitted_yes_buy_ms = prev.last_emitted_yes_buy_ms;
+                event.last_emitted_no_buy_ms = prev.last_emitted_no_buy_ms;
+                event.last_emitted_convert_ms = prev.last_emitted_convert_ms;
+                event.edge_stats = prev.edge_stats;
+                event.emission = prev.emission;
+                // Carried over — whatever stays open in `old` was dropped
+                event.opportunity = std::mem::take(&mut prev.opportunity);
+            }
+            events.push(Arc::new(event));
+        }
+
+        for outcome in old.iter().flat_map(|e| e.outcomes.iter()) {
+            self.release_token_slots(&outcome.clob_token_ids, &outcome.token_ids);
+        }
+        let token_index = Arc::make_mut(&mut self.token_index);
+        for (event_idx, event) in events.iter().enumerate() {
+            index_neg_risk_event(token_index, event, event_idx as u16);
+        }
+
+        self.neg_risk_events = events;
+
+        // Opportunities of dropped (or re-shaped) events close
+        let now_ms = self.now_ms();
+        for event in old {
+            let mut event = Arc::unwrap_or_clone(event);
+            event.close_opportunities(|_| false, now_ms);
+            self.queue_opportunity_events(event.opportunity_events);
+        }
+        self.neg_risk_events.len() as i32
+    }
+
+    /// Reload every ladder's leg quotes from the PriceTable — call after writing
+    /// prices without going through `handle_top_of_book` (e.g. snapshot restore).
+    pub fn resync_ladders(&mut self) {
+        for group in self.groups.iter_mut().map(Arc::make_mut) {
+            group.resync_ladder(&self.price_table);
+        }
+    }
 
-            // Initialize trios (adjacent parent pairs)
-            let (trio_states, trio_lookup) =
-                initialize_trio_states(&child_metas, &parent_metas);
-            total_trios += trio_states.len() as i32;
-
-            // Index tokens for dispatch
-            // Child tokens — YES only (range evaluator reads yes_slot)
-            for (child_idx, meta) in child_metas.iter().enumerate() {
-                let yes_token = &meta.clob_token_ids[0];
-                self.token_index
-                    .entry(yes_token.clone())
-                    .or_default()
-                    .push(TokenRole::RangeChild {
-                        group_idx,
-                        child_idx: child_idx as u16,
-                    });
+    /// Rebuild the input descriptor of an existing group.
+    fn group_input(&self, group_key: &str) -> Option<RangeGroupInput> {
+        let &group_idx = self.group_key_index.get(group_key)?;
+        let group = &self.groups[group_idx as usize];
+        Some(RangeGroupInput {
+            group_key: group.group_key.clone(),
+            event_slug: group.event_slug.clone(),
+            crypto: group.crypto.clone(),
+            children: group.child_metas.iter().map(MarketDescriptorInput::from).collect(),
+            parents: group.parent_metas.iter().map(MarketDescriptorInput::from).collect(),
+        })
+    }
+
+    /// Build a group's metas and trios, taking one slot reference per market token.
+    /// Markets that cannot be built are dropped and reported in `diagnostics`.
+    fn build_group(&mut self, g: RangeGroupInput) -> GroupState {
+        let mut rejected = Vec::new();
+
+        // Build child metas (range markets)
+        let mut child_metas = Vec::with_capacity(g.children.len());
+        for c in g.children {
+            match diagnostics::rejection_reason(&c.clob_token_ids) {
+                Some(reason) => rejected.push(RejectedMarket {
+                    market_id: c.market_id,
+                    role: "child",
+                    reason,
+                }),
+                None => child_metas.push(self.build_market_meta(c)),
             }
+        }
 
-            // Parent tokens — YES only (range evaluator reads yes_slot)
-            for (parent_idx, meta) in parent_metas.iter().enumerate() {
-                let yes_token = &meta.clob_token_ids[0];
-                self.token_index
-                    .entry(yes_token.clone())
-                    .or_default()
-                    .push(TokenRole::Parent {
-                        group_idx,
-                        parent_idx: parent_idx as u16,
-                    });
+        // Build parent metas
+        let mut parent_metas = Vec::with_capacity(g.parents.len());
+        for p in g.parents {
+            match diagnostics::rejection_reason(&p.clob_token_ids) {
+                Some(reason) => rejected.push(RejectedMarket {
+                    market_id: p.market_id,
+                    role: "parent",
+                    reason,
+                }),
+                None => parent_metas.push(self.build_market_meta(p)),
             }
+        }
 
-            // Trio leg tokens — Triangle BUY (YES/NO/NO)
-            for (trio_idx, trio) in trio_states.iter().enumerate() {
-                let triangle_roles = [
-                    (&trio.lower_yes_token, TrioLegRole::ParentLowerYes),
-                    (&trio.upper_no_token, TrioLegRole::ParentUpperNo),
-                    (&trio.range_no_token, TrioLegRole::RangeNo),
-                ];
-                for (token_id, role) in triangle_roles {
-                    self.token_index
-                        .entry(token_id.clone())
-                        .or_default()
-                        .push(TokenRole::TrioLeg {
-                            group_idx,
-                            trio_idx: trio_idx as u16,
-                            role,
-                        });
-                }
+        // Initialize trios (adjacent parent pairs) + brackets (everything wider)
+        let (trio_states, trio_lookup) = initialize_trio_states(&child_metas, &parent_metas);
+        let config = self.resolve_config(&g.group_key, &g.event_slug, &g.crypto);
+        let bracket_states = initialize_bracket_states(
+            &child_metas,
+            &parent_metas,
+            &trio_states,
+            config.max_bracket_children as usize,
+        );
+        let ladder = initialize_ladder_state(&child_metas, &self.price_table);
+        let monotone_pairs = initialize_monotone_pairs(&parent_metas);
+        let parity_states = initialize_parity_states(&child_metas, &parent_metas);
+        let diagnostics = diagnostics::diagnose_group(
+            rejected,
+            &child_metas,
+            &parent_metas,
+            &trio_states,
+            &bracket_states,
+        );
 
-                // Complement BUY legs (NO/YES/YES)
-                let complement_roles = [
-                    (&trio.lower_no_token, TrioLegRole::ParentLowerNo),
-                    (&trio.range_yes_token, TrioLegRole::RangeYes),
-                    (&trio.upper_yes_token, TrioLegRole::ParentUpperYes),
-                ];
-                for (token_id, role) in complement_roles {
-                    self.token_index
-                        .entry(token_id.clone())
-                        .or_default()
-                        .push(TokenRole::TrioLeg {
-                            group_idx,
-                            trio_idx: trio_idx as u16,
-                            role,
-                        });
+        GroupState {
+            group_key: g.group_key,
+            event_slug: g.event_slug,
+            crypto: g.crypto,
+            child_metas,
+            parent_metas,
+            trio_states,
+            bracket_states,
+            ladder,
+            monotone_pairs,
+            parity_states,
+            trios_by_child: trio_lookup.by_child,
+            trios_by_parent: trio_lookup.by_parent,
+            config,
+            diagnostics,
+            opportunity_events: Vec::new(),
+            scratch_slots: Vec::new(),
+        }
+    }
+
+    fn build_market_meta(&mut self, m: MarketDescriptorInput) -> MarketMeta {
+        let token_ids = [
+            self.interner.intern(&m.clob_token_ids[0]),
+            self.interner.intern(&m.clob_token_ids[1]),
+        ];
+        let yes_slot = self.price_table.alloc_slot(&m.clob_token_ids[0], token_ids[0]);
+        let no_slot = self.price_table.alloc_slot(&m.clob_token_ids[1], token_ids[1]);
+        MarketMeta {
+            market_id: m.market_id,
+            slug: m.slug,
+            clob_token_ids: [m.clob_token_ids[0].clone(), m.clob_token_ids[1].clone()],
+            bounds_lower: m.bounds_lower,
+            bounds_upper: m.bounds_upper,
+            kind: MarketKind::from_str(&m.kind),
+            neg_risk: m.neg_risk,
+            yes_slot,
+            no_slot,
+            token_ids,
+            fee_rate_bps: m.fee_rate_bps,
+        }
+    }
+
+    /// Hand the parity checks `released` owned to another group that lists the
+    /// same market, so a market shared across groups keeps exactly one owner.
+    fn reclaim_parity(&mut self, released: &GroupState) {
+        for meta in released.child_metas.iter().chain(released.parent_metas.iter()) {
+            let yes = meta.token_ids[0];
+            if has_parity_role(&self.token_index, yes) {
+                continue;
+            }
+            let owner = self.groups.iter().enumerate().find_map(|(gi, g)| {
+                let pi = g.parity_states.iter().position(|p| p.meta(g).token_ids[0] == yes)?;
+                Some((gi, pi, g.parity_states[pi].meta(g).token_ids))
+            });
+            if let Some((gi, pi, token_ids)) = owner {
+                for id in token_ids {
+                    push_role(
+                        Arc::make_mut(&mut self.token_index),
+                        id,
+                        TokenRole::ParityLeg {
+                            group_idx: gi as u16,
+                            parity_idx: pi as u16,
+                        },
+                    );
                 }
             }
+        }
+    }
 
-            self.group_key_index.insert(g.group_key.clone(), group_idx);
+    /// Close the opportunities still open on a group that was dropped or
+    /// replaced (trackers carried into the replacement are already reset).
+    fn close_group_opportunities(&mut self, mut group: GroupState) {
+        let now_ms = self.now_ms();
+        group.close_opportunities(|_| false, now_ms);
+        self.queue_opportunity_events(group.opportunity_events);
+    }
 
-            self.groups.push(GroupState {
-                group_key: g.group_key,
-                event_slug: g.event_slug,
-                crypto: g.crypto,
-                child_metas,
-                parent_metas,
-                trio_states,
-                trio_lookup_by_asset: trio_lookup,
-            });
+    /// Drop the slot references held by a group; reclaimed tokens lose their dirty-check cache.
+    fn release_group_slots(&mut self, group: &GroupState) {
+        for meta in group.child_metas.iter().chain(group.parent_metas.iter()) {
+            self.release_token_slots(&meta.clob_token_ids, &meta.token_ids);
         }
+    }
 
-        total_trios
+    /// Drop one market's YES/NO slot references.
+    fn release_token_slots(&mut self, clob_token_ids: &[String; 2], token_ids: &[TokenId; 2]) {
+        for (token_id, &id) in clob_token_ids.iter().zip(token_ids) {
+            if self.price_table.release_slot(token_id, id) {
+                if let Some(entry) = self.last_price_cache.get_mut(id as usize) {
+                    *entry = None;
+                }
+            }
+        }
     }
 
     // =========================================================================
@@ -187,6 +493,9 @@ impl EngineState {
     // =========================================================================
 
     /// Process a single TopOfBook update. Returns any emitted signals.
+    ///
+    /// Convenience entry by token string; the socket path resolves ids up front
+    /// and calls `handle_top_of_book_id` directly.
     pub fn handle_top_of_book(
         &mut self,
         asset_id: &str,
@@ -196,48 +505,80 @@ impl EngineState {
         ask_size: Option<f64>,
         timestamp_ms: i64,
     ) -> Vec<ArbSignal> {
-        // 1. Dirty check
-        if !self.is_price_changed(asset_id, bid, ask, timestamp_ms) {
-            return vec![];
+        match self.interner.get(asset_id) {
+            Some(id) => {
+                self.handle_top_of_book_id(id, bid, ask, bid_size, ask_size, timestamp_ms)
+            }
+            None => vec![],
         }
-        
-        // 2. Lookup slot → single write
-        let slot = match self.price_table.token_to_slot.get(asset_id) {
-            Some(&s) => s,
+    }
+
+    /// Process a single TopOfBook update for an interned token. Returns any emitted signals.
+    pub fn handle_top_of_book_id(
+        &mut self,
+        id: TokenId,
+        bid: f64,
+        ask: f64,
+        bid_size: Option<f64>,
+        ask_size: Option<f64>,
+        timestamp_ms: i64,
+    ) -> Vec<ArbSignal> {
+        // 1. Lookup slot — unknown tokens never enter the dirty-check cache
+        let slot = match self.price_table.slot_of(id) {
+            Some(s) => s,
             None => return vec![],
         };
+
+        // 2. Dirty check → single write
+        if !self.is_price_changed_id(id, bid, ask, bid_size, ask_size, timestamp_ms) {
+            return vec![];
+        }
         self.price_table
             .update(slot, bid, ask, bid_size, ask_size, timestamp_ms);
+        if timestamp_ms > self.last_exchange_ts_ms {
+            self.last_exchange_ts_ms = timestamp_ms;
+        }
+        // Opt-in price feed — drained by `take_price_updates`
+        if self.price_feed.watches(id) {
+            let now_ms = self.now_ms();
+            Arc::make_mut(&mut self.price_feed).observe(id, now_ms);
+        }
 
-        // 3. Dispatch to evaluators
-        let roles = match self.token_index.get(asset_id) {
-            Some(r) => r.clone(), // clone to avoid borrow issues
-            None => return vec![],
-        };
+        // 3. Dispatch to evaluators — roles are Copy, read by index (no clone)
+        let role_count = self.token_index.get(id as usize).map_or(0, Vec::len);
+        if role_count == 0 {
+            return vec![];
+        }
 
         let mut signals = Vec::new();
-        let config = self.config.clone();
+        let now_ms = self.now_ms();
 
-        for role in &roles {
+        for r in 0..role_count {
+            let role = self.token_index[id as usize][r];
             match role {
                 TokenRole::TrioLeg {
                     group_idx,
                     trio_idx,
                     role,
                 } => {
-                    let gi = *group_idx as usize;
-                    let ti = *trio_idx as usize;
+                    let gi = group_idx as usize;
+                    let ti = trio_idx as usize;
                     if gi < self.groups.len() {
+                        let config = self.groups[gi].config;
                         match role {
                             // Triangle BUY legs → evaluate triangle
                             TrioLegRole::ParentLowerYes
                             | TrioLegRole::ParentUpperNo
                             | TrioLegRole::RangeNo => {
+                                if !config.is_enabled(StrategyKind::Triangle) {
+                                    continue;
+                                }
                                 if let Some(sig) = trio_evaluator::evaluate_single_trio(
-                                    &mut self.groups[gi],
+                                    Arc::make_mut(&mut self.groups[gi]),
                                     ti,
                                     &self.price_table,
                                     &config,
+                                    now_ms,
                                 ) {
                                     signals.push(sig);
                                 }
@@ -246,11 +587,15 @@ impl EngineState {
                             TrioLegRole::ParentLowerNo
                             | TrioLegRole::RangeYes
                             | TrioLegRole::ParentUpperYes => {
+                                if !config.is_enabled(StrategyKind::Complement) {
+                                    continue;
+                                }
                                 if let Some(sig) = trio_evaluator::evaluate_complement_trio(
-                                    &mut self.groups[gi],
+                                    Arc::make_mut(&mut self.groups[gi]),
                                     ti,
                                     &self.price_table,
                                     &config,
+                                    now_ms,
                                 ) {
                                     signals.push(sig);
                                 }
@@ -258,50 +603,559 @@ impl EngineState {
                         }
                     }
                 }
-                // TokenRole::RangeChild { group_idx, .. }
-                // | TokenRole::Parent { group_idx, .. } => {
-                //     let gi = *group_idx as usize;
-                //     if gi < self.groups.len() {
-                //         // Find trios affected by this token
-                //         let trio_indices: Vec<u16> = self.groups[gi]
-                //             .trio_lookup_by_asset
-                //             .get(asset_id)
-                //             .cloned()
-                //             .unwrap_or_default();
-
-                //         if !trio_indices.is_empty() {
-                //             let range_signals =
-                //                 range_evaluator::evaluate_trios_for_range_arbitrage(
-                //                     &mut self.groups[gi],
-                //                     &trio_indices,
-                //                     &self.price_table,
-                //                     &config,
-                //                 );
-                //             signals.extend(range_signals);
-                //         }
-                //     }
-                // }
-                _ => {}
+                // YES tokens of range children / parents → unbundling + bundling
+                TokenRole::RangeChild { group_idx, .. } | TokenRole::Parent { group_idx, .. } => {
+                    let gi = group_idx as usize;
+                    if gi >= self.groups.len() {
+                        continue;
+                    }
+                    let config = self.groups[gi].config;
+                    if !config.is_enabled(StrategyKind::Unbundle)
+                        && !config.is_enabled(StrategyKind::Bundle)
+                    {
+                        continue;
+                    }
+
+                    // Trios affected by this token — precomputed per market, copied
+                    // into a reused buffer (no allocation)
+                    let group = &self.groups[gi];
+                    let affected: &[u16] = match role {
+                        TokenRole::RangeChild { child_idx, .. } => {
+                            &group.trios_by_child[child_idx as usize]
+                        }
+                        TokenRole::Parent { parent_idx, .. } => {
+                            &group.trios_by_parent[parent_idx as usize]
+                        }
+                        TokenRole::TrioLeg { .. }
+                        | TokenRole::BracketLeg { .. }
+                        | TokenRole::LadderLeg { .. }
+                        | TokenRole::MonotoneLeg { .. }
+                        | TokenRole::ParityLeg { .. }
+                        | TokenRole::NegRiskLeg { .. } => &[],
+                    };
+                    let mut trio_indices = std::mem::take(&mut self.scratch_trios);
+                    trio_indices.clear();
+                    trio_indices.extend_from_slice(affected);
+
+                    if !trio_indices.is_empty() {
+                        let range_signals = range_evaluator::evaluate_trios_for_range_arbitrage(
+                            Arc::make_mut(&mut self.groups[gi]),
+                            &trio_indices,
+                            &self.price_table,
+                            &config,
+                            now_ms,
+                        );
+                        signals.extend(range_signals);
+                    }
+                    self.scratch_trios = trio_indices;
+                }
+                // Bracket legs → N-leg triangle / complement
+                TokenRole::BracketLeg {
+                    group_idx,
+                    bracket_idx,
+                    strategy,
+                } => {
+                    let gi = group_idx as usize;
+                    if gi >= self.groups.len() {
+                        continue;
+                    }
+                    let config = self.groups[gi].config;
+                    if !config.is_enabled(strategy) {
+                        continue;
+                    }
+                    if let Some(sig) = bracket_evaluator::evaluate_bracket(
+                        Arc::make_mut(&mut self.groups[gi]),
+                        bracket_idx as usize,
+                        &self.price_table,
+                        &config,
+                        now_ms,
+                        strategy,
+                    ) {
+                        signals.push(sig);
+                    }
+                }
+                // Ladder legs → keep the partition sums current, then buy / sell the full set
+                TokenRole::LadderLeg { group_idx, leg_idx } => {
+                    let gi = group_idx as usize;
+                    if gi >= self.groups.len() {
+                        continue;
+                    }
+                    // Disabled ladders skip their sums too (no group copy per tick);
+                    // `refresh_group_configs` reloads them when a ladder strategy is enabled
+                    let config = self.groups[gi].config;
+                    if !config.is_enabled(StrategyKind::LadderBuy) && !config.is_enabled(StrategyKind::LadderSell) {
+                        continue;
+                    }
+                    let q = self.price_table.get(slot);
+                    let Some(ladder) = Arc::make_mut(&mut self.groups[gi]).ladder.as_mut() else {
+                        continue;
+                    };
+                    ladder.update_leg(leg_idx as usize, q.best_bid, q.best_ask);
+
+                    for strategy in [StrategyKind::LadderBuy, StrategyKind::LadderSell] {
+                        if !config.is_enabled(strategy) {
+                            continue;
+                        }
+                        if let Some(sig) = ladder_evaluator::evaluate_ladder(
+                            Arc::make_mut(&mut self.groups[gi]),
+                            &self.price_table,
+                            &config,
+                            now_ms,
+                            strategy,
+                        ) {
+                            signals.push(sig);
+                        }
+                    }
+                }
+                // Same-kind parent YES tokens → strike monotonicity
+                TokenRole::MonotoneLeg { group_idx, pair_idx } => {
+                    let gi = group_idx as usize;
+                    if gi >= self.groups.len() {
+                        continue;
+                    }
+                    let config = self.groups[gi].config;
+                    if !config.is_enabled(StrategyKind::Monotone) {
+                        continue;
+                    }
+                    if let Some(sig) = monotone_evaluator::evaluate_monotone_pair(
+                        Arc::make_mut(&mut self.groups[gi]),
+                        pair_idx as usize,
+                        &self.price_table,
+                        &config,
+                        now_ms,
+                    ) {
+                        signals.push(sig);
+                    }
+                }
+                // YES / NO of one market → parity buy (merge) + sell (mint)
+                TokenRole::ParityLeg {
+                    group_idx,
+                    parity_idx,
+                } => {
+                    let gi = group_idx as usize;
+                    if gi >= self.groups.len() {
+                        continue;
+                    }
+                    let config = self.groups[gi].config;
+                    for strategy in [StrategyKind::ParityBuy, StrategyKind::ParitySell] {
+                        if !config.is_enabled(strategy) {
+                            continue;
+                        }
+                        if let Some(sig) = parity_evaluator::evaluate_parity(
+                            Arc::make_mut(&mut self.groups[gi]),
+                            parity_idx as usize,
+                            &self.price_table,
+                            &config,
+                            now_ms,
+                            strategy,
+                        ) {
+                            signals.push(sig);
+                        }
+                    }
+                }
+                // Neg-risk outcome → YES tokens feed YES BUY, NO tokens NO BUY; both feed CONVERT
+                TokenRole::NegRiskLeg {
+                    event_idx, is_no, ..
+                } => {
+                    let ei = event_idx as usize;
+                    if ei >= self.neg_risk_events.len() {
+                        continue;
+                    }
+                    let config = self.neg_risk_events[ei].config;
+                    let direct = if is_no {
+                        StrategyKind::NegRiskNoBuy
+                    } else {
+                        StrategyKind::NegRiskYesBuy
+                    };
+                    for strategy in [direct, StrategyKind::NegRiskConvert] {
+                        if !config.is_enabled(strategy) {
+                            continue;
+                        }
+                        if let Some(sig) = neg_risk_evaluator::evaluate_neg_risk_event(
+                            Arc::make_mut(&mut self.neg_risk_events[ei]),
+                            &self.price_table,
+                            &config,
+                            now_ms,
+                            strategy,
+                        ) {
+                            signals.push(sig);
+                        }
+                    }
+                }
             }
         }
 
+        // 4. Collect the opportunity lifecycle events the evaluators raised
+        for r in 0..role_count {
+            let role = self.token_index[id as usize][r];
+            self.collect_opportunity_events(role);
+        }
+
         signals
     }
 }
 
+// =============================================================================
+// TOKEN INDEX MAINTENANCE — Per-group add/remove of dispatch roles
+// =============================================================================
+
+/// Register all dispatch roles of a group under `group_idx`.
+fn index_group(token_index: &mut Vec<Vec<TokenRole>>, group: &GroupState, group_idx: u16) {
+    // Child tokens — YES only (range evaluator reads yes_slot)
+    for (child_idx, meta) in group.child_metas.iter().enumerate() {
+        push_role(
+            token_index,
+            meta.token_ids[0],
+            TokenRole::RangeChild {
+                group_idx,
+                child_idx: child_idx as u16,
+            },
+        );
+    }
+
+    // Parent tokens — YES only (range evaluator reads yes_slot)
+    for (parent_idx, meta) in group.parent_metas.iter().enumerate() {
+        push_role(
+            token_index,
+            meta.token_ids[0],
+            TokenRole::Parent {
+                group_idx,
+                parent_idx: parent_idx as u16,
+            },
+        );
+    }
+
+    for (trio_idx, trio) in group.trio_states.iter().enumerate() {
+        let lower = &group.parent_metas[trio.parent_lower_idx as usize];
+        let upper = &group.parent_metas[trio.parent_upper_idx as usize];
+        let range = &group.child_metas[trio.range_idx as usize];
+
+        // Triangle BUY legs (YES/NO/NO) + Complement BUY legs (NO/YES/YES)
+        let leg_roles = [
+            (lower.token_ids[0], TrioLegRole::ParentLowerYes),
+            (upper.token_ids[1], TrioLegRole::ParentUpperNo),
+            (range.token_ids[1], TrioLegRole::RangeNo),
+            (lower.token_ids[1], TrioLegRole::ParentLowerNo),
+            (range.token_ids[0], TrioLegRole::RangeYes),
+            (upper.token_ids[0], TrioLegRole::ParentUpperYes),
+        ];
+        for (id, role) in leg_roles {
+            push_role(
+                token_index,
+                id,
+                TokenRole::TrioLeg {
+                    group_idx,
+                    trio_idx: trio_idx as u16,
+                    role,
+                },
+            );
+        }
+    }
+
+    for (bracket_idx, bracket) in group.bracket_states.iter().enumerate() {
+        let lower = &group.parent_metas[bracket.parent_lower_idx as usize];
+        let upper = &group.parent_metas[bracket.parent_upper_idx as usize];
+        let children = bracket
+            .child_idxs
+            .iter()
+            .map(|&c| &group.child_metas[c as usize]);
+
+        // Triangle: lower YES, upper NO, children NO — Complement: lower NO, upper YES, children YES
+        let legs = [
+            (lower.token_ids[0], StrategyKind::Triangle),
+            (upper.token_ids[1], StrategyKind::Triangle),
+            (lower.token_ids[1], StrategyKind::Complement),
+            (upper.token_ids[0], StrategyKind::Complement),
+        ]
+        .into_iter()
+        .chain(children.flat_map(|c| {
+            [
+                (c.token_ids[1], StrategyKind::Triangle),
+                (c.token_ids[0], StrategyKind::Complement),
+            ]
+        }));
+        for (id, strategy) in legs {
+            push_role(
+                token_index,
+                id,
+                TokenRole::BracketLeg {
+                    group_idx,
+                    bracket_idx: bracket_idx as u16,
+                    strategy,
+                },
+            );
+        }
+    }
+
+    // Ladder legs — child YES tokens
+    if let Some(ladder) = &group.ladder {
+        for (leg_idx, &c) in ladder.child_idxs.iter().enumerate() {
+            push_role(
+                token_index,
+                group.child_metas[c as usize].token_ids[0],
+                TokenRole::LadderLeg {
+                    group_idx,
+                    leg_idx: leg_idx as u16,
+                },
+            );
+        }
+    }
+
+    // Monotonicity pairs — both parents' YES tokens
+    for (pair_idx, pair) in group.monotone_pairs.iter().enumerate() {
+        for parent_idx in [pair.buy_idx, pair.sell_idx] {
+            push_role(
+                token_index,
+                group.parent_metas[parent_idx as usize].token_ids[0],
+                TokenRole::MonotoneLeg {
+                    group_idx,
+                    pair_idx: pair_idx as u16,
+                },
+            );
+        }
+    }
+
+    // Parity — YES and NO of every market, once across all groups: a market
+    // another group already checks is skipped (see `EngineState::reclaim_parity`)
+    for (parity_idx, parity) in group.parity_states.iter().enumerate() {
+        let token_ids = parity.meta(group).token_ids;
+        if has_parity_role(token_index, token_ids[0]) {
+            continue;
+        }
+        for id in token_ids {
+            push_role(
+                token_index,
+                id,
+                TokenRole::ParityLeg {
+                    group_idx,
+                    parity_idx: parity_idx as u16,
+                },
+            );
+        }
+    }
+}
+
+/// Append a dispatch role to a token, growing the index as needed.
+fn push_role(token_index: &mut Vec<Vec<TokenRole>>, id: TokenId, role: TokenRole) {
+    let i = id as usize;
+    if i >= token_index.len() {
+        token_index.resize_with(i + 1, Vec::new);
+    }
+    token_index[i].push(role);
+}
+
+/// True if some group already dispatches parity for this token.
+fn has_parity_role(token_index: &[Vec<TokenRole>], id: TokenId) -> bool {
+    token_index
+        .get(id as usize)
+        .is_some_and(|roles| roles.iter().any(|r| matches!(r, TokenRole::ParityLeg { .. })))
+}
+
+/// Remove every dispatch role registered under `group_idx` for the group's tokens.
+fn unindex_group(token_index: &mut [Vec<TokenRole>], group: &GroupState, group_idx: u16) {
+    for meta in group.child_metas.iter().chain(group.parent_metas.iter()) {
+        for &id in &meta.token_ids {
+            if let Some(roles) = token_index.get_mut(id as usize) {
+                roles.retain(|r| r.group_idx() != Some(group_idx));
+            }
+        }
+    }
+}
+
+/// Register the dispatch roles of a neg-risk event under `event_idx`.
+fn index_neg_risk_event(token_index: &mut Vec<Vec<TokenRole>>, event: &NegRiskEventState, event_idx: u16) {
+    for (outcome_idx, outcome) in event.outcomes.iter().enumerate() {
+        for (side, &id) in outcome.token_ids.iter().enumerate() {
+            let i = id as usize;
+            if i >= token_index.len() {
+                token_index.resize_with(i + 1, Vec::new);
+            }
+            token_index[i].push(TokenRole::NegRiskLeg {
+                event_idx,
+                outcome_idx: outcome_idx as u16,
+                is_no: side == 1,
+            });
+        }
+    }
+}
+
+/// Remove the neg-risk roles registered under `event_idx` for the event's tokens.
+fn unindex_neg_risk_event(token_index: &mut [Vec<TokenRole>], event: &NegRiskEventState, event_idx: u16) {
+    for outcome in &event.outcomes {
+        for &id in &outcome.token_ids {
+            if let Some(roles) = token_index.get_mut(id as usize) {
+                roles.retain(|r| !matches!(r, TokenRole::NegRiskLeg { event_idx: e, .. } if *e == event_idx));
+            }
+        }
+    }
+}
+
+/// Key of a bracket that is stable across rebuilds: the market ids it spans.
+pub(crate) fn bracket_key(g: &GroupState, b: &BracketState) -> Vec<String> {
+    let mut key = vec![
+        g.parent_metas[b.parent_lower_idx as usize].market_id.clone(),
+        g.parent_metas[b.parent_upper_idx as usize].market_id.clone(),
+    ];
+    key.extend(b.child_idxs.iter().map(|&c| g.child_metas[c as usize].market_id.clone()));
+    key
+}
+
+/// Key of a group's ladder that is stable across rebuilds: its children's market ids.
+pub(crate) fn ladder_key(g: &GroupState) -> Option<Vec<String>> {
+    let ladder = g.ladder.as_ref()?;
+    Some(ladder.child_idxs.iter().map(|&c| g.child_metas[c as usize].market_id.clone()).collect())
+}
+
+/// Key of a monotonicity pair that is stable across rebuilds: (buy, sell) market ids.
+pub(crate) fn monotone_key<'a>(g: &'a GroupState, p: &MonotonePairState) -> (&'a str, &'a str) {
+    (
+        g.parent_metas[p.buy_idx as usize].market_id.as_str(),
+        g.parent_metas[p.sell_idx as usize].market_id.as_str(),
+    )
+}
+
+/// Copy cooldown timestamps from trios of `old` that still exist in `new`.
+/// Carried opportunity trackers are reset in `old`, so whatever is still open
+/// there afterwards belongs to a structure the rebuild dropped.
+fn carry_over_cooldowns(old: &mut GroupState, new: &mut GroupState) {
+    let previous: HashMap<(&str, &str, &str), usize> = old
+        .trio_states
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            (
+                (
+                    t.lower_yes_token.as_str(),
+                    t.upper_no_token.as_str(),
+                    t.range_no_token.as_str(),
+                ),
+                i,
+            )
+        })
+        .collect();
+    let mut carried = Vec::new();
+    for trio in &mut new.trio_states {
+        let key = (
+            trio.lower_yes_token.as_str(),
+            trio.upper_no_token.as_str(),
+            trio.range_no_token.as_str(),
+        );
+        if let Some(&i) = previous.get(&key) {
+            let prev = &old.trio_states[i];
+            trio.last_emitted_buy_ms = prev.last_emitted_buy_ms;
+            trio.last_emitted_complement_ms = prev.last_emitted_complement_ms;
+            trio.last_emitted_unbundle_ms = prev.last_emitted_unbundle_ms;
+            trio.last_emitted_bundle_ms = prev.last_emitted_bundle_ms;
+            trio.edge_stats = prev.edge_stats;
+            trio.emission = prev.emission;
+            trio.opportunity = prev.opportunity;
+            carried.push(i);
+        }
+    }
+    for i in carried {
+        old.trio_states[i].opportunity = Default::default();
+    }
+
+    // Brackets are keyed by the market ids they span
+    let previous: HashMap<Vec<String>, usize> = old
+        .bracket_states
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (bracket_key(old, b), i))
+        .collect();
+    let keys: Vec<Vec<String>> = new.bracket_states.iter().map(|b| bracket_key(new, b)).collect();
+    for (bracket, key) in new.bracket_states.iter_mut().zip(keys) {
+        if let Some(&i) = previous.get(&key) {
+            let prev = &mut old.bracket_states[i];
+            bracket.last_emitted_buy_ms = prev.last_emitted_buy_ms;
+            bracket.last_emitted_complement_ms = prev.last_emitted_complement_ms;
+            bracket.edge_stats = prev.edge_stats;
+            bracket.emission = prev.emission;
+            bracket.opportunity = std::mem::take(&mut prev.opportunity);
+        }
+    }
+
+    // The ladder is keyed by its children's market ids
+    let prev_key = ladder_key(old);
+    if let (Some(prev), Some(prev_key)) = (old.ladder.as_mut(), prev_key) {
+        if ladder_key(new) == Some(prev_key) {
+            let ladder = new.ladder.as_mut().expect("keyed ladder exists");
+            ladder.last_emitted_buy_ms = prev.last_emitted_buy_ms;
+            ladder.last_emitted_sell_ms = prev.last_emitted_sell_ms;
+            ladder.edge_stats = prev.edge_stats;
+            ladder.emission = prev.emission;
+            ladder.opportunity = std::mem::take(&mut prev.opportunity);
+        }
+    }
+
+    // Monotonicity pairs are keyed by (buy, sell) market ids
+    let previous: HashMap<(&str, &str), usize> = old
+        .monotone_pairs
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (monotone_key(old, p), i))
+        .collect();
+    let mut carried = Vec::new();
+    for pair in &mut new.monotone_pairs {
+        let key = (
+            new.parent_metas[pair.buy_idx as usize].market_id.as_str(),
+            new.parent_metas[pair.sell_idx as usize].market_id.as_str(),
+        );
+        if let Some(&i) = previous.get(&key) {
+            let prev = &old.monotone_pairs[i];
+            pair.last_emitted_ms = prev.last_emitted_ms;
+            pair.edge_stats = prev.edge_stats;
+            pair.emission = prev.emission;
+            pair.opportunity = prev.opportunity;
+            carried.push(i);
+        }
+    }
+    for i in carried {
+        old.monotone_pairs[i].opportunity = Default::default();
+    }
+
+    // Parity states are keyed by market id
+    let previous: HashMap<String, usize> = old
+        .parity_states
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.meta(old).market_id.clone(), i))
+        .collect();
+    let keys: Vec<String> = new.parity_states.iter().map(|p| p.meta(new).market_id.clone()).collect();
+    for (parity, key) in new.parity_states.iter_mut().zip(keys) {
+        if let Some(&i) = previous.get(&key) {
+            let prev = &mut old.parity_states[i];
+            parity.last_emitted_buy_ms = prev.last_emitted_buy_ms;
+            parity.last_emitted_sell_ms = prev.last_emitted_sell_ms;
+            parity.edge_stats = prev.edge_stats;
+            parity.emission = prev.emission;
+            parity.opportunity = std::mem::take(&mut prev.opportunity);
+        }
+    }
+}
+
 // =============================================================================
 // TRIO INITIALIZATION — Adjacent parent pairs + connecting range
 // =============================================================================
 
+/// Trio indices per market, used to dispatch range child / parent updates.
+struct TrioLookup {
+    by_child: Vec<Vec<u16>>,
+    by_parent: Vec<Vec<u16>>,
+}
+
 /// Build TrioStates from adjacent parent pairs.
 ///
 /// Structure: Parent[i] YES + Range(i→i+1) NO + Parent[i+1] NO
 fn initialize_trio_states(
     child_metas: &[MarketMeta],
     parent_metas: &[MarketMeta],
-) -> (Vec<TrioState>, HashMap<String, Vec<u16>>) {
+) -> (Vec<TrioState>, TrioLookup) {
     let mut trios = Vec::new();
-    let mut trio_lookup: HashMap<String, Vec<u16>> = HashMap::new();
+    let mut lookup = TrioLookup {
+        by_child: vec![Vec::new(); child_metas.len()],
+        by_parent: vec![Vec::new(); parent_metas.len()],
+    };
 
     // O(1) lookup: lower_bound → child index (range markets only)
     let mut range_lower_map: HashMap<i64, usize> = HashMap::new();
@@ -374,48 +1228,267 @@ fn initialize_trio_states(
             lower_no_token: lower_no_token.clone(),
             range_yes_token: range_yes_token.clone(),
             upper_yes_token: upper_yes_token.clone(),
+            // Taker fee rates
+            triangle_fee_bps: [lower.fee_rate_bps[0], upper.fee_rate_bps[1], range_child.fee_rate_bps[1]],
+            complement_fee_bps: [lower.fee_rate_bps[1], range_child.fee_rate_bps[0], upper.fee_rate_bps[0]],
             // Cooldowns
             last_emitted_buy_ms: 0,
             last_emitted_complement_ms: 0,
             last_emitted_unbundle_ms: 0,
             last_emitted_bundle_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+            emission: [EmissionLatch::default(); StrategyKind::COUNT],
+            opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
         };
 
         let trio_idx = trios.len() as u16;
         trios.push(trio);
 
-        // Index ALL tokens: 3 triangle + 3 complement (some may overlap)
-        for token in [
-            lower_yes_token,
-            upper_no_token,
-            range_no_token,
-            lower_no_token,
-            upper_yes_token,
-            range_yes_token,
-        ] {
-            trio_lookup.entry(token.clone()).or_default().push(trio_idx);
-        }
+        // Index by market: the range child and both parents
+        lookup.by_child[range_idx].push(trio_idx);
+        lookup.by_parent[lower_idx].push(trio_idx);
+        lookup.by_parent[lower_idx + 1].push(trio_idx);
     }
 
-    (trios, trio_lookup)
+    (trios, lookup)
 }
 
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn make_engine() -> EngineState {
-        EngineState::new(EngineConfig {
-            min_profit_abs: 0.005,
-            min_profit_bps: 30.0,
-            cooldown_ms: 0, // disable cooldown for tests
-        })
+/// Build brackets: every above-parent pair (i, j) whose bounds are joined by a
+/// chain of at most `max_children` consecutive range children, except pairs
+/// already covered by a trio. `max_children == 0` builds none.
+///
+/// Structure: Parent[i] YES + Range(L_i→…→L_j) NO × k + Parent[j] NO
+fn initialize_bracket_states(
+    child_metas: &[MarketMeta],
+    parent_metas: &[MarketMeta],
+    trio_states: &[TrioState],
+    max_children: usize,
+) -> Vec<BracketState> {
+    let mut brackets = Vec::new();
+    if max_children == 0 {
+        return brackets;
     }
 
-    fn make_group_input() -> RangeGroupInput {
-        RangeGroupInput {
-            group_key: "eth-2026-01-20T17:00:00.000Z".to_string(),
+    // lower_bound → child index (range markets with finite, increasing bounds)
+    let mut range_lower_map: HashMap<u64, usize> = HashMap::new();
+    for (i, meta) in child_metas.iter().enumerate() {
+        if meta.kind != MarketKind::Range || meta.clob_token_ids.iter().any(String::is_empty) {
+            continue;
+        }
+        if let (Some(lower), Some(upper)) = (meta.bounds_lower, meta.bounds_upper) {
+            if lower.is_finite() && upper.is_finite() && upper > lower {
+                range_lower_map.insert(lower.to_bits(), i);
+            }
+        }
+    }
+
+    let parent_lower = |p: &MarketMeta| match p.bounds_lower {
+        Some(v) if v.is_finite() && p.kind == MarketKind::Above => Some(v),
+        _ => None,
+    };
+    let is_trio = |lower: usize, upper: usize, child: usize| {
+        trio_states.iter().any(|t| {
+            t.parent_lower_idx as usize == lower
+                && t.parent_upper_idx as usize == upper
+                && t.range_idx as usize == child
+        })
+    };
+
+    for (lower_idx, lower) in parent_metas.iter().enumerate() {
+        let Some(start) = parent_lower(lower) else {
+            continue;
+        };
+
+        // Walk the child chain upwards; every parent whose bound the chain reaches closes a bracket
+        let mut chain: Vec<u16> = Vec::new();
+        let mut at = start;
+        while chain.len() < max_children {
+            let Some(&child_idx) = range_lower_map.get(&at.to_bits()) else {
+                break;
+            };
+            chain.push(child_idx as u16);
+            at = child_metas[child_idx].bounds_upper.unwrap_or(f64::NAN);
+
+            for (upper_idx, upper) in parent_metas.iter().enumerate() {
+                if parent_lower(upper) != Some(at) {
+                    continue;
+                }
+                if chain.len() == 1 && is_trio(lower_idx, upper_idx, child_idx) {
+                    continue;
+                }
+                brackets.push(BracketState {
+                    parent_lower_idx: lower_idx as u16,
+                    parent_upper_idx: upper_idx as u16,
+                    child_idxs: chain.clone(),
+                    last_emitted_buy_ms: 0,
+                    last_emitted_complement_ms: 0,
+                    stale_skip_count: 0,
+                    edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+                    emission: [EmissionLatch::default(); StrategyKind::COUNT],
+                    opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
+                });
+            }
+        }
+    }
+
+    brackets
+}
+
+// =============================================================================
+// LADDER INITIALIZATION — Complete partition of the outcome space
+// =============================================================================
+
+/// Outcome interval `[lower, upper)` a child covers; open ends are infinite.
+/// `None` if the child's bounds do not describe an interval.
+fn outcome_span(meta: &MarketMeta) -> Option<(f64, f64)> {
+    let (lower, upper) = match meta.kind {
+        MarketKind::Above => (meta.bounds_lower?, f64::INFINITY),
+        MarketKind::Below => (f64::NEG_INFINITY, meta.bounds_upper?),
+        MarketKind::Range => {
+            if meta.bounds_lower.is_none() && meta.bounds_upper.is_none() {
+                return None;
+            }
+            (
+                meta.bounds_lower.unwrap_or(f64::NEG_INFINITY),
+                meta.bounds_upper.unwrap_or(f64::INFINITY),
+            )
+        }
+    };
+    (lower < upper).then_some((lower, upper))
+}
+
+/// Build the group's ladder if its children partition the outcome space:
+/// sorted by bound, the first is open below, the last open above, and each
+/// child starts exactly where the previous one ends. Leg sums are seeded from
+/// the PriceTable (prices survive structure rebuilds).
+fn initialize_ladder_state(child_metas: &[MarketMeta], pt: &PriceTable) -> Option<LadderState> {
+    if child_metas.len() < 2 {
+        return None;
+    }
+
+    let mut spans = Vec::with_capacity(child_metas.len());
+    for (i, meta) in child_metas.iter().enumerate() {
+        spans.push((outcome_span(meta)?, i as u16));
+    }
+    spans.sort_by(|a, b| a.0 .0.total_cmp(&b.0 .0));
+
+    let first = spans[0].0;
+    let last = spans[spans.len() - 1].0;
+    if first.0 != f64::NEG_INFINITY || last.1 != f64::INFINITY {
+        return None;
+    }
+    if spans.windows(2).any(|pair| pair[0].0 .1 != pair[1].0 .0) {
+        return None;
+    }
+
+    let child_idxs: Vec<u16> = spans.into_iter().map(|(_, i)| i).collect();
+    let fee_rates = child_idxs.iter().map(|&c| child_metas[c as usize].fee_rate_bps[0]).collect();
+    let mut ladder = LadderState::new(child_idxs, fee_rates);
+    for (leg, &c) in ladder.child_idxs.iter().enumerate() {
+        let q = pt.get(child_metas[c as usize].yes_slot);
+        ladder.bids[leg] = q.best_bid;
+        ladder.asks[leg] = q.best_ask;
+    }
+    ladder.resync_sums();
+    Some(ladder)
+}
+
+// =============================================================================
+// MONOTONE PAIR INITIALIZATION — Same-kind parents sorted by strike
+// =============================================================================
+
+/// Build every monotonicity pair among above parents and among below parents.
+///
+/// Parents are sorted by strike (above: lower bound, below: upper bound); each
+/// pair with distinct finite strikes yields one state. Above: BUY lower strike,
+/// SELL higher. Below: BUY higher strike, SELL lower.
+fn initialize_monotone_pairs(parent_metas: &[MarketMeta]) -> Vec<MonotonePairState> {
+    let mut pairs = Vec::new();
+
+    for kind in [MarketKind::Above, MarketKind::Below] {
+        let mut ladder: Vec<(f64, u16)> = parent_metas
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.kind == kind)
+            .filter_map(|(i, m)| {
+                let strike = match kind {
+                    MarketKind::Above => m.bounds_lower,
+                    _ => m.bounds_upper,
+                }?;
+                strike.is_finite().then_some((strike, i as u16))
+            })
+            .collect();
+        ladder.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (lo, &(lo_strike, lo_idx)) in ladder.iter().enumerate() {
+            for &(hi_strike, hi_idx) in &ladder[lo + 1..] {
+                if hi_strike == lo_strike {
+                    continue;
+                }
+                let (buy_idx, sell_idx) = match kind {
+                    MarketKind::Above => (lo_idx, hi_idx),
+                    _ => (hi_idx, lo_idx),
+                };
+                pairs.push(MonotonePairState {
+                    buy_idx,
+                    sell_idx,
+                    kind,
+                    last_emitted_ms: 0,
+                    stale_skip_count: 0,
+                    edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+                    emission: [EmissionLatch::default(); StrategyKind::COUNT],
+                    opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
+                });
+            }
+        }
+    }
+
+    pairs
+}
+
+// =============================================================================
+// PARITY INITIALIZATION — One state per market
+// =============================================================================
+
+/// One parity state per child, then per parent.
+fn initialize_parity_states(child_metas: &[MarketMeta], parent_metas: &[MarketMeta]) -> Vec<ParityState> {
+    let children = (0..child_metas.len()).map(|i| (false, i));
+    let parents = (0..parent_metas.len()).map(|i| (true, i));
+    children
+        .chain(parents)
+        .map(|(is_parent, i)| ParityState {
+            is_parent,
+            market_idx: i as u16,
+            last_emitted_buy_ms: 0,
+            last_emitted_sell_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+            emission: [EmissionLatch::default(); StrategyKind::COUNT],
+            opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_support::{market, neg_risk_event};
+    use crate::types::signal::{FlatArbSignal, OpportunityPhase};
+
+    fn make_engine() -> EngineState {
+        EngineState::new(EngineConfig {
+            min_profit_abs: 0.005,
+            min_profit_bps: 30.0,
+            cooldown_ms: 0, // disable cooldown for tests
+            strategy_enabled: [true; StrategyKind::COUNT],
+            ..EngineConfig::default()
+        })
+    }
+
+    fn make_group_input() -> RangeGroupInput {
+        RangeGroupInput {
+            group_key: "eth-2026-01-20T17:00:00.000Z".to_string(),
             event_slug: "eth-price".to_string(),
             crypto: "ETH".to_string(),
             children: vec![MarketDescriptorInput {
@@ -429,6 +1502,7 @@ mod tests {
                 bounds_upper: Some(2900.0),
                 kind: "range".to_string(),
                 neg_risk: false,
+                fee_rate_bps: [0, 0],
             }],
             parents: vec![
                 MarketDescriptorInput {
@@ -442,6 +1516,7 @@ mod tests {
                     bounds_upper: None,
                     kind: "above".to_string(),
                     neg_risk: false,
+                    fee_rate_bps: [0, 0],
                 },
                 MarketDescriptorInput {
                     market_id: "above-2900".to_string(),
@@ -454,6 +1529,7 @@ mod tests {
                     bounds_upper: None,
                     kind: "above".to_string(),
                     neg_risk: false,
+                    fee_rate_bps: [0, 0],
                 },
             ],
         }
@@ -479,14 +1555,413 @@ mod tests {
         // Verify PriceTable has 6 slots (2 per market × 3 markets)
         assert_eq!(engine.price_table.slots.len(), 6);
 
-        // Verify all 6 tokens are in trio_lookup (3 triangle + 3 complement)
-        let lookup = &engine.groups[0].trio_lookup_by_asset;
-        assert!(lookup.contains_key("parent_lower_yes"));
-        assert!(lookup.contains_key("parent_upper_no"));
-        assert!(lookup.contains_key("range_no_token"));
-        assert!(lookup.contains_key("parent_lower_no"));
-        assert!(lookup.contains_key("parent_upper_yes"));
-        assert!(lookup.contains_key("range_yes_token"));
+        // Verify the range child and both parents map back to the trio
+        let group = &engine.groups[0];
+        assert_eq!(group.trios_by_child[trio.range_idx as usize], vec![0]);
+        assert_eq!(group.trios_by_parent[trio.parent_lower_idx as usize], vec![0]);
+        assert_eq!(group.trios_by_parent[trio.parent_upper_idx as usize], vec![0]);
+    }
+
+    #[test]
+    fn test_structure_diagnostics() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+        assert!(engine.groups[0].diagnostics.is_clean());
+
+        let mut no_tokens = market("no-tokens", "only_yes", "", Some(2900.0), Some(3000.0), "range");
+        no_tokens.clob_token_ids.truncate(1);
+        let mut input = make_group_input();
+        input.children.extend([
+            no_tokens,
+            // Overlaps range-2800-2900, leaves a gap up to 3100
+            market("range-2850-3000", "r2_yes", "r2_no", Some(2850.0), Some(3000.0), "range"),
+            market("range-3100-3200", "r3_yes", "parent_lower_no", Some(3100.0), Some(3200.0), "range"),
+        ]);
+        input.parents.extend([
+            market("above-3100", "p3_yes", "p3_no", Some(3100.0), None, "above"),
+            market("below-3300", "p4_yes", "p4_no", Some(3300.0), None, "below"),
+        ]);
+        engine.update_market_structure(vec![input]);
+
+        let d = &engine.groups[0].diagnostics;
+        assert_eq!(engine.groups[0].trio_states.len(), 1);
+        assert_eq!(d.rejected_markets.len(), 1);
+        assert_eq!(d.rejected_markets[0].market_id, "no-tokens");
+        assert_eq!(d.rejected_markets[0].role, "child");
+        assert_eq!(d.bound_overlaps.len(), 1);
+        assert_eq!(d.bound_overlaps[0].upper_market_id, "range-2850-3000");
+        assert_eq!(d.bound_gaps.len(), 1);
+        assert_eq!((d.bound_gaps[0].from, d.bound_gaps[0].to), (3000.0, 3100.0));
+        assert_eq!(d.duplicate_tokens.len(), 1);
+        assert_eq!(d.duplicate_tokens[0].token_id, "parent_lower_no");
+        let orphans: Vec<&str> = d
+            .parents_without_trio
+            .iter()
+            .map(|p| p.market_id.as_str())
+            .collect();
+        assert_eq!(orphans, ["above-3100", "below-3300"]);
+        assert!(d.notes.iter().any(|n| n.contains("mixed parent kinds")));
+    }
+
+    fn make_ladder_input() -> RangeGroupInput {
+        RangeGroupInput {
+            group_key: "eth-ladder".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![
+                market("range-2800-2900", "c1_yes", "c1_no", Some(2800.0), Some(2900.0), "range"),
+                market("range-2900-3000", "c2_yes", "c2_no", Some(2900.0), Some(3000.0), "range"),
+            ],
+            parents: vec![
+                market("above-2800", "p1_yes", "p1_no", Some(2800.0), None, "above"),
+                market("above-2900", "p2_yes", "p2_no", Some(2900.0), None, "above"),
+                market("above-3000", "p3_yes", "p3_no", Some(3000.0), None, "above"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_bracket_spans_multiple_children() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_ladder_input()]);
+        assert_eq!(engine.groups[0].trio_states.len(), 2);
+        assert!(engine.groups[0].bracket_states.is_empty()); // off by default
+
+        engine.config.max_bracket_children = 2;
+        engine.refresh_group_configs();
+        engine.rebuild_groups();
+        let bracket = &engine.groups[0].bracket_states;
+        assert_eq!(bracket.len(), 1);
+        assert_eq!((bracket[0].parent_lower_idx, bracket[0].parent_upper_idx), (0, 2));
+        assert_eq!(bracket[0].child_idxs, vec![0, 1]);
+
+        // Adjacent trios stay above payout 2 (middle parent is expensive both ways)
+        engine.handle_top_of_book("p2_yes", 0.68, 0.70, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("p2_no", 0.68, 0.70, Some(100.0), Some(100.0), 2);
+        engine.handle_top_of_book("p1_yes", 0.58, 0.60, Some(40.0), Some(40.0), 3);
+        engine.handle_top_of_book("p3_no", 0.53, 0.55, Some(100.0), Some(100.0), 4);
+        engine.handle_top_of_book("c1_no", 0.78, 0.80, Some(100.0), Some(100.0), 5);
+        // Bracket: 0.60 + 0.55 + 0.80 + 0.85 = 2.80 < payout 3
+        let signals =
+            engine.handle_top_of_book("c2_no", 0.83, 0.85, Some(100.0), Some(100.0), 6);
+
+        assert_eq!(signals.len(), 1);
+        let sig = FlatArbSignal::from(&signals[0]);
+        assert_eq!(sig.strategy, "POLYMARKET_TRIANGLE_BUY");
+        assert_eq!(sig.parent_asset_id, "p1_yes");
+        assert_eq!(sig.parent_upper_asset_id, "p3_no");
+        assert_eq!(sig.child_asset_id, "c1_no");
+        assert_eq!(sig.extra_child_legs.len(), 1);
+        assert_eq!(sig.extra_child_legs[0].asset_id, "c2_no");
+        assert_eq!(sig.triangle_payout, Some(3.0));
+        assert!((sig.profit_abs - 0.20).abs() < 1e-9);
+        assert!((sig.children_sum_ask - 1.65).abs() < 1e-9);
+        assert_eq!(sig.max_executable_size, 40.0);
+    }
+
+    #[test]
+    fn test_bracket_parents_are_not_orphans() {
+        let mut engine = make_engine();
+        engine.config.max_bracket_children = 2;
+        let mut input = make_ladder_input();
+        input.parents.remove(1); // no above-2900 → no trios, one 2-child bracket
+        engine.update_market_structure(vec![input]);
+
+        let group = &engine.groups[0];
+        assert!(group.trio_states.is_empty());
+        assert_eq!(group.bracket_states.len(), 1);
+        assert!(group.diagnostics.parents_without_trio.is_empty());
+    }
+
+    fn make_partition_input(upper_tail_lower: f64) -> RangeGroupInput {
+        RangeGroupInput {
+            group_key: "eth-partition".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![
+                market("above-3000", "above-3000_yes", "above-3000_no", Some(upper_tail_lower), None, "above"),
+                market("r2800", "r2800_yes", "r2800_no", Some(2800.0), Some(2900.0), "range"),
+                market("below-2800", "below-2800_yes", "below-2800_no", None, Some(2800.0), "below"),
+                market("r2900", "r2900_yes", "r2900_no", Some(2900.0), Some(3000.0), "range"),
+            ],
+            parents: vec![],
+        }
+    }
+
+    #[test]
+    fn test_ladder_requires_complete_partition() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_partition_input(3000.0)]);
+        let ladder = engine.groups[0].ladder.as_ref().expect("complete partition");
+        assert_eq!(ladder.child_idxs, vec![2, 1, 3, 0]); // sorted by bound
+
+        // Gap between 3000 and 3100 → no ladder
+        engine.update_market_structure(vec![make_partition_input(3100.0)]);
+        assert!(engine.groups[0].ladder.is_none());
+    }
+
+    #[test]
+    fn test_ladder_buy_and_sell() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_partition_input(3000.0)]);
+
+        engine.handle_top_of_book("below-2800_yes", 0.18, 0.20, Some(50.0), Some(50.0), 1);
+        engine.handle_top_of_book("r2800_yes", 0.28, 0.30, Some(50.0), Some(30.0), 2);
+        engine.handle_top_of_book("r2900_yes", 0.23, 0.25, Some(50.0), Some(50.0), 3);
+        // Σ ask = 0.20 + 0.30 + 0.25 + 0.20 = 0.95 < 1
+        let signals =
+            engine.handle_top_of_book("above-3000_yes", 0.18, 0.20, Some(50.0), Some(50.0), 4);
+        assert_eq!(signals.len(), 1);
+        let sig = FlatArbSignal::from(&signals[0]);
+        assert_eq!(sig.strategy, "POLYMARKET_LADDER_BUY");
+        assert_eq!(sig.child_asset_id, "below-2800_yes");
+        assert_eq!(sig.extra_child_legs.len(), 3);
+        assert!((sig.profit_abs - 0.05).abs() < 1e-9);
+        assert!((sig.children_sum_ask - 0.95).abs() < 1e-9);
+        assert_eq!(sig.max_executable_size, 30.0);
+
+        // Bids move up: Σ bid = 0.18 + 0.28 + 0.23 + 0.36 = 1.05 > 1 (incremental sums)
+        let signals =
+            engine.handle_top_of_book("above-3000_yes", 0.36, 0.38, Some(50.0), Some(50.0), 5);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].strategy, StrategyKind::LadderSell);
+        assert!((signals[0].profit_abs - 0.05).abs() < 1e-9);
+        let ladder = engine.groups[0].ladder.as_ref().unwrap();
+        assert!((ladder.sum_ask - 1.13).abs() < 1e-9);
+        assert_eq!((ladder.missing_bids, ladder.missing_asks), (0, 0));
+    }
+
+    #[test]
+    fn test_ladder_profit_net_of_fees() {
+        let mut engine = make_engine();
+        let mut input = make_partition_input(3000.0);
+        for child in &mut input.children {
+            child.fee_rate_bps = [100, 100];
+        }
+        engine.update_market_structure(vec![input]);
+
+        engine.handle_top_of_book("below-2800_yes", 0.18, 0.20, Some(50.0), Some(50.0), 1);
+        engine.handle_top_of_book("r2800_yes", 0.28, 0.30, Some(50.0), Some(50.0), 2);
+        engine.handle_top_of_book("r2900_yes", 0.23, 0.25, Some(50.0), Some(50.0), 3);
+        // Gross 1 - 0.95 = 0.05; fees 1% × (0.20 + 0.30 + 0.25 + 0.20) = 0.0095
+        let signals =
+            engine.handle_top_of_book("above-3000_yes", 0.18, 0.20, Some(50.0), Some(50.0), 4);
+        assert_eq!(signals.len(), 1);
+        let sig = FlatArbSignal::from(&signals[0]);
+        assert!((sig.gross_profit_abs - 0.05).abs() < 1e-9);
+        assert!((sig.profit_abs - 0.0405).abs() < 1e-9);
+        assert!(sig.profit_bps < sig.gross_profit_bps);
+        assert_eq!(sig.child_fee_rate_bps, 100);
+        assert!(sig.extra_child_legs.iter().all(|l| l.fee_rate_bps == 100));
+
+        // Incremental fee sum tracks quote changes
+        engine.handle_top_of_book("above-3000_yes", 0.18, 0.40, Some(50.0), Some(50.0), 5);
+        let ladder = engine.groups[0].ladder.as_ref().unwrap();
+        assert!((ladder.sum_ask_fee - 0.0115).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_disabled_ladder_skips_updates_until_enabled() {
+        let mut engine = make_engine();
+        engine.config.strategy_enabled = [false; StrategyKind::COUNT];
+        engine.update_market_structure(vec![make_partition_input(3000.0)]);
+        let snapshot = engine.snapshot();
+
+        // No enabled strategy reads the ladder → ticks never copy the group
+        engine.handle_top_of_book("below-2800_yes", 0.18, 0.20, Some(50.0), Some(50.0), 1);
+        engine.handle_top_of_book("r2800_yes", 0.28, 0.30, Some(50.0), Some(50.0), 2);
+        engine.handle_top_of_book("r2900_yes", 0.23, 0.25, Some(50.0), Some(50.0), 3);
+        engine.handle_top_of_book("above-3000_yes", 0.18, 0.20, Some(50.0), Some(50.0), 4);
+        assert!(Arc::ptr_eq(&engine.groups[0], &snapshot.groups[0]));
+
+        // Enabling reloads the sums from the PriceTable
+        engine.config.strategy_enabled[StrategyKind::LadderBuy.index()] = true;
+        engine.refresh_group_configs();
+        let ladder = engine.groups[0].ladder.as_ref().unwrap();
+        assert!((ladder.sum_ask - 0.95).abs() < 1e-9);
+        assert_eq!((ladder.missing_bids, ladder.missing_asks), (0, 0));
+        let signals =
+            engine.handle_top_of_book("r2900_yes", 0.23, 0.24, Some(50.0), Some(50.0), 5);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].strategy, StrategyKind::LadderBuy);
+    }
+
+    fn make_strike_input() -> RangeGroupInput {
+        RangeGroupInput {
+            group_key: "eth-strikes".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![],
+            parents: vec![
+                market("above-3000", "above-3000_yes", "above-3000_no", Some(3000.0), None, "above"),
+                market("above-2800", "above-2800_yes", "above-2800_no", Some(2800.0), None, "above"),
+                market("below-3000", "below-3000_yes", "below-3000_no", None, Some(3000.0), "below"),
+                market("above-2900", "above-2900_yes", "above-2900_no", Some(2900.0), None, "above"),
+                market("below-2800", "below-2800_yes", "below-2800_no", None, Some(2800.0), "below"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_monotone_pairs_sorted_by_strike() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_strike_input()]);
+        let group = &engine.groups[0];
+        let ids = |i: u16| group.parent_metas[i as usize].market_id.as_str();
+        let pairs: Vec<(&str, &str)> = group
+            .monotone_pairs
+            .iter()
+            .map(|p| (ids(p.buy_idx), ids(p.sell_idx)))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("above-2800", "above-2900"),
+                ("above-2800", "above-3000"),
+                ("above-2900", "above-3000"),
+                ("below-3000", "below-2800"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monotone_crossing_emits_buy_sell_pair() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_strike_input()]);
+
+        // Consistent: above-2800 asks more than above-2900 bids
+        engine.handle_top_of_book("above-2900_yes", 0.45, 0.47, Some(80.0), Some(80.0), 1);
+        let signals =
+            engine.handle_top_of_book("above-2800_yes", 0.50, 0.52, Some(60.0), Some(60.0), 2);
+        assert!(signals.is_empty());
+
+        // Crossed: Ask(above 2800) = 0.40 < Bid(above 2900) = 0.45
+        let signals =
+            engine.handle_top_of_book("above-2800_yes", 0.38, 0.40, Some(60.0), Some(60.0), 3);
+        assert_eq!(signals.len(), 1);
+        let sig = FlatArbSignal::from(&signals[0]);
+        assert_eq!(sig.strategy, "POLYMARKET_MONOTONE_BUY_SELL");
+        assert_eq!(sig.reason, "POLYMARKET_MONOTONE_ABOVE_ASK_LT_BID");
+        assert_eq!(sig.parent_asset_id, "above-2800_yes");
+        assert_eq!(sig.parent_upper_asset_id, "above-2900_yes");
+        assert!((sig.profit_abs - 0.05).abs() < 1e-9);
+        assert_eq!(sig.max_executable_size, 60.0);
+
+        // Below: Ask(below 3000) = 0.50 < Bid(below 2800) = 0.55
+        engine.handle_top_of_book("below-2800_yes", 0.55, 0.57, Some(50.0), Some(50.0), 4);
+        let signals =
+            engine.handle_top_of_book("below-3000_yes", 0.48, 0.50, Some(50.0), Some(50.0), 5);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].reason, "POLYMARKET_MONOTONE_BELOW_ASK_LT_BID");
+        assert_eq!(signals[0].legs[0].asset_id, "below-3000_yes");
+        assert_eq!(signals[0].legs[1].asset_id, "below-2800_yes");
+    }
+
+    #[test]
+    fn test_parity_buy_and_sell() {
+        let mut engine = make_engine();
+        engine.config.strategy_enabled = [false; StrategyKind::COUNT];
+        engine.config.strategy_enabled[StrategyKind::ParityBuy.index()] = true;
+        engine.config.strategy_enabled[StrategyKind::ParitySell.index()] = true;
+        engine.update_market_structure(vec![make_group_input()]);
+        assert_eq!(engine.groups[0].parity_states.len(), 3);
+
+        engine.handle_top_of_book("range_yes_token", 0.40, 0.42, Some(70.0), Some(70.0), 1000);
+        // Ask(YES) + Ask(NO) = 0.42 + 0.52 = 0.94 < 1
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.50, 0.52, Some(90.0), Some(90.0), 1001);
+        assert_eq!(signals.len(), 1);
+        let sig = FlatArbSignal::from(&signals[0]);
+        assert_eq!(sig.strategy, "POLYMARKET_PARITY_BUY");
+        assert_eq!(sig.parent_asset_id, "range_yes_token");
+        assert_eq!(sig.parent_upper_asset_id, "range_no_token");
+        assert!((sig.profit_abs - 0.06).abs() < 1e-9);
+        assert_eq!(sig.max_executable_size, 70.0);
+
+        // Bid(YES) + Bid(NO) = 0.55 + 0.50 = 1.05 > 1; asks now sum to 1.09
+        let signals =
+            engine.handle_top_of_book("range_yes_token", 0.55, 0.57, Some(70.0), Some(70.0), 1002);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].strategy, StrategyKind::ParitySell);
+        assert!((signals[0].profit_abs - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parity_shared_market_checked_once() {
+        let mut engine = make_engine();
+        engine.config.strategy_enabled = [false; StrategyKind::COUNT];
+        engine.config.strategy_enabled[StrategyKind::ParityBuy.index()] = true;
+        let mut other = make_group_input();
+        other.group_key = "eth-2026-01-20T18:00:00.000Z".to_string();
+        engine.update_market_structure(vec![make_group_input(), other]);
+        assert_eq!(engine.groups[1].parity_states.len(), 3);
+
+        // Both groups list the same three markets — one signal, not two
+        engine.handle_top_of_book("range_yes_token", 0.40, 0.42, Some(70.0), Some(70.0), 1000);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.50, 0.52, Some(90.0), Some(90.0), 1001);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].group_key, "eth-2026-01-20T17:00:00.000Z");
+
+        // The remaining group takes the market over once the owner is removed
+        assert!(engine.remove_group("eth-2026-01-20T17:00:00.000Z"));
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.49, 0.51, Some(90.0), Some(90.0), 1002);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].group_key, "eth-2026-01-20T18:00:00.000Z");
+    }
+
+    #[test]
+    fn test_neg_risk_events_replace_and_skip_incomplete() {
+        let mut engine = make_engine();
+        let mut broken = neg_risk_event(&["x", "y"]);
+        broken.outcomes[1].clob_token_ids.pop();
+        let tracked = engine.update_neg_risk_events(vec![
+            neg_risk_event(&["cut", "hold", "hike"]),
+            broken,
+        ]);
+        assert_eq!(tracked, 1);
+        assert_eq!(engine.token_roles("hold_no").len(), 1);
+        assert!(engine.token_roles("x_yes").is_empty());
+
+        engine.handle_top_of_book("hold_yes", 0.50, 0.52, None, None, 1);
+        engine.update_neg_risk_events(vec![neg_risk_event(&["cut", "hold", "hike"])]);
+        let slot = engine.price_table.token_to_slot["hold_yes"];
+        assert_eq!(engine.price_table.get(slot).best_ask, 0.52);
+        assert_eq!(engine.token_roles("hold_yes").len(), 1);
+
+        engine.update_neg_risk_events(vec![]);
+        assert!(engine.token_roles("hold_yes").is_empty());
+        assert_eq!(engine.price_table.active_slots(), 0);
+    }
+
+    #[test]
+    fn test_neg_risk_yes_buy_and_convert() {
+        let mut engine = make_engine();
+        engine.update_neg_risk_events(vec![neg_risk_event(&["cut", "hold", "hike"])]);
+
+        engine.handle_top_of_book("cut_yes", 0.28, 0.30, Some(40.0), Some(40.0), 1);
+        engine.handle_top_of_book("hold_yes", 0.28, 0.30, Some(40.0), Some(40.0), 2);
+        // Σ Ask(YES) = 0.30 + 0.30 + 0.35 = 0.95 < 1
+        let signals = engine.handle_top_of_book("hike_yes", 0.33, 0.35, Some(40.0), Some(40.0), 3);
+        assert_eq!(signals.len(), 1);
+        let sig = FlatArbSignal::from(&signals[0]);
+        assert_eq!(sig.strategy, "POLYMARKET_NEG_RISK_YES_BUY");
+        assert_eq!(sig.group_key, "0xnegrisk");
+        assert_eq!(sig.extra_child_legs.len(), 2);
+        assert!(sig.child_neg_risk && sig.extra_child_legs.iter().all(|l| l.neg_risk));
+        assert!((sig.profit_abs - 0.05).abs() < 1e-9);
+
+        // NO(cut) at 0.55 converts into YES(hold) + YES(hike), bid 0.28 + 0.33 = 0.61
+        let signals = engine.handle_top_of_book("cut_no", 0.53, 0.55, Some(25.0), Some(25.0), 4);
+        assert_eq!(signals.len(), 1);
+        let sig = FlatArbSignal::from(&signals[0]);
+        assert_eq!(sig.strategy, "POLYMARKET_NEG_RISK_CONVERT");
+        assert_eq!(sig.parent_asset_id, "cut_no");
+        assert_eq!(sig.child_asset_id, "hold_yes");
+        assert_eq!(sig.extra_child_legs[0].asset_id, "hike_yes");
+        assert!((sig.profit_abs - 0.06).abs() < 1e-9);
+        assert_eq!(sig.max_executable_size, 25.0);
     }
 
     #[test]
@@ -495,13 +1970,61 @@ mod tests {
         engine.update_market_structure(vec![make_group_input()]);
 
         // First update — should process
-        assert!(engine.is_price_changed("parent_lower_yes", 0.60, 0.62, 100));
+        assert!(engine.is_price_changed("parent_lower_yes", 0.60, 0.62, None, None, 100));
 
         // Same price — should skip
-        assert!(!engine.is_price_changed("parent_lower_yes", 0.60, 0.62, 101));
+        assert!(!engine.is_price_changed("parent_lower_yes", 0.60, 0.62, None, None, 101));
 
         // Price changed — should process
-        assert!(engine.is_price_changed("parent_lower_yes", 0.61, 0.63, 102));
+        assert!(engine.is_price_changed("parent_lower_yes", 0.61, 0.63, None, None, 102));
+    }
+
+    #[test]
+    fn test_dirty_check_size_only_change() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+
+        assert!(engine.is_price_changed("parent_lower_yes", 0.60, 0.62, Some(10.0), Some(20.0), 100));
+        // Same price and size — skip; unreported size is not a change
+        assert!(!engine.is_price_changed("parent_lower_yes", 0.60, 0.62, Some(10.0), Some(20.0), 101));
+        assert!(!engine.is_price_changed("parent_lower_yes", 0.60, 0.62, None, None, 102));
+        // Size-only change — process
+        assert!(engine.is_price_changed("parent_lower_yes", 0.60, 0.62, Some(10.0), Some(5.0), 103));
+        assert!(!engine.is_price_changed("parent_lower_yes", 0.60, 0.62, None, Some(5.0), 104));
+    }
+
+    #[test]
+    fn test_signal_max_executable_size() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+
+        // Triangle BUY: min of ask sizes (bid sizes ignored)
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(1.0), Some(40.0), 1);
+        engine.handle_top_of_book("parent_upper_no", 0.52, 0.50, Some(1.0), Some(25.0), 2);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.82, 0.80, Some(1.0), Some(30.0), 3);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].max_executable_size(), 25.0);
+    }
+
+    #[test]
+    fn test_size_only_update_reevaluates() {
+        let mut engine = make_engine();
+        engine.config.cooldown_ms = 0;
+        engine.refresh_group_configs();
+        engine.update_market_structure(vec![make_group_input()]);
+
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("parent_upper_no", 0.52, 0.50, Some(100.0), Some(100.0), 2);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.82, 0.80, Some(100.0), Some(100.0), 3);
+        assert_eq!(signals.len(), 1);
+
+        // Depth refresh at an unchanged price re-emits with the new size
+        let signals =
+            engine.handle_top_of_book("parent_upper_no", 0.52, 0.50, Some(100.0), Some(7.0), 4);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].max_executable_size(), 7.0);
     }
 
     #[test]
@@ -518,7 +2041,7 @@ mod tests {
             engine.handle_top_of_book("range_no_token", 0.82, 0.80, Some(100.0), Some(100.0), 3);
 
         assert_eq!(signals.len(), 1);
-        assert_eq!(signals[0].strategy, "POLYMARKET_TRIANGLE_BUY");
+        assert_eq!(signals[0].strategy, StrategyKind::Triangle);
         assert!((signals[0].profit_abs - 0.10).abs() < 1e-10);
     }
 
@@ -545,7 +2068,7 @@ mod tests {
         // Should have unbundling signal
         let unbundle_sigs: Vec<_> = signals
             .iter()
-            .filter(|s| s.strategy == "SELL_PARENT_BUY_CHILDREN")
+            .filter(|s| s.strategy == StrategyKind::Unbundle)
             .collect();
         assert!(
             !unbundle_sigs.is_empty(),
@@ -570,7 +2093,7 @@ mod tests {
 
         let complement_sigs: Vec<_> = signals
             .iter()
-            .filter(|s| s.strategy == "POLYMARKET_COMPLEMENT_BUY")
+            .filter(|s| s.strategy == StrategyKind::Complement)
             .collect();
         assert!(
             !complement_sigs.is_empty(),
@@ -578,7 +2101,652 @@ mod tests {
             signals.iter().map(|s| &s.strategy).collect::<Vec<_>>()
         );
         assert!((complement_sigs[0].profit_abs - 0.25).abs() < 1e-10);
-        assert_eq!(complement_sigs[0].triangle_mode, Some("COMPLEMENT_BUY".to_string()));
-        assert_eq!(complement_sigs[0].triangle_payout, Some(1.0));
+        assert_eq!(complement_sigs[0].mode, Some("COMPLEMENT_BUY"));
+        assert_eq!(complement_sigs[0].payout, Some(1.0));
+    }
+
+    fn make_second_group_input() -> RangeGroupInput {
+        let mut g = make_group_input();
+        g.group_key = "btc-2026-01-20T17:00:00.000Z".to_string();
+        g.crypto = "BTC".to_string();
+        for m in g.children.iter_mut().chain(g.parents.iter_mut()) {
+            m.market_id = format!("btc-{}", m.market_id);
+            for t in m.clob_token_ids.iter_mut() {
+                *t = format!("btc_{}", t);
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn test_update_market_structure_keeps_prices() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 1);
+
+        // Refresh with the same structure — live price must survive
+        engine.update_market_structure(vec![make_group_input()]);
+
+        let slot = engine.price_table.token_to_slot["parent_lower_yes"];
+        assert!((engine.price_table.get(slot).best_ask - 0.60).abs() < 1e-10);
+        assert_eq!(engine.price_table.active_slots(), 6);
+    }
+
+    #[test]
+    fn test_upsert_group_keeps_cooldowns() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+        Arc::make_mut(&mut engine.groups[0]).trio_states[0].last_emitted_buy_ms = 42;
+
+        let trio_count = engine.upsert_group(make_group_input());
+
+        assert_eq!(trio_count, 1);
+        assert_eq!(engine.groups.len(), 1);
+        assert_eq!(engine.groups[0].trio_states[0].last_emitted_buy_ms, 42);
+    }
+
+    #[test]
+    fn test_snapshot_shares_untouched_structure() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input(), make_second_group_input()]);
+        let snapshot = engine.snapshot();
+
+        // Updates copy the group they evaluate, never the index or other groups
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 1);
+        assert!(!Arc::ptr_eq(&engine.groups[0], &snapshot.groups[0]));
+        assert!(Arc::ptr_eq(&engine.groups[1], &snapshot.groups[1]));
+        assert!(Arc::ptr_eq(&engine.token_index, &snapshot.token_index));
+        assert!(Arc::ptr_eq(&engine.group_key_index, &snapshot.group_key_index));
+        assert!(Arc::ptr_eq(&engine.price_table.token_to_slot, &snapshot.price_table.token_to_slot));
+        assert!(Arc::ptr_eq(&engine.config_overrides, &snapshot.config_overrides));
+        assert!(Arc::ptr_eq(&engine.closed_opportunities, &snapshot.closed_opportunities));
+        assert!(Arc::ptr_eq(&engine.price_feed, &snapshot.price_feed));
+        assert!(snapshot.last_price_cache.is_empty());
+        assert!(snapshot.quote("parent_lower_yes").is_some_and(|q| q.best_ask.is_nan()));
+    }
+
+    #[test]
+    fn test_remove_group_reindexes_moved_group() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input(), make_second_group_input()]);
+        assert_eq!(engine.price_table.active_slots(), 12);
+
+        assert!(engine.remove_group("eth-2026-01-20T17:00:00.000Z"));
+        assert!(!engine.remove_group("eth-2026-01-20T17:00:00.000Z"));
+
+        assert_eq!(engine.groups.len(), 1);
+        assert_eq!(engine.group_key_index["btc-2026-01-20T17:00:00.000Z"], 0);
+        assert_eq!(engine.price_table.active_slots(), 6);
+        assert!(engine.token_roles("parent_lower_yes").is_empty());
+        assert!(!engine.price_table.token_to_slot.contains_key("parent_lower_yes"));
+
+        // Moved group still dispatches correctly
+        engine.handle_top_of_book("btc_parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("btc_parent_upper_no", 0.52, 0.50, Some(100.0), Some(100.0), 2);
+        let signals =
+            engine.handle_top_of_book("btc_range_no_token", 0.82, 0.80, Some(100.0), Some(100.0), 3);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].crypto, "BTC");
+
+        // Reclaimed slots are reused
+        engine.upsert_group(make_group_input());
+        assert_eq!(engine.price_table.slots.len(), 12);
+    }
+
+    #[test]
+    fn test_retire_and_add_markets() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 1);
+
+        let group_key = "eth-2026-01-20T17:00:00.000Z";
+        let trio_count = engine
+            .retire_markets(group_key, &["range-2800-2900".to_string()])
+            .unwrap();
+        assert_eq!(trio_count, 0);
+        assert_eq!(engine.price_table.active_slots(), 4);
+        assert!(engine.token_roles("range_no_token").is_empty());
+
+        let child = make_group_input().children.remove(0);
+        let trio_count = engine.add_markets(group_key, vec![child], vec![]).unwrap();
+        assert_eq!(trio_count, 1);
+
+        // Parent price untouched throughout
+        let slot = engine.price_table.token_to_slot["parent_lower_yes"];
+        assert!((engine.price_table.get(slot).best_ask - 0.60).abs() < 1e-10);
+
+        assert!(engine.retire_markets("unknown", &[]).is_none());
+    }
+
+    #[test]
+    fn test_stale_leg_suppresses_signal() {
+        let mut engine = make_engine();
+        engine.config.max_quote_age_ms = 1000;
+        engine.config.clock = EngineClock::Manual(2500);
+        engine.update_market_structure(vec![make_group_input()]);
+
+        // Parent lower quote is 1500ms old at evaluation time → stale
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 1000);
+        engine.handle_top_of_book("parent_upper_no", 0.52, 0.50, Some(100.0), Some(100.0), 2000);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.82, 0.80, Some(100.0), Some(100.0), 2400);
+        assert!(signals.is_empty());
+        assert_eq!(engine.groups[0].trio_states[0].stale_skip_count, 1);
+
+        // Per-strategy override relaxes the limit for triangle only
+        engine.config.strategy_max_quote_age_ms[StrategyKind::Triangle.index()] = Some(5000);
+        engine.refresh_group_configs();
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.83, 0.80, Some(100.0), Some(100.0), 2450);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].timestamp_ms, 2500);
+    }
+
+    #[test]
+    fn test_hysteresis_emission() {
+        let mut engine = make_engine();
+        engine.config.cooldown_ms = 10_000;
+        engine.config.emission_policy = EmissionPolicy::Hysteresis;
+        engine.config.reemit_improvement_bps = 50.0;
+        engine.config.strategy_enabled = [false; StrategyKind::COUNT];
+        engine.config.strategy_enabled[StrategyKind::Triangle.index()] = true;
+        engine.update_market_structure(vec![make_group_input()]);
+        let at = |engine: &mut EngineState, now_ms: i64| {
+            engine.config.clock = EngineClock::Manual(now_ms);
+            engine.refresh_group_configs();
+        };
+
+        // Opens at 1.90 → 526 bps
+        at(&mut engine, 1000);
+        engine.handle_top_of_book("parent_lower_yes", 0.58, 0.60, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("parent_upper_no", 0.48, 0.50, Some(100.0), Some(100.0), 2);
+        let signals = engine.handle_top_of_book("range_no_token", 0.78, 0.80, Some(100.0), Some(100.0), 3);
+        assert_eq!(signals.len(), 1);
+
+        // Unchanged edge inside the cooldown → suppressed
+        at(&mut engine, 2000);
+        let signals = engine.handle_top_of_book("range_no_token", 0.77, 0.80, Some(100.0), Some(100.0), 4);
+        assert!(signals.is_empty());
+
+        // +56 bps improvement → re-emitted before the cooldown
+        at(&mut engine, 3000);
+        let signals = engine.handle_top_of_book("range_no_token", 0.77, 0.79, Some(100.0), Some(100.0), 5);
+        assert_eq!(signals.len(), 1);
+
+        // Edge closes, then reopens → emitted again despite the cooldown
+        at(&mut engine, 4000);
+        let signals = engine.handle_top_of_book("range_no_token", 0.97, 0.99, Some(100.0), Some(100.0), 6);
+        assert!(signals.is_empty());
+        at(&mut engine, 5000);
+        let signals = engine.handle_top_of_book("range_no_token", 0.78, 0.80, Some(100.0), Some(100.0), 7);
+        assert_eq!(signals.len(), 1);
+
+        // Cooldown policy keeps suppressing the reopened edge
+        engine.config.emission_policy = EmissionPolicy::Cooldown;
+        at(&mut engine, 6000);
+        engine.handle_top_of_book("range_no_token", 0.97, 0.99, Some(100.0), Some(100.0), 8);
+        let signals = engine.handle_top_of_book("range_no_token", 0.78, 0.80, Some(100.0), Some(100.0), 9);
+        assert!(signals.is_empty());
+    }
+
+    #[test]
+    fn test_opportunity_lifecycle_events() {
+        let mut engine = make_engine();
+        engine.config.cooldown_ms = 60_000;
+        engine.config.strategy_enabled = [false; StrategyKind::COUNT];
+        engine.config.strategy_enabled[StrategyKind::Triangle.index()] = true;
+        engine.update_market_structure(vec![make_group_input()]);
+        let at = |engine: &mut EngineState, now_ms: i64| {
+            engine.config.clock = EngineClock::Manual(now_ms);
+            engine.refresh_group_configs();
+        };
+
+        // Crossing opens the opportunity (1.90 → 526 bps)
+        at(&mut engine, 1000);
+        engine.handle_top_of_book("parent_lower_yes", 0.58, 0.60, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("parent_upper_no", 0.48, 0.50, Some(100.0), Some(100.0), 2);
+        engine.handle_top_of_book("range_no_token", 0.78, 0.80, Some(100.0), Some(100.0), 3);
+        let events = engine.take_opportunity_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, OpportunityPhase::Opened);
+        assert_eq!(events[0].strategy, "triangle");
+        assert_eq!(events[0].structure, "trio");
+        assert_eq!(events[0].opened_at_ms, 1000);
+
+        // New peak while open → updated; a smaller edge raises nothing
+        at(&mut engine, 1500);
+        engine.handle_top_of_book("range_no_token", 0.77, 0.79, Some(100.0), Some(100.0), 4);
+        at(&mut engine, 1800);
+        engine.handle_top_of_book("range_no_token", 0.78, 0.80, Some(100.0), Some(100.0), 5);
+        let events = engine.take_opportunity_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, OpportunityPhase::Updated);
+        assert_eq!(events[0].peak_at_ms, 1500);
+
+        // Edge gone → closed with duration, peak and edge at close
+        at(&mut engine, 2500);
+        engine.handle_top_of_book("range_no_token", 0.97, 0.99, Some(100.0), Some(100.0), 6);
+        let events = engine.take_opportunity_events();
+        assert_eq!(events.len(), 1);
+        let closed = &events[0];
+        assert_eq!(closed.phase, OpportunityPhase::Closed);
+        assert_eq!((closed.opened_at_ms, closed.timestamp_ms, closed.duration_ms), (1000, 2500, 1500));
+        assert!((closed.peak_profit_abs - 0.11).abs() < 1e-9);
+        assert!(closed.profit_abs < 0.0);
+        assert_eq!(engine.closed_opportunities.len(), 1);
+
+        // Below threshold stays quiet until the next crossing
+        at(&mut engine, 3000);
+        engine.handle_top_of_book("range_no_token", 0.96, 0.98, Some(100.0), Some(100.0), 7);
+        assert!(engine.take_opportunity_events().is_empty());
+    }
+
+    #[test]
+    fn test_dropped_opportunities_close() {
+        let mut engine = make_engine();
+        engine.config.strategy_enabled = [false; StrategyKind::COUNT];
+        engine.config.strategy_enabled[StrategyKind::Triangle.index()] = true;
+        engine.config.strategy_enabled[StrategyKind::NegRiskYesBuy.index()] = true;
+        engine.config.clock = EngineClock::Manual(1000);
+        engine.update_market_structure(vec![make_group_input()]);
+        engine.update_neg_risk_events(vec![neg_risk_event(&["cut", "hold", "hike"])]);
+        let open_triangle = |engine: &mut EngineState, ts: i64| {
+            engine.handle_top_of_book("parent_lower_yes", 0.58, 0.60, Some(100.0), Some(100.0), ts);
+            engine.handle_top_of_book("parent_upper_no", 0.48, 0.50, Some(100.0), Some(100.0), ts + 1);
+            engine.handle_top_of_book("range_no_token", 0.78, 0.80, Some(100.0), Some(100.0), ts + 2);
+            let events = engine.take_opportunity_events();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].phase, OpportunityPhase::Opened);
+        };
+        let closed = |engine: &mut EngineState| -> Vec<(String, String)> {
+            engine
+                .take_opportunity_events()
+                .into_iter()
+                .inspect(|e| assert_eq!(e.phase, OpportunityPhase::Closed))
+                .map(|e| (e.strategy, e.structure))
+                .collect()
+        };
+
+        // Disabling the strategy closes its open opportunity
+        open_triangle(&mut engine, 1);
+        engine.config.strategy_enabled[StrategyKind::Triangle.index()] = false;
+        engine.refresh_group_configs();
+        assert_eq!(closed(&mut engine), [("triangle".to_string(), "trio".to_string())]);
+
+        // A rebuild that keeps the trio carries it; removing the group closes it
+        engine.config.strategy_enabled[StrategyKind::Triangle.index()] = true;
+        engine.refresh_group_configs();
+        engine.handle_top_of_book("range_no_token", 0.97, 0.99, Some(100.0), Some(100.0), 4);
+        open_triangle(&mut engine, 5);
+        engine.rebuild_groups();
+        assert!(engine.take_opportunity_events().is_empty());
+        engine.update_market_structure(vec![]);
+        assert_eq!(closed(&mut engine), [("triangle".to_string(), "trio".to_string())]);
+
+        // Dropping a neg-risk event closes its opportunity
+        engine.handle_top_of_book("cut_yes", 0.28, 0.30, Some(40.0), Some(40.0), 10);
+        engine.handle_top_of_book("hold_yes", 0.28, 0.30, Some(40.0), Some(40.0), 11);
+        engine.handle_top_of_book("hike_yes", 0.33, 0.35, Some(40.0), Some(40.0), 12);
+        assert_eq!(engine.take_opportunity_events().len(), 1);
+        engine.update_neg_risk_events(vec![]);
+        assert_eq!(closed(&mut engine), [("neg_risk_yes_buy".to_string(), "neg_risk".to_string())]);
+        assert_eq!(engine.closed_opportunities.len(), 3);
+    }
+
+    #[test]
+    fn test_price_feed_throttles_watched_tokens() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+        engine.set_price_feed(&["range_no_token".to_string()], 100);
+
+        // First change goes out at once; unwatched tokens never do
+        engine.config.clock = EngineClock::Manual(1000);
+        engine.handle_top_of_book("range_no_token", 0.78, 0.80, Some(10.0), Some(20.0), 1);
+        engine.handle_top_of_book("parent_lower_yes", 0.58, 0.60, Some(10.0), Some(20.0), 2);
+        let updates = engine.take_price_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].asset_id, "range_no_token");
+        assert_eq!((updates[0].best_bid, updates[0].best_ask_size), (0.78, Some(20.0)));
+
+        // Changes inside the window are held back...
+        engine.config.clock = EngineClock::Manual(1050);
+        engine.handle_top_of_book("range_no_token", 0.77, 0.79, Some(10.0), Some(20.0), 3);
+        engine.handle_top_of_book("range_no_token", 0.76, 0.78, Some(10.0), Some(20.0), 4);
+        assert!(engine.take_price_updates().is_empty());
+        assert!(engine.price_feed.has_pending());
+
+        // ...and flushed once, at the latest quote, when it ends
+        engine.config.clock = EngineClock::Manual(1100);
+        let updates = engine.take_price_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].best_bid, 0.76);
+        assert!(!engine.price_feed.has_pending());
+
+        // An empty list turns the feed off
+        engine.set_price_feed(&[], 100);
+        engine.config.clock = EngineClock::Manual(5000);
+        engine.handle_top_of_book("range_no_token", 0.70, 0.72, Some(10.0), Some(20.0), 5);
+        assert!(engine.take_price_updates().is_empty());
+    }
+
+    #[test]
+    fn test_exchange_clock_tracks_latest_timestamp() {
+        let mut engine = make_engine();
+        engine.config.max_quote_age_ms = 1000;
+        engine.config.clock = EngineClock::Exchange;
+        engine.update_market_structure(vec![make_group_input()]);
+
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 10_000);
+        engine.handle_top_of_book("parent_upper_no", 0.52, 0.50, Some(100.0), Some(100.0), 10_500);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.82, 0.80, Some(100.0), Some(100.0), 10_900);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(engine.now_ms(), 10_900);
+
+        // Quotes without a timestamp are treated as stale while the guard is on
+        let signals =
+            engine.handle_top_of_book("parent_upper_no", 0.53, 0.50, Some(100.0), Some(100.0), 0);
+        assert!(signals.is_empty());
+        assert_eq!(engine.groups[0].trio_states[0].stale_skip_count, 1);
+    }
+
+    #[test]
+    fn test_handle_top_of_book_bundling() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+
+        // Bundling: profit = (Bid(RC_YES) + Bid(PU_YES)) - Ask(PL_YES)
+        // = (0.40 + 0.30) - 0.60 = 0.10
+        engine.handle_top_of_book("range_yes_token", 0.40, 0.42, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("parent_upper_yes", 0.30, 0.32, Some(100.0), Some(100.0), 2);
+        let signals =
+            engine.handle_top_of_book("parent_lower_yes", 0.58, 0.60, Some(100.0), Some(100.0), 3);
+
+        let bundle_sigs: Vec<_> = signals
+            .iter()
+            .filter(|s| s.strategy == StrategyKind::Bundle)
+            .collect();
+        assert_eq!(bundle_sigs.len(), 1);
+        assert!((bundle_sigs[0].profit_abs - 0.10).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_strategy_switches() {
+        let mut engine = make_engine();
+        engine.config.strategy_enabled[StrategyKind::Triangle.index()] = false;
+        engine.config.strategy_enabled[StrategyKind::Unbundle.index()] = false;
+        engine.update_market_structure(vec![make_group_input()]);
+
+        engine.handle_top_of_book("parent_lower_yes", 0.80, 0.60, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("parent_upper_no", 0.52, 0.50, Some(100.0), Some(100.0), 2);
+        engine.handle_top_of_book("parent_upper_yes", 0.38, 0.40, Some(100.0), Some(100.0), 3);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.82, 0.80, Some(100.0), Some(100.0), 4);
+        assert!(signals.is_empty());
+        let signals =
+            engine.handle_top_of_book("range_yes_token", 0.28, 0.30, Some(100.0), Some(100.0), 5);
+        assert!(signals.iter().all(|s| s.strategy != StrategyKind::Unbundle));
+
+        // Re-enabling at runtime takes effect on the next update
+        engine.config.strategy_enabled[StrategyKind::Unbundle.index()] = true;
+        engine.refresh_group_configs();
+        let signals =
+            engine.handle_top_of_book("range_yes_token", 0.27, 0.30, Some(100.0), Some(100.0), 6);
+        assert!(signals.iter().any(|s| s.strategy == StrategyKind::Unbundle));
+    }
+
+    #[test]
+    fn test_range_arbitrage_disabled_by_default() {
+        let config = EngineConfig::default();
+        assert!(config.is_enabled(StrategyKind::Triangle));
+        assert!(config.is_enabled(StrategyKind::Complement));
+        assert!(!config.is_enabled(StrategyKind::Unbundle));
+        assert!(!config.is_enabled(StrategyKind::Bundle));
+    }
+
+    #[test]
+    fn test_later_strategies_are_opt_in() {
+        let config = EngineConfig::default();
+        for kind in StrategyKind::ALL {
+            let expected = matches!(kind, StrategyKind::Triangle | StrategyKind::Complement);
+            assert_eq!(config.is_enabled(kind), expected, "{}", kind.as_str());
+        }
+    }
+
+    #[test]
+    fn test_config_override_precedence() {
+        let mut engine = make_engine();
+        engine.set_config_overrides(vec![
+            ConfigOverride {
+                selector: OverrideSelector::GroupKey("eth-2026-01-20T17:00:00.000Z".to_string()),
+                min_profit_abs: Some(0.05),
+                ..override_for(OverrideSelector::GroupKey(String::new()))
+            },
+            ConfigOverride {
+                min_profit_abs: Some(0.20),
+                cooldown_ms: Some(500),
+                ..override_for(OverrideSelector::Crypto("eth".to_string()))
+            },
+            ConfigOverride {
+                min_profit_abs: Some(0.15),
+                ..override_for(OverrideSelector::EventSlug("eth-*".to_string()))
+            },
+        ]);
+        engine.update_market_structure(vec![make_group_input(), make_second_group_input()]);
+
+        // ETH: group key beats event slug beats crypto; cooldown inherited from crypto layer
+        let eth = &engine.groups[0].config;
+        assert!((eth.min_profit_abs - 0.05).abs() < 1e-10);
+        assert_eq!(eth.cooldown_ms, 500);
+
+        // BTC shares the event slug in this fixture but not the crypto or group key
+        let btc = &engine.groups[1].config;
+        assert!((btc.min_profit_abs - 0.15).abs() < 1e-10);
+        assert_eq!(btc.cooldown_ms, engine.config.cooldown_ms);
+    }
+
+    #[test]
+    fn test_config_override_gates_signals() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input()]);
+        engine.set_config_overrides(vec![ConfigOverride {
+            min_profit_abs: Some(0.20),
+            ..override_for(OverrideSelector::Crypto("ETH".to_string()))
+        }]);
+
+        engine.handle_top_of_book("parent_lower_yes", 0.62, 0.60, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("parent_upper_no", 0.52, 0.50, Some(100.0), Some(100.0), 2);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.82, 0.80, Some(100.0), Some(100.0), 3);
+        assert!(signals.is_empty(), "0.10 profit is below the ETH override");
+
+        // Clearing overrides falls back to the global threshold
+        engine.set_config_overrides(Vec::new());
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.81, 0.80, Some(100.0), Some(100.0), 4);
+        assert_eq!(signals.len(), 1);
+    }
+
+    fn override_for(selector: OverrideSelector) -> ConfigOverride {
+        ConfigOverride {
+            selector,
+            min_profit_bps: None,
+            min_profit_abs: None,
+            cooldown_ms: None,
+            max_quote_age_ms: None,
+            strategy_enabled: [None; StrategyKind::COUNT],
+        }
+    }
+
+    #[test]
+    fn test_inspect_reports_edges_below_threshold() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input(), make_second_group_input()]);
+
+        // totalAsk = 0.67 × 3 = 2.01 → negative triangle edge, no signal
+        engine.handle_top_of_book("parent_lower_yes", 0.65, 0.67, Some(100.0), Some(100.0), 1);
+        engine.handle_top_of_book("parent_upper_no", 0.65, 0.67, Some(100.0), Some(100.0), 2);
+        let signals =
+            engine.handle_top_of_book("range_no_token", 0.65, 0.67, Some(100.0), Some(100.0), 3);
+        assert!(signals.is_empty());
+
+        let groups =
+            crate::engine::inspect::inspect_engine(&engine, Some("eth-2026-01-20T17:00:00.000Z"));
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].parents.len(), 2);
+        assert!((groups[0].parents[0].yes.best_ask - 0.67).abs() < 1e-10);
+
+        let trio = &groups[0].trios[0];
+        assert_eq!(trio.range_market_id, "range-2800-2900");
+        let triangle = trio.triangle.expect("all triangle legs quoted");
+        assert!((triangle.total_ask - 2.01).abs() < 1e-10);
+        assert!((triangle.profit_abs + 0.01).abs() < 1e-10);
+        assert!(!triangle.meets_threshold);
+        assert!(trio.complement.is_none());
+
+        assert_eq!(crate::engine::inspect::inspect_engine(&engine, None).len(), 2);
+    }
+
+    #[test]
+    fn test_near_miss_scanner_ranks_negative_edges() {
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_group_input(), make_second_group_input()]);
+
+        // ETH triangle: totalAsk = 2.01 → -0.01; BTC triangle: totalAsk = 2.04 → -0.04
+        for (prefix, ask) in [("", 0.67), ("btc_", 0.68)] {
+            for token in ["parent_lower_yes", "parent_upper_no", "range_no_token"] {
+                let token = format!("{}{}", prefix, token);
+                engine.handle_top_of_book(&token, 0.50, ask, Some(100.0), Some(100.0), 1);
+            }
+        }
+
+        let ranked =
+            crate::engine::scanner::top_near_misses(&engine, StrategyKind::Triangle, 5, 0);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].group_key, "eth-2026-01-20T17:00:00.000Z");
+        assert!((ranked[0].profit_abs + 0.01).abs() < 1e-10);
+        assert!((ranked[1].profit_abs + 0.04).abs() < 1e-10);
+
+        let top1 = crate::engine::scanner::top_near_misses(&engine, StrategyKind::Triangle, 1, 0);
+        assert_eq!(top1.len(), 1);
+    }
+
+    #[test]
+    fn test_near_miss_scanner_ranks_every_structure() {
+        use crate::engine::scanner::{reset_edge_stats, top_near_misses};
+        let mut engine = make_engine();
+        engine.update_market_structure(vec![make_partition_input(3000.0)]);
+        for (token, ask) in [("below-2800", 0.30), ("r2800", 0.30), ("r2900", 0.25), ("above-3000", 0.20)] {
+            engine.handle_top_of_book(&format!("{}_yes", token), ask - 0.02, ask, Some(50.0), Some(50.0), 1);
+            engine.handle_top_of_book(&format!("{}_no", token), 0.70, 0.72, Some(50.0), Some(50.0), 1);
+        }
+
+        // Σ ask = 1.05 → the ladder is ranked at -0.05
+        let ladder = top_near_misses(&engine, StrategyKind::LadderBuy, 5, 0);
+        assert_eq!(ladder.len(), 1);
+        assert_eq!((ladder[0].structure, ladder[0].structure_index), ("ladder", 0));
+        assert!((ladder[0].profit_abs + 0.05).abs() < 1e-9);
+
+        // One parity state per market, best (YES 0.20 + NO 0.72) first
+        let parity = top_near_misses(&engine, StrategyKind::ParityBuy, 10, 0);
+        assert_eq!(parity.len(), 4);
+        assert!(parity.iter().all(|m| m.structure == "parity"));
+        assert!((parity[0].profit_abs - 0.08).abs() < 1e-9);
+
+        reset_edge_stats(&mut engine);
+        assert!(top_near_misses(&engine, StrategyKind::LadderBuy, 5, 0).is_empty());
+        assert!(top_near_misses(&engine, StrategyKind::ParityBuy, 5, 0).is_empty());
+    }
+
+    #[test]
+    fn test_edge_stats_running_max_and_time_above_zero() {
+        let mut stats = EdgeStats::default();
+        stats.record(-0.01, -50.0, 1_000);
+        stats.record(0.02, 100.0, 2_000);
+        stats.record(0.01, 50.0, 2_500);
+        stats.record(-0.01, -50.0, 3_000);
+        stats.record(0.03, 150.0, 4_000);
+
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.max_profit_bps, 150.0);
+        assert_eq!(stats.max_at_ms, 4_000);
+        // Closed interval 2000→3000, open interval 4000→4500
+        assert_eq!(stats.time_above_zero_ms(4_500), 1_500);
+    }
+
+    /// Synthetic structure: `groups` groups × `parents` strikes, every range in between.
+    fn make_bench_inputs(groups: usize, parents: usize) -> Vec<RangeGroupInput> {
+        (0..groups)
+            .map(|g| {
+                let bench_market = |id: String, lower: f64, upper: Option<f64>, kind: &str| {
+                    market(&id, &format!("{}-yes", id), &format!("{}-no", id), Some(lower), upper, kind)
+                };
+                let strike = |i: usize| 1000.0 + 100.0 * i as f64;
+                RangeGroupInput {
+                    group_key: format!("g{}", g),
+                    event_slug: format!("event-{}", g),
+                    crypto: "ETH".to_string(),
+                    children: (0..parents - 1)
+                        .map(|i| bench_market(format!("g{}-r{}", g, i), strike(i), Some(strike(i + 1)), "range"))
+                        .collect(),
+                    parents: (0..parents)
+                        .map(|i| bench_market(format!("g{}-p{}", g, i), strike(i), None, "above"))
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Throughput of the update path. Run with:
+    /// `cargo test --release bench_handle_top_of_book -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_handle_top_of_book() {
+        let mut engine = make_engine();
+        // Default switches: triangle + complement (range arb is opt-in)
+        engine.config.strategy_enabled = EngineConfig::default().strategy_enabled;
+        engine.update_market_structure(make_bench_inputs(50, 12));
+        let tokens: Vec<String> = engine.price_table.token_to_slot.keys().cloned().collect();
+        const UPDATES: usize = 2_000_000;
+
+        let start = std::time::Instant::now();
+        let mut emitted = 0usize;
+        for i in 0..UPDATES {
+            let token = &tokens[i % tokens.len()];
+            // No-arb quotes (every leg ≥ 0.70) — measures dispatch, not signal building
+            let tick = (i % 7) as f64 * 0.001;
+            let (bid, ask) = (0.68 + tick, 0.70 + tick);
+            emitted += engine
+                .handle_top_of_book(token, bid, ask, Some(50.0), Some(50.0), i as i64 + 1)
+                .len();
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "by string: {:.0} updates/s ({} tokens, {} signals)",
+            UPDATES as f64 / elapsed.as_secs_f64(),
+            tokens.len(),
+            emitted
+        );
+
+        // Socket path: ids resolved by the parser, engine indexes flat arrays
+        let ids: Vec<TokenId> = tokens.iter().map(|t| engine.interner.get(t).unwrap()).collect();
+        let start = std::time::Instant::now();
+        let mut emitted = 0usize;
+        for i in 0..UPDATES {
+            let id = ids[i % ids.len()];
+            let tick = (i % 7) as f64 * 0.001;
+            let (bid, ask) = (0.68 + tick, 0.70 + tick);
+            let ts = (UPDATES + i) as i64 + 1;
+            emitted += engine
+                .handle_top_of_book_id(id, bid, ask, Some(50.0), Some(50.0), ts)
+                .len();
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "by id: {:.0} updates/s ({} tokens, {} signals)",
+            UPDATES as f64 / elapsed.as_secs_f64(),
+            ids.len(),
+            emitted
+        );
     }
 }
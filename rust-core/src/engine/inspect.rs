@@ -0,0 +1,152 @@
+//! Engine inspection — read-only snapshot of what the engine currently believes.
+//!
+//! For every group: markets with their live YES/NO quotes, and for every trio the
+//! raw triangle + complement edge (computed even when below threshold) plus cooldowns.
+//! Diagnostic path only — allocates freely, never called from the hot path.
+
+use crate::engine::state::{EngineState, GroupState, MarketMeta, PriceSlot, PriceTable, TrioState};
+use crate::engine::trio_evaluator;
+
+/// A market with its live YES/NO quotes.
+#[derive(Debug, Clone)]
+pub struct MarketInspection {
+    pub market_id: String,
+    pub slug: String,
+    pub kind: &'static str,
+    pub bounds_lower: Option<f64>,
+    pub bounds_upper: Option<f64>,
+    pub neg_risk: bool,
+    pub yes_token: String,
+    pub no_token: String,
+    pub yes: PriceSlot,
+    pub no: PriceSlot,
+}
+
+/// Raw edge of one trio direction (absent on the trio when a leg has no quote).
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeInspection {
+    pub total_ask: f64,
+    pub total_bid: f64,
+    pub payout: f64,
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    /// Whether the edge clears the group's effective thresholds.
+    pub meets_threshold: bool,
+}
+
+/// A trio with its current triangle + complement edges and cooldown state.
+#[derive(Debug, Clone)]
+pub struct TrioInspection {
+    pub trio_index: usize,
+    pub parent_lower_market_id: String,
+    pub parent_upper_market_id: String,
+    pub range_market_id: String,
+    pub triangle: Option<EdgeInspection>,
+    pub complement: Option<EdgeInspection>,
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_complement_ms: i64,
+    pub last_emitted_unbundle_ms: i64,
+    pub last_emitted_bundle_ms: i64,
+    pub stale_skip_count: u64,
+}
+
+/// Full snapshot of one group.
+#[derive(Debug, Clone)]
+pub struct GroupInspection {
+    pub group_key: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub children: Vec<MarketInspection>,
+    pub parents: Vec<MarketInspection>,
+    pub trios: Vec<TrioInspection>,
+}
+
+/// Snapshot every group, or only `group_key` when given.
+pub fn inspect_engine(engine: &EngineState, group_key: Option<&str>) -> Vec<GroupInspection> {
+    engine
+        .groups
+        .iter()
+        .filter(|g| group_key.is_none_or(|k| g.group_key == k))
+        .map(|g| inspect_group(g, &engine.price_table))
+        .collect()
+}
+
+fn inspect_group(group: &GroupState, pt: &PriceTable) -> GroupInspection {
+    let cfg = &group.config;
+    let edge = |total_ask: f64, total_bid: f64, payout: f64, profit_abs: f64, profit_bps: f64| {
+        EdgeInspection {
+            total_ask,
+            total_bid,
+            payout,
+            profit_abs,
+            profit_bps,
+            meets_threshold: profit_abs >= cfg.min_profit_abs && profit_bps >= cfg.min_profit_bps,
+        }
+    };
+
+    let trios = group
+        .trio_states
+        .iter()
+        .enumerate()
+        .map(|(i, trio)| {
+            let triangle = trio_evaluator::calc_trio_edge(trio, pt)
+                .map(|p| edge(p.total_ask, p.total_bid, p.payout, p.profit_abs, p.profit_bps));
+            let complement = trio_evaluator::calc_complement_edge(trio, pt)
+                .map(|p| edge(p.total_ask, p.total_bid, p.payout, p.profit_abs, p.profit_bps));
+            inspect_trio(group, trio, i, triangle, complement)
+        })
+        .collect();
+
+    GroupInspection {
+        group_key: group.group_key.clone(),
+        event_slug: group.event_slug.clone(),
+        crypto: group.crypto.clone(),
+        children: group.child_metas.iter().map(|m| inspect_market(m, pt)).collect(),
+        parents: group.parent_metas.iter().map(|m| inspect_market(m, pt)).collect(),
+        trios,
+    }
+}
+
+fn inspect_trio(
+    group: &GroupState,
+    trio: &TrioState,
+    trio_index: usize,
+    triangle: Option<EdgeInspection>,
+    complement: Option<EdgeInspection>,
+) -> TrioInspection {
+    let market_id = |metas: &[MarketMeta], idx: u16| {
+        metas
+            .get(idx as usize)
+            .map(|m| m.market_id.clone())
+            .unwrap_or_default()
+    };
+
+    TrioInspection {
+        trio_index,
+        parent_lower_market_id: market_id(&group.parent_metas, trio.parent_lower_idx),
+        parent_upper_market_id: market_id(&group.parent_metas, trio.parent_upper_idx),
+        range_market_id: market_id(&group.child_metas, trio.range_idx),
+        triangle,
+        complement,
+        last_emitted_buy_ms: trio.last_emitted_buy_ms,
+        last_emitted_complement_ms: trio.last_emitted_complement_ms,
+        last_emitted_unbundle_ms: trio.last_emitted_unbundle_ms,
+        last_emitted_bundle_ms: trio.last_emitted_bundle_ms,
+        stale_skip_count: trio.stale_skip_count,
+    }
+}
+
+fn inspect_market(meta: &MarketMeta, pt: &PriceTable) -> MarketInspection {
+    MarketInspection {
+        market_id: meta.market_id.clone(),
+        slug: meta.slug.clone(),
+        kind: meta.kind.as_str(),
+        bounds_lower: meta.bounds_lower,
+        bounds_upper: meta.bounds_upper,
+        neg_risk: meta.neg_risk,
+        yes_token: meta.clob_token_ids[0].clone(),
+        no_token: meta.clob_token_ids[1].clone(),
+        yes: *pt.get(meta.yes_slot),
+        no: *pt.get(meta.no_slot),
+    }
+}
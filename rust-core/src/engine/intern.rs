@@ -0,0 +1,86 @@
+//! Token interner — maps CLOB token ID strings to dense `u32` ids.
+//!
+//! Tokens are interned once, at subscription or structure-build time. The socket
+//! parser resolves `asset_id` to a `TokenId` before an update reaches the engine,
+//! so the hot path indexes flat arrays instead of hashing strings.
+//!
+//! Ids are never reused: an update resolved just before a token is retired can
+//! never be misattributed to a different token that replaced it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Dense interned token id.
+pub type TokenId = u32;
+
+/// Shared, grow-only token interner.
+#[derive(Debug, Default)]
+pub struct TokenInterner {
+    inner: RwLock<InternerInner>,
+}
+
+#[derive(Debug, Default)]
+struct InternerInner {
+    ids: HashMap<Arc<str>, TokenId>,
+    names: Vec<Arc<str>>,
+}
+
+impl TokenInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `token`, assigning the next id if it is new.
+    pub fn intern(&self, token: &str) -> TokenId {
+        if let Some(id) = self.get(token) {
+            return id;
+        }
+        let mut inner = self.inner.write().unwrap();
+        if let Some(&id) = inner.ids.get(token) {
+            return id;
+        }
+        let id = inner.names.len() as TokenId;
+        let name: Arc<str> = Arc::from(token);
+        inner.names.push(Arc::clone(&name));
+        inner.ids.insert(name, id);
+        id
+    }
+
+    /// Id of an already-interned token (read lock only, no allocation).
+    #[inline]
+    pub fn get(&self, token: &str) -> Option<TokenId> {
+        self.inner.read().unwrap().ids.get(token).copied()
+    }
+
+    /// Token string of an id.
+    pub fn name(&self, id: TokenId) -> Option<Arc<str>> {
+        self.inner.read().unwrap().names.get(id as usize).cloned()
+    }
+
+    /// Number of interned tokens (= one past the highest id).
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_is_stable_and_dense() {
+        let interner = TokenInterner::new();
+        let a = interner.intern("token-a");
+        let b = interner.intern("token-b");
+        assert_eq!((a, b), (0, 1));
+        assert_eq!(interner.intern("token-a"), a);
+        assert_eq!(interner.get("token-b"), Some(b));
+        assert_eq!(interner.get("token-c"), None);
+        assert_eq!(interner.name(b).as_deref(), Some("token-b"));
+        assert_eq!(interner.len(), 2);
+    }
+}
@@ -0,0 +1,250 @@
+//! Ladder evaluator — complete-partition arbitrage over all range children.
+//!
+//! When a group's children partition the outcome space, exactly one child
+//! resolves YES, so a full set of child YES tokens always pays 1.
+//!
+//! Ladder BUY:  Σ Ask(child YES) < 1 → buy every child YES.
+//! Ladder SELL: Σ Bid(child YES) > 1 → sell every child YES (from minted sets).
+//! Reads the incrementally maintained price and fee sums in `LadderState`; only
+//! the signal builder touches the PriceTable. Profit is net of taker fees.
+
+use crate::engine::state::{
+    EngineConfig, GroupState, OpportunitySite, PriceSlot, PriceTable,
+};
+use crate::engine::trio_evaluator::meets_threshold;
+use crate::types::order::OrderSide;
+use crate::types::signal::{ArbSignal, LegRole};
+use crate::types::strategy::StrategyKind;
+
+/// Internal profit result from ladder calculation.
+pub struct LadderProfit {
+    /// Net of taker fees.
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
+}
+
+/// Raw ladder edge — no threshold applied. `None` while a leg is unquoted
+/// or `strategy` is not LadderBuy / LadderSell.
+pub fn calc_ladder_edge(group: &GroupState, strategy: StrategyKind) -> Option<LadderProfit> {
+    let ladder = group.ladder.as_ref()?;
+    match strategy {
+        StrategyKind::LadderBuy => {
+            if ladder.missing_asks > 0 {
+                return None;
+            }
+            let gross_profit_abs = 1.0 - ladder.sum_ask;
+            let profit_abs = gross_profit_abs - ladder.sum_ask_fee;
+            Some(LadderProfit {
+                profit_abs,
+                profit_bps: (profit_abs / ladder.sum_ask) * 10000.0,
+                gross_profit_abs,
+                gross_profit_bps: (gross_profit_abs / ladder.sum_ask) * 10000.0,
+            })
+        }
+        StrategyKind::LadderSell => {
+            if ladder.missing_bids > 0 {
+                return None;
+            }
+            // Cost basis is the 1.0 of collateral locked in the minted set
+            let gross_profit_abs = ladder.sum_bid - 1.0;
+            let profit_abs = gross_profit_abs - ladder.sum_bid_fee;
+            Some(LadderProfit {
+                profit_abs,
+                profit_bps: profit_abs * 10000.0,
+                gross_profit_abs,
+                gross_profit_bps: gross_profit_abs * 10000.0,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Evaluate the group's ladder: calc profit → check staleness → check cooldown → build signal.
+pub fn evaluate_ladder(
+    group: &mut GroupState,
+    pt: &PriceTable,
+    cfg: &EngineConfig,
+    now_ms: i64,
+    strategy: StrategyKind,
+) -> Option<ArbSignal> {
+    let Some(calc) = calc_ladder_edge(group, strategy) else {
+        group.track_opportunity(OpportunitySite::Ladder, strategy, false, f64::NAN, f64::NAN, now_ms);
+        return None;
+    };
+    let above = meets_threshold(calc.profit_abs, calc.profit_bps, cfg);
+    group.track_opportunity(OpportunitySite::Ladder, strategy, above, calc.profit_abs, calc.profit_bps, now_ms);
+    let ladder = group.ladder.as_mut()?;
+    ladder.edge_stats[strategy.index()].record(calc.profit_abs, calc.profit_bps, now_ms);
+    if !above {
+        ladder.emission[strategy.index()].close();
+        return None;
+    }
+
+    // Staleness check
+    let legs: Vec<&PriceSlot> = ladder
+        .child_idxs
+        .iter()
+        .map(|&c| pt.get(group.child_metas[c as usize].yes_slot))
+        .collect();
+    if !cfg.quotes_fresh(strategy, &legs, now_ms) {
+        ladder.stale_skip_count += 1;
+        return None;
+    }
+
+    // Emission check (cooldown / hysteresis)
+    let last_emitted_ms = match strategy {
+        StrategyKind::LadderBuy => ladder.last_emitted_buy_ms,
+        _ => ladder.last_emitted_sell_ms,
+    };
+    if !cfg.should_emit(&ladder.emission[strategy.index()], last_emitted_ms, calc.profit_bps, now_ms) {
+        return None;
+    }
+
+    // Mark cooldown
+    match strategy {
+        StrategyKind::LadderBuy => ladder.last_emitted_buy_ms = now_ms,
+        _ => ladder.last_emitted_sell_ms = now_ms,
+    }
+    ladder.emission[strategy.index()].emitted(calc.profit_bps);
+
+    Some(build_ladder_signal(group, strategy, &calc, &legs, now_ms))
+}
+
+fn build_ladder_signal(
+    group: &GroupState,
+    strategy: StrategyKind,
+    calc: &LadderProfit,
+    legs: &[&PriceSlot],
+    now_ms: i64,
+) -> ArbSignal {
+    let ladder = group.ladder.as_ref().expect("ladder signal without ladder");
+
+    // BUY fills against asks, SELL against bids
+    let (side, reason, mode) = match strategy {
+        StrategyKind::LadderBuy => (OrderSide::Buy, "POLYMARKET_LADDER_BUY_SUM_ASK_LT_1", "BUY"),
+        _ => (OrderSide::Sell, "POLYMARKET_LADDER_SELL_SUM_BID_GT_1", "SELL"),
+    };
+    let legs: Vec<_> = ladder
+        .child_idxs
+        .iter()
+        .zip(legs)
+        .map(|(&c, q)| group.child_metas[c as usize].leg(0, LegRole::Child, side, q, c as i32))
+        .collect();
+
+    ArbSignal {
+        group_key: group.group_key.clone(),
+        event_slug: group.event_slug.clone(),
+        crypto: group.crypto.clone(),
+        strategy,
+
+        profit_abs: calc.profit_abs,
+        profit_bps: calc.profit_bps,
+        gross_profit_abs: calc.gross_profit_abs,
+        gross_profit_bps: calc.gross_profit_bps,
+        timestamp_ms: now_ms,
+
+        // One YES leg per child, no parent legs
+        child_index: legs[0].child_index,
+        legs,
+
+        // Set context — a full set always pays 1
+        total_cost: Some(ladder.sum_ask),
+        total_bid: Some(ladder.sum_bid),
+        payout: Some(1.0),
+        mode: Some(mode),
+
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::engine::RangeGroupInput;
+    use crate::engine::state::EngineState;
+    use crate::engine::test_support::{market, quote};
+    use std::sync::Arc;
+
+    /// Three children partitioning the outcome space: below 2800 | 2800-2900 | above 2900.
+    fn make_ladder_engine(fee_rate_bps: u32) -> EngineState {
+        let mut engine = EngineState::new(EngineConfig::default());
+        let mut input = RangeGroupInput {
+            group_key: "eth".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![
+                market("below-2800", "c1_yes", "c1_no", None, Some(2800.0), "below"),
+                market("r2800", "c2_yes", "c2_no", Some(2800.0), Some(2900.0), "range"),
+                market("above-2900", "c3_yes", "c3_no", Some(2900.0), None, "above"),
+            ],
+            parents: vec![],
+        };
+        for m in &mut input.children {
+            m.fee_rate_bps = [fee_rate_bps; 2];
+        }
+        engine.update_market_structure(vec![input]);
+        assert!(engine.groups[0].ladder.is_some());
+        engine
+    }
+
+    /// Quote every leg's YES, then reload the ladder's running sums.
+    fn quote_legs(engine: &mut EngineState, quotes: [(f64, f64); 3]) {
+        for (token, (bid, ask)) in ["c1_yes", "c2_yes", "c3_yes"].into_iter().zip(quotes) {
+            quote(engine, token, bid, ask);
+        }
+        engine.resync_ladders();
+    }
+
+    #[test]
+    fn test_ladder_buy_profit_net_of_fees() {
+        // Σ ask = 0.30 + 0.30 + 0.35 = 0.95 → gross 0.05
+        // Fees at 200 bps: 0.02 × (0.30 + 0.30 + 0.35) = 0.019 → net 0.031
+        let mut engine = make_ladder_engine(200);
+        quote_legs(&mut engine, [(0.28, 0.30), (0.28, 0.30), (0.33, 0.35)]);
+
+        let r = calc_ladder_edge(&engine.groups[0], StrategyKind::LadderBuy).unwrap();
+        assert!((r.gross_profit_abs - 0.05).abs() < 1e-10);
+        assert!((r.profit_abs - 0.031).abs() < 1e-10);
+        assert!((r.profit_bps - 0.031 / 0.95 * 10000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ladder_sell_basis_is_one_set() {
+        // Σ bid = 0.40 + 0.35 + 0.30 = 1.05 → gross 0.05 on the 1.0 minted set
+        // Fees at 200 bps: 0.02 × (0.40 + 0.35 + 0.30) = 0.021 → net 0.029
+        let mut engine = make_ladder_engine(200);
+        quote_legs(&mut engine, [(0.40, 0.42), (0.35, 0.37), (0.30, 0.32)]);
+
+        let r = calc_ladder_edge(&engine.groups[0], StrategyKind::LadderSell).unwrap();
+        assert!((r.gross_profit_bps - 500.0).abs() < 1e-6);
+        assert!((r.profit_abs - 0.029).abs() < 1e-10);
+        assert!((r.profit_bps - 290.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ladder_needs_every_leg_and_net_threshold() {
+        let mut engine = make_ladder_engine(200);
+        quote_legs(&mut engine, [(0.28, 0.30), (0.28, 0.30), (f64::NAN, f64::NAN)]);
+        assert!(calc_ladder_edge(&engine.groups[0], StrategyKind::LadderBuy).is_none());
+        assert!(calc_ladder_edge(&engine.groups[0], StrategyKind::Monotone).is_none());
+
+        quote_legs(&mut engine, [(0.28, 0.30), (0.28, 0.30), (0.33, 0.35)]);
+        let cfg = |min_profit_abs| EngineConfig {
+            min_profit_abs,
+            min_profit_bps: 0.0,
+            cooldown_ms: 0,
+            ..EngineConfig::default()
+        };
+        let group = Arc::make_mut(&mut engine.groups[0]);
+        // Gross 0.05 clears 0.04, net 0.031 does not
+        assert!(evaluate_ladder(group, &engine.price_table, &cfg(0.04), 1, StrategyKind::LadderBuy).is_none());
+        let sig = evaluate_ladder(group, &engine.price_table, &cfg(0.03), 2, StrategyKind::LadderBuy).unwrap();
+        assert_eq!(sig.legs.len(), 3);
+        assert!(sig.total_cost.is_some_and(|c| (c - 0.95).abs() < 1e-10));
+        let ladder = group.ladder.as_ref().unwrap();
+        assert_eq!(ladder.edge_stats[StrategyKind::LadderBuy.index()].samples, 2);
+        assert_eq!(ladder.last_emitted_buy_ms, 2);
+    }
+}
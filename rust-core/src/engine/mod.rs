@@ -1,4 +1,18 @@
 pub mod state;
+pub mod intern;
 pub mod trio_evaluator;
 pub mod range_evaluator;
+pub mod bracket_evaluator;
+pub mod ladder_evaluator;
+pub mod monotone_evaluator;
+pub mod parity_evaluator;
+pub mod neg_risk_evaluator;
+pub mod inspect;
+pub mod diagnostics;
+pub mod scanner;
+pub mod persistence;
+pub mod owner;
+#[cfg(test)]
+pub mod test_support;
+#[allow(clippy::module_inception)]
 pub mod engine;
@@ -0,0 +1,227 @@
+//! Monotone evaluator — above/below strike monotonicity arbitrage.
+//!
+//! For strikes k1 < k2: P(above k1) ≥ P(above k2) and P(below k1) ≤ P(below k2).
+//! When the YES that must be worth more asks less than the other YES bids, buying
+//! the first and selling the second locks in the spread:
+//!
+//! Above: Ask(above k1 YES) < Bid(above k2 YES) → BUY above k1, SELL above k2
+//! Below: Ask(below k2 YES) < Bid(below k1 YES) → BUY below k2, SELL below k1
+//! Profit = Bid(sell YES) - Ask(buy YES) - fees. Reads YES slots only.
+
+use crate::engine::state::{
+    taker_fee, EngineConfig, GroupState, MarketKind, OpportunitySite, PriceSlot, PriceTable,
+};
+use crate::engine::trio_evaluator::meets_threshold;
+use crate::types::order::OrderSide;
+use crate::types::signal::{ArbSignal, LegRole};
+use crate::types::strategy::StrategyKind;
+
+/// Internal profit result from a monotonicity pair.
+pub struct MonotoneProfit {
+    /// Net of taker fees.
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
+}
+
+/// Raw pair edge — no threshold applied. `None` when a leg has no quote.
+pub fn calc_monotone_edge(group: &GroupState, pair_idx: usize, pt: &PriceTable) -> Option<MonotoneProfit> {
+    let pair = &group.monotone_pairs[pair_idx];
+    let buy_meta = &group.parent_metas[pair.buy_idx as usize];
+    let sell_meta = &group.parent_metas[pair.sell_idx as usize];
+    let buy = pt.get(buy_meta.yes_slot);
+    let sell = pt.get(sell_meta.yes_slot);
+    if buy.best_ask.is_nan() || sell.best_bid.is_nan() {
+        return None;
+    }
+
+    let fees = taker_fee(buy.best_ask, buy_meta.fee_rate_bps[0]) + taker_fee(sell.best_bid, sell_meta.fee_rate_bps[0]);
+    let gross_profit_abs = sell.best_bid - buy.best_ask;
+    let profit_abs = gross_profit_abs - fees;
+    let bps = |abs: f64| if buy.best_ask > 0.0 { (abs / buy.best_ask) * 10000.0 } else { 0.0 };
+    Some(MonotoneProfit {
+        profit_abs,
+        profit_bps: bps(profit_abs),
+        gross_profit_abs,
+        gross_profit_bps: bps(gross_profit_abs),
+    })
+}
+
+/// Evaluate one pair: calc profit → check staleness → check cooldown → build signal.
+pub fn evaluate_monotone_pair(
+    group: &mut GroupState,
+    pair_idx: usize,
+    pt: &PriceTable,
+    cfg: &EngineConfig,
+    now_ms: i64,
+) -> Option<ArbSignal> {
+    let strategy = StrategyKind::Monotone;
+    let site = OpportunitySite::Monotone(pair_idx);
+    let Some(calc) = calc_monotone_edge(group, pair_idx, pt) else {
+        group.track_opportunity(site, strategy, false, f64::NAN, f64::NAN, now_ms);
+        return None;
+    };
+    group.monotone_pairs[pair_idx].edge_stats[strategy.index()]
+        .record(calc.profit_abs, calc.profit_bps, now_ms);
+    let above = meets_threshold(calc.profit_abs, calc.profit_bps, cfg);
+    group.track_opportunity(site, strategy, above, calc.profit_abs, calc.profit_bps, now_ms);
+    if !above {
+        group.monotone_pairs[pair_idx].emission[strategy.index()].close();
+        return None;
+    }
+
+    // Staleness check
+    let pair = &group.monotone_pairs[pair_idx];
+    let buy = pt.get(group.parent_metas[pair.buy_idx as usize].yes_slot);
+    let sell = pt.get(group.parent_metas[pair.sell_idx as usize].yes_slot);
+    if !cfg.quotes_fresh(strategy, &[buy, sell], now_ms) {
+        group.monotone_pairs[pair_idx].stale_skip_count += 1;
+        return None;
+    }
+
+    // Emission check (cooldown / hysteresis)
+    if !cfg.should_emit(&pair.emission[strategy.index()], pair.last_emitted_ms, calc.profit_bps, now_ms) {
+        return None;
+    }
+
+    let signal = build_monotone_signal(group, pair_idx, &calc, buy, sell, now_ms);
+
+    // Mark cooldown
+    let pair = &mut group.monotone_pairs[pair_idx];
+    pair.last_emitted_ms = now_ms;
+    pair.emission[strategy.index()].emitted(calc.profit_bps);
+
+    Some(signal)
+}
+
+fn build_monotone_signal(
+    group: &GroupState,
+    pair_idx: usize,
+    calc: &MonotoneProfit,
+    buy: &PriceSlot,
+    sell: &PriceSlot,
+    now_ms: i64,
+) -> ArbSignal {
+    let pair = &group.monotone_pairs[pair_idx];
+    let buy_meta = &group.parent_metas[pair.buy_idx as usize];
+    let sell_meta = &group.parent_metas[pair.sell_idx as usize];
+    let reason = match pair.kind {
+        MarketKind::Below => "POLYMARKET_MONOTONE_BELOW_ASK_LT_BID",
+        _ => "POLYMARKET_MONOTONE_ABOVE_ASK_LT_BID",
+    };
+
+    ArbSignal {
+        group_key: group.group_key.clone(),
+        event_slug: group.event_slug.clone(),
+        crypto: group.crypto.clone(),
+        strategy: StrategyKind::Monotone,
+
+        profit_abs: calc.profit_abs,
+        profit_bps: calc.profit_bps,
+        gross_profit_abs: calc.gross_profit_abs,
+        gross_profit_bps: calc.gross_profit_bps,
+        timestamp_ms: now_ms,
+
+        // BUY the YES that must be worth more, SELL the other YES
+        legs: vec![
+            buy_meta.leg(0, LegRole::Parent, OrderSide::Buy, buy, -1),
+            sell_meta.leg(0, LegRole::ParentUpper, OrderSide::Sell, sell, -1),
+        ],
+        child_index: -1,
+
+        // No triangle context
+        total_cost: None,
+        total_bid: None,
+        payout: None,
+        mode: None,
+
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::engine::RangeGroupInput;
+    use crate::engine::state::EngineState;
+    use crate::engine::test_support::{market, quote};
+    use std::sync::Arc;
+
+    /// Above and below parents at 2800 / 2900 → one pair per kind.
+    fn make_monotone_engine(fee_rate_bps: u32) -> EngineState {
+        let mut engine = EngineState::new(EngineConfig::default());
+        let mut input = RangeGroupInput {
+            group_key: "eth".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![],
+            parents: vec![
+                market("above-2800", "a1_yes", "a1_no", Some(2800.0), None, "above"),
+                market("above-2900", "a2_yes", "a2_no", Some(2900.0), None, "above"),
+                market("below-2800", "b1_yes", "b1_no", None, Some(2800.0), "below"),
+                market("below-2900", "b2_yes", "b2_no", None, Some(2900.0), "below"),
+            ],
+        };
+        for m in &mut input.parents {
+            m.fee_rate_bps = [fee_rate_bps; 2];
+        }
+        engine.update_market_structure(vec![input]);
+        assert_eq!(engine.groups[0].monotone_pairs.len(), 2);
+        engine
+    }
+
+    fn pair_idx(engine: &EngineState, kind: MarketKind) -> usize {
+        engine.groups[0].monotone_pairs.iter().position(|p| p.kind == kind).unwrap()
+    }
+
+    #[test]
+    fn test_monotone_above_profit_net_of_fees() {
+        // Ask(above 2800) = 0.50 < Bid(above 2900) = 0.55 → gross 0.05, 1000 bps of the buy
+        // Fees at 100 bps: 0.01 × (0.50 + (1 - 0.55)) = 0.0095 → net 0.0405
+        let mut engine = make_monotone_engine(100);
+        quote(&mut engine, "a1_yes", 0.48, 0.50);
+        quote(&mut engine, "a2_yes", 0.55, 0.57);
+
+        let r = calc_monotone_edge(&engine.groups[0], pair_idx(&engine, MarketKind::Above), &engine.price_table).unwrap();
+        assert!((r.gross_profit_abs - 0.05).abs() < 1e-10);
+        assert!((r.gross_profit_bps - 1000.0).abs() < 1e-6);
+        assert!((r.profit_abs - 0.0405).abs() < 1e-10);
+        assert!((r.profit_bps - 810.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_monotone_below_buys_higher_strike() {
+        // Below 2900 must be worth more: Ask(below 2900) = 0.40 < Bid(below 2800) = 0.43
+        let mut engine = make_monotone_engine(0);
+        quote(&mut engine, "b2_yes", 0.38, 0.40);
+        quote(&mut engine, "b1_yes", 0.43, 0.45);
+        let idx = pair_idx(&engine, MarketKind::Below);
+
+        let r = calc_monotone_edge(&engine.groups[0], idx, &engine.price_table).unwrap();
+        assert!((r.profit_abs - 0.03).abs() < 1e-10);
+
+        let cfg = EngineConfig { min_profit_abs: 0.01, min_profit_bps: 0.0, ..EngineConfig::default() };
+        let group = Arc::make_mut(&mut engine.groups[0]);
+        let sig = evaluate_monotone_pair(group, idx, &engine.price_table, &cfg, 1).unwrap();
+        assert_eq!(sig.legs[0].asset_id, "b2_yes");
+        assert_eq!(sig.legs[0].side, OrderSide::Buy);
+        assert_eq!(sig.legs[1].asset_id, "b1_yes");
+        assert_eq!(sig.reason, "POLYMARKET_MONOTONE_BELOW_ASK_LT_BID");
+    }
+
+    #[test]
+    fn test_monotone_uncrossed_pair_records_negative_edge() {
+        let mut engine = make_monotone_engine(0);
+        let idx = pair_idx(&engine, MarketKind::Above);
+        quote(&mut engine, "a1_yes", 0.58, 0.60);
+        assert!(calc_monotone_edge(&engine.groups[0], idx, &engine.price_table).is_none());
+
+        // Ask 0.60 > Bid 0.55 — no arbitrage, but the edge is still recorded
+        quote(&mut engine, "a2_yes", 0.55, 0.57);
+        let group = Arc::make_mut(&mut engine.groups[0]);
+        assert!(evaluate_monotone_pair(group, idx, &engine.price_table, &EngineConfig::default(), 1).is_none());
+        let stats = &group.monotone_pairs[idx].edge_stats[StrategyKind::Monotone.index()];
+        assert!((stats.last_profit_abs + 0.05).abs() < 1e-10);
+    }
+}
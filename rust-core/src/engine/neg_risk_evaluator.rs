@@ -0,0 +1,351 @@
+//! Neg-risk evaluator — multi-outcome event arbitrage.
+//!
+//! Exactly one outcome of a neg-risk event resolves YES, and the neg-risk adapter
+//! converts 1 NO of an outcome into 1 YES of every other outcome.
+//!
+//! YES BUY:  Σ Ask(YES) < 1         → buy every YES.
+//! NO BUY:   Σ Ask(NO)  < n - 1     → buy every NO.
+//! CONVERT:  Ask(NO_i) < Σ_{j≠i} Bid(YES_j) → buy NO_i, convert, sell every other YES.
+//!           Info-only: the executor does not perform the on-chain conversion.
+//! Every leg is tagged `neg_risk` so the executor signs against the neg-risk exchange.
+//! Profit is net of each leg's taker fee.
+
+use crate::engine::state::{
+    taker_fee, EngineConfig, NegRiskEventState, NegRiskOutcome, PriceSlot, PriceTable,
+};
+use crate::engine::trio_evaluator::meets_threshold;
+use crate::types::order::OrderSide;
+use crate::types::signal::{ArbSignal, Leg, LegRole};
+use crate::types::strategy::StrategyKind;
+
+/// Internal profit result from a neg-risk event check.
+pub struct NegRiskProfit {
+    /// Cost of the BUY legs.
+    pub total_cost: f64,
+    /// What the position pays out (YES / NO BUY) or sells for (CONVERT).
+    pub payout: f64,
+    /// Net of taker fees.
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
+    /// CONVERT: outcome whose NO is bought.
+    pub convert_idx: Option<usize>,
+}
+
+/// Raw event edge — no threshold applied. `None` when a needed quote is missing,
+/// the event has fewer than two outcomes, or `strategy` is not a neg-risk strategy.
+pub fn calc_neg_risk_edge(
+    event: &NegRiskEventState,
+    pt: &PriceTable,
+    strategy: StrategyKind,
+) -> Option<NegRiskProfit> {
+    let n = event.outcomes.len();
+    if n < 2 {
+        return None;
+    }
+
+    // (Σ price, Σ taker fee) over one side (0 = YES, 1 = NO) of every outcome
+    let sum = |side: usize, field: fn(&PriceSlot) -> f64| -> (f64, f64) {
+        event.outcomes.iter().fold((0.0, 0.0), |(total, fees), o| {
+            let price = field(pt.get(outcome_slot(o, side)));
+            (total + price, fees + taker_fee(price, o.fee_rate_bps[side]))
+        })
+    };
+
+    let (total_cost, payout, fees, convert_idx) = match strategy {
+        StrategyKind::NegRiskYesBuy => {
+            let (cost, fees) = sum(0, |q| q.best_ask);
+            (cost, 1.0, fees, None)
+        }
+        StrategyKind::NegRiskNoBuy => {
+            let (cost, fees) = sum(1, |q| q.best_ask);
+            (cost, (n - 1) as f64, fees, None)
+        }
+        StrategyKind::NegRiskConvert => {
+            let (total_yes_bid, total_yes_fee) = sum(0, |q| q.best_bid);
+            if total_yes_bid.is_nan() {
+                return None;
+            }
+            // Best outcome to convert: max Σ_{j≠i} Bid(YES_j) - Ask(NO_i) - fees
+            let (i, no_ask, revenue, fees) = event
+                .outcomes
+                .iter()
+                .enumerate()
+                .map(|(i, o)| {
+                    let yes_bid = pt.get(o.yes_slot).best_bid;
+                    let no_ask = pt.get(o.no_slot).best_ask;
+                    let fees = total_yes_fee - taker_fee(yes_bid, o.fee_rate_bps[0])
+                        + taker_fee(no_ask, o.fee_rate_bps[1]);
+                    (i, no_ask, total_yes_bid - yes_bid, fees)
+                })
+                .filter(|(_, ask, _, _)| !ask.is_nan())
+                .max_by(|a, b| (a.2 - a.1 - a.3).total_cmp(&(b.2 - b.1 - b.3)))?;
+            (no_ask, revenue, fees, Some(i))
+        }
+        _ => return None,
+    };
+    if total_cost.is_nan() {
+        return None;
+    }
+
+    let gross_profit_abs = payout - total_cost;
+    let profit_abs = gross_profit_abs - fees;
+    let bps = |abs: f64| if total_cost > 0.0 { (abs / total_cost) * 10000.0 } else { 0.0 };
+    Some(NegRiskProfit {
+        total_cost,
+        payout,
+        profit_abs,
+        profit_bps: bps(profit_abs),
+        gross_profit_abs,
+        gross_profit_bps: bps(gross_profit_abs),
+        convert_idx,
+    })
+}
+
+#[inline(always)]
+fn outcome_slot(outcome: &NegRiskOutcome, side: usize) -> u32 {
+    if side == 0 {
+        outcome.yes_slot
+    } else {
+        outcome.no_slot
+    }
+}
+
+/// Evaluate one event: calc profit → check staleness → check cooldown → build signal.
+pub fn evaluate_neg_risk_event(
+    event: &mut NegRiskEventState,
+    pt: &PriceTable,
+    cfg: &EngineConfig,
+    now_ms: i64,
+    strategy: StrategyKind,
+) -> Option<ArbSignal> {
+    let Some(calc) = calc_neg_risk_edge(event, pt, strategy) else {
+        event.track_opportunity(strategy, false, f64::NAN, f64::NAN, now_ms);
+        return None;
+    };
+    event.edge_stats[strategy.index()].record(calc.profit_abs, calc.profit_bps, now_ms);
+    let above = meets_threshold(calc.profit_abs, calc.profit_bps, cfg);
+    event.track_opportunity(strategy, above, calc.profit_abs, calc.profit_bps, now_ms);
+    if !above {
+        event.emission[strategy.index()].close();
+        return None;
+    }
+
+    // Staleness check — every leg the strategy trades
+    let legs: Vec<&PriceSlot> = match (strategy, calc.convert_idx) {
+        (StrategyKind::NegRiskNoBuy, _) => event.outcomes.iter().map(|o| pt.get(o.no_slot)).collect(),
+        (_, Some(i)) => event
+            .outcomes
+            .iter()
+            .enumerate()
+            .map(|(j, o)| pt.get(if j == i { o.no_slot } else { o.yes_slot }))
+            .collect(),
+        _ => event.outcomes.iter().map(|o| pt.get(o.yes_slot)).collect(),
+    };
+    if !cfg.quotes_fresh(strategy, &legs, now_ms) {
+        event.stale_skip_count += 1;
+        return None;
+    }
+
+    // Emission check (cooldown / hysteresis)
+    let last_emitted_ms = match strategy {
+        StrategyKind::NegRiskYesBuy => event.last_emitted_yes_buy_ms,
+        StrategyKind::NegRiskNoBuy => event.last_emitted_no_buy_ms,
+        _ => event.last_emitted_convert_ms,
+    };
+    if !cfg.should_emit(&event.emission[strategy.index()], last_emitted_ms, calc.profit_bps, now_ms) {
+        return None;
+    }
+
+    let signal = build_neg_risk_signal(event, strategy, &calc, pt, now_ms);
+
+    // Mark cooldown
+    match strategy {
+        StrategyKind::NegRiskYesBuy => event.last_emitted_yes_buy_ms = now_ms,
+        StrategyKind::NegRiskNoBuy => event.last_emitted_no_buy_ms = now_ms,
+        _ => event.last_emitted_convert_ms = now_ms,
+    }
+    event.emission[strategy.index()].emitted(calc.profit_bps);
+
+    Some(signal)
+}
+
+/// Neg-risk signal leg for one side (0 = YES, 1 = NO) of an outcome.
+fn outcome_leg(
+    outcome: &NegRiskOutcome,
+    token: usize,
+    role: LegRole,
+    side: OrderSide,
+    q: &PriceSlot,
+    outcome_idx: usize,
+) -> Leg {
+    Leg {
+        neg_risk: true,
+        fee_rate_bps: outcome.fee_rate_bps[token],
+        child_index: outcome_idx as i32,
+        ..Leg::at_quote(role, side, outcome.clob_token_ids[token].clone(), outcome.slug.clone(), q)
+    }
+}
+
+fn build_neg_risk_signal(
+    event: &NegRiskEventState,
+    strategy: StrategyKind,
+    calc: &NegRiskProfit,
+    pt: &PriceTable,
+    now_ms: i64,
+) -> ArbSignal {
+    // CONVERT: BUY NO_i as the parent, SELL every other YES as children.
+    // YES / NO BUY: BUY every outcome as children, no parent.
+    let (legs, reason, mode) = match calc.convert_idx {
+        Some(i) => {
+            let no = &event.outcomes[i];
+            let mut legs = vec![outcome_leg(no, 1, LegRole::Parent, OrderSide::Buy, pt.get(no.no_slot), i)];
+            legs.extend(
+                event
+                    .outcomes
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(j, o)| outcome_leg(o, 0, LegRole::Child, OrderSide::Sell, pt.get(o.yes_slot), j)),
+            );
+            (legs, "POLYMARKET_NEG_RISK_CONVERT_NO_ASK_LT_OTHER_YES_BID", "CONVERT")
+        }
+        None => {
+            let token = if strategy == StrategyKind::NegRiskNoBuy { 1 } else { 0 };
+            let legs = event
+                .outcomes
+                .iter()
+                .enumerate()
+                .map(|(j, o)| outcome_leg(o, token, LegRole::Child, OrderSide::Buy, pt.get(outcome_slot(o, token)), j))
+                .collect();
+            let reason = if token == 0 {
+                "POLYMARKET_NEG_RISK_YES_BUY_SUM_ASK_LT_1"
+            } else {
+                "POLYMARKET_NEG_RISK_NO_BUY_SUM_ASK_LT_N_MINUS_1"
+            };
+            (legs, reason, "BUY")
+        }
+    };
+    let children = || legs.iter().filter(|l| l.role == LegRole::Child);
+    let children_sum_bid = children().map(|l| l.best_bid).sum();
+    let child_index = children().next().expect("neg-risk event has at least two outcomes").child_index;
+
+    ArbSignal {
+        group_key: event.event_id.clone(),
+        event_slug: event.event_slug.clone(),
+        crypto: event.crypto.clone(),
+        strategy,
+
+        profit_abs: calc.profit_abs,
+        profit_bps: calc.profit_bps,
+        gross_profit_abs: calc.gross_profit_abs,
+        gross_profit_bps: calc.gross_profit_bps,
+        timestamp_ms: now_ms,
+
+        legs,
+        child_index,
+
+        // Set context
+        total_cost: Some(calc.total_cost),
+        total_bid: Some(children_sum_bid),
+        payout: Some(calc.payout),
+        mode: Some(mode),
+
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::state::EngineState;
+    use crate::engine::test_support::{neg_risk_event, quote};
+    use std::sync::Arc;
+
+    /// Three-outcome event with every outcome's YES and NO at `fee_rate_bps`.
+    fn make_event_engine(fee_rate_bps: u32) -> EngineState {
+        let mut engine = EngineState::new(EngineConfig::default());
+        let mut input = neg_risk_event(&["cut", "hold", "hike"]);
+        for outcome in &mut input.outcomes {
+            outcome.fee_rate_bps = [fee_rate_bps; 2];
+        }
+        assert_eq!(engine.update_neg_risk_events(vec![input]), 1);
+        engine
+    }
+
+    fn edge(engine: &EngineState, strategy: StrategyKind) -> Option<NegRiskProfit> {
+        calc_neg_risk_edge(&engine.neg_risk_events[0], &engine.price_table, strategy)
+    }
+
+    #[test]
+    fn test_yes_buy_profit_net_of_fees() {
+        // Σ Ask(YES) = 0.30 + 0.30 + 0.35 = 0.95 → gross 0.05
+        // Fees at 100 bps: 0.01 × (0.30 + 0.30 + 0.35) = 0.0095 → net 0.0405
+        let mut engine = make_event_engine(100);
+        for (token, ask) in [("cut_yes", 0.30), ("hold_yes", 0.30), ("hike_yes", 0.35)] {
+            quote(&mut engine, token, ask - 0.02, ask);
+        }
+        let r = edge(&engine, StrategyKind::NegRiskYesBuy).unwrap();
+        assert!((r.payout - 1.0).abs() < 1e-10);
+        assert!((r.gross_profit_abs - 0.05).abs() < 1e-10);
+        assert!((r.profit_abs - 0.0405).abs() < 1e-10);
+        assert!((r.profit_bps - 0.0405 / 0.95 * 10000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_no_buy_pays_outcomes_minus_one() {
+        // Payout = 3 - 1 = 2; Σ Ask(NO) = 0.60 + 0.65 + 0.70 = 1.95 → profit 0.05
+        let mut engine = make_event_engine(0);
+        for (token, ask) in [("cut_no", 0.60), ("hold_no", 0.65), ("hike_no", 0.70)] {
+            quote(&mut engine, token, ask - 0.02, ask);
+        }
+        let r = edge(&engine, StrategyKind::NegRiskNoBuy).unwrap();
+        assert!((r.payout - 2.0).abs() < 1e-10);
+        assert!((r.total_cost - 1.95).abs() < 1e-10);
+        assert!((r.profit_abs - 0.05).abs() < 1e-10);
+        assert!(edge(&engine, StrategyKind::NegRiskYesBuy).is_none());
+    }
+
+    #[test]
+    fn test_convert_picks_best_outcome() {
+        // Σ Bid(YES) = 0.28 + 0.28 + 0.33 = 0.89
+        // cut:  0.89 - 0.28 - 0.55 = +0.06   hold / hike: 0.61 - 0.70, 0.56 - 0.70 < 0
+        let mut engine = make_event_engine(0);
+        for (token, bid) in [("cut_yes", 0.28), ("hold_yes", 0.28), ("hike_yes", 0.33)] {
+            quote(&mut engine, token, bid, bid + 0.02);
+        }
+        for (token, ask) in [("cut_no", 0.55), ("hold_no", 0.70), ("hike_no", 0.70)] {
+            quote(&mut engine, token, ask - 0.02, ask);
+        }
+        let r = edge(&engine, StrategyKind::NegRiskConvert).unwrap();
+        assert_eq!(r.convert_idx, Some(0));
+        assert!((r.total_cost - 0.55).abs() < 1e-10);
+        assert!((r.payout - 0.61).abs() < 1e-10);
+        assert!((r.profit_abs - 0.06).abs() < 1e-10);
+        assert!(edge(&engine, StrategyKind::LadderBuy).is_none());
+    }
+
+    #[test]
+    fn test_threshold_applies_to_net_edge() {
+        let mut engine = make_event_engine(100);
+        for (token, ask) in [("cut_yes", 0.30), ("hold_yes", 0.30), ("hike_yes", 0.35)] {
+            quote(&mut engine, token, ask - 0.02, ask);
+        }
+        let cfg = |min_profit_abs| EngineConfig {
+            min_profit_abs,
+            min_profit_bps: 0.0,
+            cooldown_ms: 0,
+            ..EngineConfig::default()
+        };
+        let event = Arc::make_mut(&mut engine.neg_risk_events[0]);
+        // Gross 0.05 clears 0.045, net 0.0405 does not
+        let strategy = StrategyKind::NegRiskYesBuy;
+        assert!(evaluate_neg_risk_event(event, &engine.price_table, &cfg(0.045), 1, strategy).is_none());
+        let sig = evaluate_neg_risk_event(event, &engine.price_table, &cfg(0.04), 2, strategy).unwrap();
+        assert_eq!(sig.legs.len(), 3);
+        assert!(sig.legs.iter().all(|l| l.neg_risk));
+        assert_eq!(event.edge_stats[strategy.index()].samples, 2);
+        assert_eq!(event.last_emitted_yes_buy_ms, 2);
+    }
+}
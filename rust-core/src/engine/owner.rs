@@ -0,0 +1,317 @@
+//! Single-owner engine thread.
+//!
+//! One dedicated OS thread owns the `EngineState`. Socket updates and commands
+//! (structure / config changes from JS) reach it over channels, so nothing ever
+//! locks the engine on the hot path. Readers (status, inspection, near-miss scans,
+//! persistence) load a consistent `Arc<EngineState>` snapshot published through
+//! an `ArcSwap` — a pointer load, never blocking the engine thread.
+//!
+//! Snapshots are published after every command and, while updates flow, at most
+//! once per `publish_interval_ms` (plus once when the stream goes idle). Publishing
+//! takes `EngineState::snapshot()`, which shares the structure, every untouched
+//! group and the rarely-changing logs with the previous snapshot and leaves out
+//! the engine-thread working state, so it copies the price slots and what was
+//! updated since — not the whole engine.
+
+use crate::engine::intern::TokenInterner;
+use crate::engine::state::EngineState;
+use crate::types::market::{TokenUpdate, TopOfBookUpdate};
+use crate::types::signal::{ArbSignal, OpportunityEvent};
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::info;
+
+/// A mutation run on the engine thread.
+pub type EngineCommand = Box<dyn FnOnce(&mut EngineState) + Send>;
+
+/// Handle to the engine thread: update/command senders and the snapshot reader.
+pub struct EngineHandle {
+    update_tx: mpsc::UnboundedSender<TokenUpdate>,
+    cmd_tx: mpsc::UnboundedSender<EngineCommand>,
+    snapshot: Arc<ArcSwap<EngineState>>,
+    interner: Arc<TokenInterner>,
+}
+
+impl EngineHandle {
+    /// Move `state` onto a new engine thread. `on_signals` runs on that thread
+    /// for every non-empty batch of signals (e.g. forwards them to the executor),
+    /// `on_opportunities` for every non-empty batch of opportunity lifecycle events
+    /// and `on_prices` for every non-empty batch of price feed updates.
+    pub fn spawn<F, G, H>(
+        state: EngineState,
+        publish_interval_ms: u64,
+        on_signals: F,
+        on_opportunities: G,
+        on_prices: H,
+    ) -> Self
+    where
+        F: FnMut(Vec<ArbSignal>) + Send + 'static,
+        G: FnMut(Vec<OpportunityEvent>) + Send + 'static,
+        H: FnMut(Vec<TopOfBookUpdate>) + Send + 'static,
+    {
+        let (update_tx, update_rx) = mpsc::unbounded_channel();
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let snapshot = Arc::new(ArcSwap::from_pointee(state.snapshot()));
+        let interner = state.interner.clone();
+
+        let published = snapshot.clone();
+        let interval = Duration::from_millis(publish_interval_ms);
+        std::thread::Builder::new()
+            .name("rust-core-engine".to_string())
+            .spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_time()
+                    .build()
+                    .expect("Failed to create engine thread runtime");
+                runtime.block_on(run_engine(
+                    state,
+                    update_rx,
+                    cmd_rx,
+                    published,
+                    interval,
+                    on_signals,
+                    on_opportunities,
+                    on_prices,
+                ));
+                info!("Engine thread stopped");
+            })
+            .expect("Failed to spawn engine thread");
+
+        Self {
+            update_tx,
+            cmd_tx,
+            snapshot,
+            interner,
+        }
+    }
+
+    /// Sender for socket updates (hand a clone to the socket manager).
+    pub fn update_sender(&self) -> mpsc::UnboundedSender<TokenUpdate> {
+        self.update_tx.clone()
+    }
+
+    /// Token interner shared with the socket parser.
+    pub fn interner(&self) -> Arc<TokenInterner> {
+        self.interner.clone()
+    }
+
+    /// Latest published read snapshot.
+    pub fn snapshot(&self) -> Arc<EngineState> {
+        self.snapshot.load_full()
+    }
+
+    /// Run `f` on the engine thread and wait for its result.
+    ///
+    /// Queued behind nothing but earlier commands (commands take priority over
+    /// updates); a fresh snapshot is published before the result is returned.
+    pub fn call<R, F>(&self, f: F) -> Result<R, String>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut EngineState) -> R + Send + 'static,
+    {
+        let (reply_tx, reply_rx) = std::sync::mpsc::sync_channel(1);
+        let snapshot = self.snapshot.clone();
+        let cmd: EngineCommand = Box::new(move |engine| {
+            let result = f(engine);
+            // Publish before replying so the caller reads its own write
+            snapshot.store(Arc::new(engine.snapshot()));
+            let _ = reply_tx.send(result);
+        });
+        self.cmd_tx
+            .send(cmd)
+            .map_err(|_| "Engine thread stopped".to_string())?;
+        reply_rx
+            .recv()
+            .map_err(|_| "Engine thread stopped".to_string())
+    }
+}
+
+// =============================================================================
+// ENGINE THREAD LOOP
+// =============================================================================
+
+#[allow(clippy::too_many_arguments)]
+async fn run_engine<F, G, H>(
+    mut state: EngineState,
+    mut update_rx: mpsc::UnboundedReceiver<TokenUpdate>,
+    mut cmd_rx: mpsc::UnboundedReceiver<EngineCommand>,
+    snapshot: Arc<ArcSwap<EngineState>>,
+    interval: Duration,
+    mut on_signals: F,
+    mut on_opportunities: G,
+    mut on_prices: H,
+) where
+    F: FnMut(Vec<ArbSignal>),
+    G: FnMut(Vec<OpportunityEvent>),
+    H: FnMut(Vec<TopOfBookUpdate>),
+{
+    let publish = |state: &EngineState| snapshot.store(Arc::new(state.snapshot()));
+    let mut ticker = tokio::time::interval(interval.max(Duration::from_millis(1)));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    ticker.reset(); // skip the immediate first tick
+    let mut last_publish = Instant::now();
+    let mut dirty = false;
+
+    loop {
+        tokio::select! {
+            biased;
+
+            cmd = cmd_rx.recv() => {
+                let Some(cmd) = cmd else { break };
+                cmd(&mut state); // publishes its own snapshot
+                last_publish = Instant::now();
+                dirty = false;
+            }
+
+            update = update_rx.recv() => {
+                let Some(u) = update else { break };
+                let signals = state.handle_top_of_book_id(
+                    u.token,
+                    u.best_bid,
+                    u.best_ask,
+                    u.best_bid_size,
+                    u.best_ask_size,
+                    u.timestamp_ms,
+                );
+                if !signals.is_empty() {
+                    on_signals(signals);
+                }
+                let events = state.take_opportunity_events();
+                if !events.is_empty() {
+                    on_opportunities(events);
+                }
+                let prices = state.take_price_updates();
+                if !prices.is_empty() {
+                    on_prices(prices);
+                }
+                dirty = true;
+                if last_publish.elapsed() >= interval {
+                    publish(&state);
+                    last_publish = Instant::now();
+                    dirty = false;
+                }
+            }
+
+            // Idle stream: make sure readers eventually see the last updates,
+            // and flush price feed changes held back by the throttle
+            _ = ticker.tick(), if dirty || state.price_feed.has_pending() => {
+                let prices = state.take_price_updates();
+                if !prices.is_empty() {
+                    on_prices(prices);
+                }
+                if dirty {
+                    publish(&state);
+                    last_publish = Instant::now();
+                    dirty = false;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::engine::RangeGroupInput;
+    use crate::engine::state::EngineConfig;
+    use crate::engine::test_support::market;
+    use crate::types::signal::OpportunityPhase;
+
+    fn group_input() -> RangeGroupInput {
+        RangeGroupInput {
+            group_key: "eth".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![market("range", "ry", "rn", Some(2800.0), Some(2900.0), "range")],
+            parents: vec![
+                market("above-2800", "ly", "ln", Some(2800.0), None, "above"),
+                market("above-2900", "uy", "un", Some(2900.0), None, "above"),
+            ],
+        }
+    }
+
+    fn send_update(engine: &EngineHandle, token: &str, bid: f64, ask: f64, ts: i64) {
+        let token = engine.interner().get(token).unwrap();
+        engine
+            .update_sender()
+            .send(TokenUpdate {
+                token,
+                best_bid: bid,
+                best_ask: ask,
+                best_bid_size: Some(100.0),
+                best_ask_size: Some(100.0),
+                timestamp_ms: ts,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_engine_thread_commands_updates_and_snapshots() {
+        let (signal_tx, signal_rx) = std::sync::mpsc::channel();
+        let state = EngineState::new(EngineConfig {
+            min_profit_bps: 10.0,
+            min_profit_abs: 0.001,
+            ..EngineConfig::default()
+        });
+        let (event_tx, event_rx) = std::sync::mpsc::channel();
+        let (price_tx, price_rx) = std::sync::mpsc::channel();
+        let engine = EngineHandle::spawn(
+            state,
+            60_000,
+            move |signals| {
+                for s in signals {
+                    let _ = signal_tx.send(s);
+                }
+            },
+            move |events| {
+                for e in events {
+                    let _ = event_tx.send(e);
+                }
+            },
+            move |prices| {
+                for p in prices {
+                    let _ = price_tx.send(p);
+                }
+            },
+        );
+
+        let trios = engine
+            .call(|e| e.update_market_structure(vec![group_input()]))
+            .unwrap();
+        assert_eq!(trios, 1);
+        // Commands publish immediately
+        assert_eq!(engine.snapshot().groups.len(), 1);
+        engine
+            .call(|e| e.set_price_feed(&["rn".to_string()], 0))
+            .unwrap();
+
+        // Triangle: 0.60 + 0.50 + 0.80 = 1.90 < 2.0
+        send_update(&engine, "ly", 0.58, 0.60, 1);
+        send_update(&engine, "un", 0.48, 0.50, 2);
+        send_update(&engine, "rn", 0.78, 0.80, 3);
+
+        let signal = signal_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("signal from engine thread");
+        assert_eq!(signal.group_key, "eth");
+
+        // Updates reach readers throttled; a command is a barrier + publish
+        engine.call(|_| ()).unwrap();
+        let snap = engine.snapshot();
+        let slot = snap.price_table.token_to_slot["rn"];
+        assert!((snap.price_table.get(slot).best_ask - 0.80).abs() < 1e-10);
+
+        // The crossing also opened a lifecycle, delivered on the same thread
+        let opened: Vec<_> = event_rx.try_iter().collect();
+        assert!(opened
+            .iter()
+            .any(|e| e.phase == OpportunityPhase::Opened && e.strategy == "triangle" && e.structure == "trio"));
+
+        // Only the watched token reaches the price feed
+        let prices: Vec<_> = price_rx.try_iter().collect();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].asset_id, "rn");
+    }
+}
@@ -0,0 +1,253 @@
+//! Parity evaluator — single-market YES + NO arbitrage.
+//!
+//! YES and NO of one market always pay exactly 1 together.
+//!
+//! Parity BUY:  Ask(YES) + Ask(NO) < 1 → buy both, merge.
+//! Parity SELL: Bid(YES) + Bid(NO) > 1 → mint a set, sell both.
+//! Checked once per child / parent market, even when several groups list it.
+//! Profit is net of taker fees.
+
+use crate::engine::state::{
+    taker_fee, EngineConfig, GroupState, OpportunitySite, PriceSlot, PriceTable,
+};
+use crate::engine::trio_evaluator::meets_threshold;
+use crate::types::order::OrderSide;
+use crate::types::signal::{ArbSignal, LegRole};
+use crate::types::strategy::StrategyKind;
+
+/// Internal profit result from a parity check.
+pub struct ParityProfit {
+    pub total_ask: f64,
+    pub total_bid: f64,
+    /// Net of taker fees.
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
+}
+
+/// Raw parity edge — no threshold applied. `None` when a needed quote is
+/// missing or `strategy` is not ParityBuy / ParitySell.
+pub fn calc_parity_edge(
+    group: &GroupState,
+    parity_idx: usize,
+    pt: &PriceTable,
+    strategy: StrategyKind,
+) -> Option<ParityProfit> {
+    let meta = group.parity_states[parity_idx].meta(group);
+    let yes = pt.get(meta.yes_slot);
+    let no = pt.get(meta.no_slot);
+    let total_ask = yes.best_ask + no.best_ask;
+    let total_bid = yes.best_bid + no.best_bid;
+
+    let [yes_fee, no_fee] = meta.fee_rate_bps;
+
+    let (gross_profit_abs, fees, basis) = match strategy {
+        StrategyKind::ParityBuy => {
+            if total_ask.is_nan() {
+                return None;
+            }
+            let fees = taker_fee(yes.best_ask, yes_fee) + taker_fee(no.best_ask, no_fee);
+            (1.0 - total_ask, fees, total_ask)
+        }
+        StrategyKind::ParitySell => {
+            if total_bid.is_nan() {
+                return None;
+            }
+            // Cost basis is the 1.0 of collateral locked by minting
+            let fees = taker_fee(yes.best_bid, yes_fee) + taker_fee(no.best_bid, no_fee);
+            (total_bid - 1.0, fees, 1.0)
+        }
+        _ => return None,
+    };
+    let profit_abs = gross_profit_abs - fees;
+
+    Some(ParityProfit {
+        total_ask,
+        total_bid,
+        profit_abs,
+        profit_bps: (profit_abs / basis) * 10000.0,
+        gross_profit_abs,
+        gross_profit_bps: (gross_profit_abs / basis) * 10000.0,
+    })
+}
+
+/// Evaluate one market's parity: calc profit → check staleness → check cooldown → build signal.
+pub fn evaluate_parity(
+    group: &mut GroupState,
+    parity_idx: usize,
+    pt: &PriceTable,
+    cfg: &EngineConfig,
+    now_ms: i64,
+    strategy: StrategyKind,
+) -> Option<ArbSignal> {
+    let site = OpportunitySite::Parity(parity_idx);
+    let Some(calc) = calc_parity_edge(group, parity_idx, pt, strategy) else {
+        group.track_opportunity(site, strategy, false, f64::NAN, f64::NAN, now_ms);
+        return None;
+    };
+    group.parity_states[parity_idx].edge_stats[strategy.index()]
+        .record(calc.profit_abs, calc.profit_bps, now_ms);
+    let above = meets_threshold(calc.profit_abs, calc.profit_bps, cfg);
+    group.track_opportunity(site, strategy, above, calc.profit_abs, calc.profit_bps, now_ms);
+    if !above {
+        group.parity_states[parity_idx].emission[strategy.index()].close();
+        return None;
+    }
+
+    // Staleness check
+    let parity = &group.parity_states[parity_idx];
+    let meta = parity.meta(group);
+    let yes = pt.get(meta.yes_slot);
+    let no = pt.get(meta.no_slot);
+    if !cfg.quotes_fresh(strategy, &[yes, no], now_ms) {
+        group.parity_states[parity_idx].stale_skip_count += 1;
+        return None;
+    }
+
+    // Emission check (cooldown / hysteresis)
+    let last_emitted_ms = match strategy {
+        StrategyKind::ParityBuy => parity.last_emitted_buy_ms,
+        _ => parity.last_emitted_sell_ms,
+    };
+    if !cfg.should_emit(&parity.emission[strategy.index()], last_emitted_ms, calc.profit_bps, now_ms) {
+        return None;
+    }
+
+    let signal = build_parity_signal(group, parity_idx, strategy, &calc, yes, no, now_ms);
+
+    // Mark cooldown
+    let parity = &mut group.parity_states[parity_idx];
+    match strategy {
+        StrategyKind::ParityBuy => parity.last_emitted_buy_ms = now_ms,
+        _ => parity.last_emitted_sell_ms = now_ms,
+    }
+    parity.emission[strategy.index()].emitted(calc.profit_bps);
+
+    Some(signal)
+}
+
+fn build_parity_signal(
+    group: &GroupState,
+    parity_idx: usize,
+    strategy: StrategyKind,
+    calc: &ParityProfit,
+    yes: &PriceSlot,
+    no: &PriceSlot,
+    now_ms: i64,
+) -> ArbSignal {
+    let parity = &group.parity_states[parity_idx];
+    let meta = parity.meta(group);
+
+    // BUY fills against asks, SELL against bids
+    let (side, reason, mode) = match strategy {
+        StrategyKind::ParityBuy => (OrderSide::Buy, "POLYMARKET_PARITY_BUY_YES_NO_ASK_LT_1", "BUY"),
+        _ => (OrderSide::Sell, "POLYMARKET_PARITY_SELL_YES_NO_BID_GT_1", "SELL"),
+    };
+
+    ArbSignal {
+        group_key: group.group_key.clone(),
+        event_slug: group.event_slug.clone(),
+        crypto: group.crypto.clone(),
+        strategy,
+
+        profit_abs: calc.profit_abs,
+        profit_bps: calc.profit_bps,
+        gross_profit_abs: calc.gross_profit_abs,
+        gross_profit_bps: calc.gross_profit_bps,
+        timestamp_ms: now_ms,
+
+        // YES leg, then the NO leg of the same market
+        legs: vec![
+            meta.leg(0, LegRole::Parent, side, yes, -1),
+            meta.leg(1, LegRole::ParentUpper, side, no, -1),
+        ],
+        child_index: if parity.is_parent { -1 } else { parity.market_idx as i32 },
+
+        // Set context — YES + NO always pays 1
+        total_cost: Some(calc.total_ask),
+        total_bid: Some(calc.total_bid),
+        payout: Some(1.0),
+        mode: Some(mode),
+
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::engine::RangeGroupInput;
+    use crate::engine::state::EngineState;
+    use crate::engine::test_support::{market, quote};
+    use std::sync::Arc;
+
+    /// One parent market → a single parity state.
+    fn make_parity_engine(fee_rate_bps: u32) -> EngineState {
+        let mut engine = EngineState::new(EngineConfig::default());
+        let mut above = market("above-2800", "yes", "no", Some(2800.0), None, "above");
+        above.fee_rate_bps = [fee_rate_bps; 2];
+        engine.update_market_structure(vec![RangeGroupInput {
+            group_key: "eth".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![],
+            parents: vec![above],
+        }]);
+        assert_eq!(engine.groups[0].parity_states.len(), 1);
+        engine
+    }
+
+    #[test]
+    fn test_parity_buy_profit_net_of_fees() {
+        // Ask(YES) + Ask(NO) = 0.45 + 0.50 = 0.95 → gross 0.05
+        // Fees at 100 bps: 0.01 × (0.45 + 0.50) = 0.0095 → net 0.0405
+        let mut engine = make_parity_engine(100);
+        quote(&mut engine, "yes", 0.43, 0.45);
+        quote(&mut engine, "no", 0.48, 0.50);
+
+        let r = calc_parity_edge(&engine.groups[0], 0, &engine.price_table, StrategyKind::ParityBuy).unwrap();
+        assert!((r.total_ask - 0.95).abs() < 1e-10);
+        assert!((r.gross_profit_abs - 0.05).abs() < 1e-10);
+        assert!((r.profit_abs - 0.0405).abs() < 1e-10);
+        assert!((r.profit_bps - 0.0405 / 0.95 * 10000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parity_sell_basis_is_one_set() {
+        // Bid(YES) + Bid(NO) = 0.55 + 0.50 = 1.05 → gross 0.05 on the 1.0 minted set
+        // Fees at 100 bps: 0.01 × ((1 - 0.55) + 0.50) = 0.0095 → net 0.0405
+        let mut engine = make_parity_engine(100);
+        quote(&mut engine, "yes", 0.55, 0.57);
+        quote(&mut engine, "no", 0.50, 0.52);
+
+        let r = calc_parity_edge(&engine.groups[0], 0, &engine.price_table, StrategyKind::ParitySell).unwrap();
+        assert!((r.total_bid - 1.05).abs() < 1e-10);
+        assert!((r.gross_profit_bps - 500.0).abs() < 1e-6);
+        assert!((r.profit_bps - 405.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parity_needs_both_quotes_and_net_threshold() {
+        let mut engine = make_parity_engine(100);
+        quote(&mut engine, "yes", 0.43, 0.45);
+        let group = &engine.groups[0];
+        assert!(calc_parity_edge(group, 0, &engine.price_table, StrategyKind::ParityBuy).is_none());
+        assert!(calc_parity_edge(group, 0, &engine.price_table, StrategyKind::ParitySell).is_none());
+
+        quote(&mut engine, "no", 0.48, 0.50);
+        let cfg = |min_profit_abs| EngineConfig {
+            min_profit_abs,
+            min_profit_bps: 0.0,
+            cooldown_ms: 0,
+            ..EngineConfig::default()
+        };
+        let group = Arc::make_mut(&mut engine.groups[0]);
+        // Gross 0.05 clears 0.045, net 0.0405 does not
+        assert!(evaluate_parity(group, 0, &engine.price_table, &cfg(0.045), 1, StrategyKind::ParityBuy).is_none());
+        let sig = evaluate_parity(group, 0, &engine.price_table, &cfg(0.04), 2, StrategyKind::ParityBuy).unwrap();
+        assert_eq!(sig.legs.len(), 2);
+        assert!(sig.legs.iter().all(|l| l.side == OrderSide::Buy));
+        assert_eq!(group.parity_states[0].last_emitted_buy_ms, 2);
+    }
+}
@@ -0,0 +1,784 @@
+//! Warm-restart persistence — versioned engine snapshots on disk.
+//!
+//! Captures market structure, neg-risk events, PriceTable quotes (with their
+//! original timestamps) and every structure's cooldown timestamps and emission
+//! latches, so a restarted process does not re-fire signals that were just
+//! executed. Restored quotes keep their timestamps, so the quote staleness guard
+//! keeps treating them as old until live updates arrive; quotes older than the
+//! restore age limit are dropped entirely.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::engine::{
+    bracket_key, ladder_key, monotone_key, MarketDescriptorInput, NegRiskEventInput,
+    NegRiskOutcomeInput, RangeGroupInput,
+};
+use crate::engine::state::{EmissionLatch, EngineState, GroupState};
+use crate::types::strategy::StrategyKind;
+
+/// Bump on any incompatible change to the snapshot layout.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+// =============================================================================
+// SNAPSHOT FORMAT
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSnapshot {
+    pub version: u32,
+    pub saved_at_ms: i64,
+    pub groups: Vec<GroupSnapshot>,
+    pub neg_risk_events: Vec<NegRiskEventSnapshot>,
+    pub prices: Vec<PriceSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupSnapshot {
+    pub group_key: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub children: Vec<MarketSnapshot>,
+    pub parents: Vec<MarketSnapshot>,
+    pub trios: Vec<TrioCooldownSnapshot>,
+    pub brackets: Vec<BracketCooldownSnapshot>,
+    pub ladder: Option<LadderCooldownSnapshot>,
+    pub monotone_pairs: Vec<MonotoneCooldownSnapshot>,
+    pub parity: Vec<ParityCooldownSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketSnapshot {
+    pub market_id: String,
+    pub slug: String,
+    pub clob_token_ids: Vec<String>,
+    pub bounds_lower: Option<f64>,
+    pub bounds_upper: Option<f64>,
+    pub kind: String,
+    pub neg_risk: bool,
+    /// Absent in snapshots taken before fee tracking — restored as fee-free.
+    #[serde(default)]
+    pub fee_rate_bps: [u32; 2],
+}
+
+/// An open emission latch. `last_emitted_bps` is `None` when the edge of the
+/// last emission is unknown (a latch that was itself restored).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatchSnapshot {
+    pub strategy: String,
+    pub last_emitted_bps: Option<f64>,
+}
+
+/// Cooldowns keyed by the trio's triangle tokens — stable across rebuilds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrioCooldownSnapshot {
+    pub lower_yes_token: String,
+    pub upper_no_token: String,
+    pub range_no_token: String,
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_complement_ms: i64,
+    pub last_emitted_unbundle_ms: i64,
+    pub last_emitted_bundle_ms: i64,
+    pub emission: Vec<LatchSnapshot>,
+}
+
+/// Cooldowns keyed by the market ids the bracket spans (parents, then children).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BracketCooldownSnapshot {
+    pub market_ids: Vec<String>,
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_complement_ms: i64,
+    pub emission: Vec<LatchSnapshot>,
+}
+
+/// Cooldowns keyed by the ladder's child market ids.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LadderCooldownSnapshot {
+    pub market_ids: Vec<String>,
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_sell_ms: i64,
+    pub emission: Vec<LatchSnapshot>,
+}
+
+/// Cooldowns keyed by the pair's (buy, sell) market ids.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonotoneCooldownSnapshot {
+    pub buy_market_id: String,
+    pub sell_market_id: String,
+    pub last_emitted_ms: i64,
+    pub emission: Vec<LatchSnapshot>,
+}
+
+/// Cooldowns keyed by the market id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParityCooldownSnapshot {
+    pub market_id: String,
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_sell_ms: i64,
+    pub emission: Vec<LatchSnapshot>,
+}
+
+/// A neg-risk event with its cooldowns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NegRiskEventSnapshot {
+    pub event_id: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub outcomes: Vec<NegRiskOutcomeSnapshot>,
+    pub last_emitted_yes_buy_ms: i64,
+    pub last_emitted_no_buy_ms: i64,
+    pub last_emitted_convert_ms: i64,
+    pub emission: Vec<LatchSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NegRiskOutcomeSnapshot {
+    pub market_id: String,
+    pub slug: String,
+    pub clob_token_ids: Vec<String>,
+    pub fee_rate_bps: [u32; 2],
+}
+
+/// One token quote. Bid/ask are `None` when the slot was never quoted (NaN).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSnapshot {
+    pub token_id: String,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub best_bid_size: f64,
+    pub best_ask_size: f64,
+    pub timestamp_ms: i64,
+}
+
+/// Outcome of a restore.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RestoreStats {
+    pub groups: usize,
+    pub trios: usize,
+    pub neg_risk_events: usize,
+    pub prices_restored: usize,
+    pub prices_dropped_stale: usize,
+    /// Structures (trios, brackets, ladders, pairs, parity checks, neg-risk
+    /// events) whose cooldowns and latches were restored.
+    pub cooldowns_restored: usize,
+}
+
+// =============================================================================
+// CAPTURE / RESTORE
+// =============================================================================
+
+/// Capture the engine state into a snapshot.
+pub fn capture(engine: &EngineState, now_ms: i64) -> EngineSnapshot {
+    let groups = engine.groups.iter().map(|g| group_snapshot(g)).collect();
+
+    let neg_risk_events = engine
+        .neg_risk_events
+        .iter()
+        .map(|e| NegRiskEventSnapshot {
+            event_id: e.event_id.clone(),
+            event_slug: e.event_slug.clone(),
+            crypto: e.crypto.clone(),
+            outcomes: e
+                .outcomes
+                .iter()
+                .map(|o| NegRiskOutcomeSnapshot {
+                    market_id: o.market_id.clone(),
+                    slug: o.slug.clone(),
+                    clob_token_ids: o.clob_token_ids.to_vec(),
+                    fee_rate_bps: o.fee_rate_bps,
+                })
+                .collect(),
+            last_emitted_yes_buy_ms: e.last_emitted_yes_buy_ms,
+            last_emitted_no_buy_ms: e.last_emitted_no_buy_ms,
+            last_emitted_convert_ms: e.last_emitted_convert_ms,
+            emission: latch_snapshots(&e.emission),
+        })
+        .collect();
+
+    let prices = engine
+        .price_table
+        .token_to_slot
+        .iter()
+        .filter_map(|(token, &slot)| {
+            let s = engine.price_table.get(slot);
+            if s.best_bid.is_nan() && s.best_ask.is_nan() {
+                return None;
+            }
+            Some(PriceSnapshot {
+                token_id: token.clone(),
+                best_bid: (!s.best_bid.is_nan()).then_some(s.best_bid),
+                best_ask: (!s.best_ask.is_nan()).then_some(s.best_ask),
+                best_bid_size: s.best_bid_size,
+                best_ask_size: s.best_ask_size,
+                timestamp_ms: s.timestamp_ms,
+            })
+        })
+        .collect();
+
+    EngineSnapshot {
+        version: SNAPSHOT_VERSION,
+        saved_at_ms: now_ms,
+        groups,
+        neg_risk_events,
+        prices,
+    }
+}
+
+fn group_snapshot(g: &GroupState) -> GroupSnapshot {
+    GroupSnapshot {
+        group_key: g.group_key.clone(),
+        event_slug: g.event_slug.clone(),
+        crypto: g.crypto.clone(),
+        children: g.child_metas.iter().map(|m| market_snapshot(m.into())).collect(),
+        parents: g.parent_metas.iter().map(|m| market_snapshot(m.into())).collect(),
+        trios: g
+            .trio_states
+            .iter()
+            .map(|t| TrioCooldownSnapshot {
+                lower_yes_token: t.lower_yes_token.clone(),
+                upper_no_token: t.upper_no_token.clone(),
+                range_no_token: t.range_no_token.clone(),
+                last_emitted_buy_ms: t.last_emitted_buy_ms,
+                last_emitted_complement_ms: t.last_emitted_complement_ms,
+                last_emitted_unbundle_ms: t.last_emitted_unbundle_ms,
+                last_emitted_bundle_ms: t.last_emitted_bundle_ms,
+                emission: latch_snapshots(&t.emission),
+            })
+            .collect(),
+        brackets: g
+            .bracket_states
+            .iter()
+            .map(|b| BracketCooldownSnapshot {
+                market_ids: bracket_key(g, b),
+                last_emitted_buy_ms: b.last_emitted_buy_ms,
+                last_emitted_complement_ms: b.last_emitted_complement_ms,
+                emission: latch_snapshots(&b.emission),
+            })
+            .collect(),
+        ladder: g.ladder.as_ref().zip(ladder_key(g)).map(|(l, market_ids)| LadderCooldownSnapshot {
+            market_ids,
+            last_emitted_buy_ms: l.last_emitted_buy_ms,
+            last_emitted_sell_ms: l.last_emitted_sell_ms,
+            emission: latch_snapshots(&l.emission),
+        }),
+        monotone_pairs: g
+            .monotone_pairs
+            .iter()
+            .map(|p| {
+                let (buy, sell) = monotone_key(g, p);
+                MonotoneCooldownSnapshot {
+                    buy_market_id: buy.to_string(),
+                    sell_market_id: sell.to_string(),
+                    last_emitted_ms: p.last_emitted_ms,
+                    emission: latch_snapshots(&p.emission),
+                }
+            })
+            .collect(),
+        parity: g
+            .parity_states
+            .iter()
+            .map(|p| ParityCooldownSnapshot {
+                market_id: p.meta(g).market_id.clone(),
+                last_emitted_buy_ms: p.last_emitted_buy_ms,
+                last_emitted_sell_ms: p.last_emitted_sell_ms,
+                emission: latch_snapshots(&p.emission),
+            })
+            .collect(),
+    }
+}
+
+/// Rebuild structure, neg-risk events, cooldowns, latches and quotes from a snapshot.
+///
+/// Replaces the current market structure and neg-risk events. Quotes older than
+/// `max_price_age_ms` (relative to `now_ms`) are dropped; `0` keeps every quote.
+pub fn restore(
+    engine: &mut EngineState,
+    snapshot: EngineSnapshot,
+    now_ms: i64,
+    max_price_age_ms: i64,
+) -> RestoreStats {
+    let mut stats = RestoreStats::default();
+
+    let mut inputs = Vec::with_capacity(snapshot.groups.len());
+    let mut cooldowns = HashMap::with_capacity(snapshot.groups.len());
+    for mut g in snapshot.groups {
+        inputs.push(RangeGroupInput {
+            group_key: g.group_key.clone(),
+            event_slug: g.event_slug.clone(),
+            crypto: g.crypto.clone(),
+            children: std::mem::take(&mut g.children).into_iter().map(market_input).collect(),
+            parents: std::mem::take(&mut g.parents).into_iter().map(market_input).collect(),
+        });
+        cooldowns.insert(g.group_key.clone(), g);
+    }
+
+    stats.trios = engine.update_market_structure(inputs).max(0) as usize;
+    stats.groups = engine.groups.len();
+
+    for group in &mut engine.groups {
+        if let Some(saved) = cooldowns.remove(&group.group_key) {
+            stats.cooldowns_restored += restore_group(Arc::make_mut(group), saved);
+        }
+    }
+
+    // Neg-risk events before quotes, so their tokens have slots to restore into
+    let mut event_cooldowns = HashMap::with_capacity(snapshot.neg_risk_events.len());
+    let mut events = Vec::with_capacity(snapshot.neg_risk_events.len());
+    for mut e in snapshot.neg_risk_events {
+        events.push(NegRiskEventInput {
+            event_id: e.event_id.clone(),
+            event_slug: e.event_slug.clone(),
+            crypto: e.crypto.clone(),
+            outcomes: std::mem::take(&mut e.outcomes)
+                .into_iter()
+                .map(|o| NegRiskOutcomeInput {
+                    market_id: o.market_id,
+                    slug: o.slug,
+                    clob_token_ids: o.clob_token_ids,
+                    fee_rate_bps: o.fee_rate_bps,
+                })
+                .collect(),
+        });
+        event_cooldowns.insert(e.event_id.clone(), e);
+    }
+    stats.neg_risk_events = engine.update_neg_risk_events(events).max(0) as usize;
+    for event in &mut engine.neg_risk_events {
+        if let Some(saved) = event_cooldowns.remove(&event.event_id) {
+            let event = Arc::make_mut(event);
+            event.last_emitted_yes_buy_ms = saved.last_emitted_yes_buy_ms;
+            event.last_emitted_no_buy_ms = saved.last_emitted_no_buy_ms;
+            event.last_emitted_convert_ms = saved.last_emitted_convert_ms;
+            restore_latches(&mut event.emission, &saved.emission);
+            stats.cooldowns_restored += 1;
+        }
+    }
+
+    // Quotes are written straight into the PriceTable, bypassing the dirty-check
+    // cache, so the first live update for every token is always processed.
+    for p in snapshot.prices {
+        let slot = match engine.price_table.token_to_slot.get(&p.token_id) {
+            Some(&s) => s,
+            None => continue,
+        };
+        if max_price_age_ms > 0 && now_ms - p.timestamp_ms > max_price_age_ms {
+            stats.prices_dropped_stale += 1;
+            continue;
+        }
+        engine.clear_dirty_cache(&p.token_id);
+        engine.price_table.update(
+            slot,
+            p.best_bid.unwrap_or(f64::NAN),
+            p.best_ask.unwrap_or(f64::NAN),
+            Some(p.best_bid_size),
+            Some(p.best_ask_size),
+            p.timestamp_ms,
+        );
+        stats.prices_restored += 1;
+    }
+    engine.resync_ladders();
+
+    stats
+}
+
+/// Apply one group's saved cooldowns and latches to the structures that still
+/// exist after the rebuild. Returns the number of structures restored.
+fn restore_group(group: &mut GroupState, saved: GroupSnapshot) -> usize {
+    let mut restored = 0;
+
+    let trios: HashMap<(String, String, String), TrioCooldownSnapshot> = saved
+        .trios
+        .into_iter()
+        .map(|t| ((t.lower_yes_token.clone(), t.upper_no_token.clone(), t.range_no_token.clone()), t))
+        .collect();
+    for trio in &mut group.trio_states {
+        let key = (
+            trio.lower_yes_token.clone(),
+            trio.upper_no_token.clone(),
+            trio.range_no_token.clone(),
+        );
+        if let Some(c) = trios.get(&key) {
+            trio.last_emitted_buy_ms = c.last_emitted_buy_ms;
+            trio.last_emitted_complement_ms = c.last_emitted_complement_ms;
+            trio.last_emitted_unbundle_ms = c.last_emitted_unbundle_ms;
+            trio.last_emitted_bundle_ms = c.last_emitted_bundle_ms;
+            restore_latches(&mut trio.emission, &c.emission);
+            restored += 1;
+        }
+    }
+
+    let brackets: HashMap<Vec<String>, BracketCooldownSnapshot> =
+        saved.brackets.into_iter().map(|b| (b.market_ids.clone(), b)).collect();
+    let keys: Vec<Vec<String>> = group.bracket_states.iter().map(|b| bracket_key(group, b)).collect();
+    for (bracket, key) in group.bracket_states.iter_mut().zip(keys) {
+        if let Some(c) = brackets.get(&key) {
+            bracket.last_emitted_buy_ms = c.last_emitted_buy_ms;
+            bracket.last_emitted_complement_ms = c.last_emitted_complement_ms;
+            restore_latches(&mut bracket.emission, &c.emission);
+            restored += 1;
+        }
+    }
+
+    if let Some(c) = saved.ladder.filter(|c| ladder_key(group).as_ref() == Some(&c.market_ids)) {
+        let ladder = group.ladder.as_mut().expect("keyed ladder exists");
+        ladder.last_emitted_buy_ms = c.last_emitted_buy_ms;
+        ladder.last_emitted_sell_ms = c.last_emitted_sell_ms;
+        restore_latches(&mut ladder.emission, &c.emission);
+        restored += 1;
+    }
+
+    let pairs: HashMap<(String, String), MonotoneCooldownSnapshot> = saved
+        .monotone_pairs
+        .into_iter()
+        .map(|p| ((p.buy_market_id.clone(), p.sell_market_id.clone()), p))
+        .collect();
+    let keys: Vec<(String, String)> = group
+        .monotone_pairs
+        .iter()
+        .map(|p| {
+            let (buy, sell) = monotone_key(group, p);
+            (buy.to_string(), sell.to_string())
+        })
+        .collect();
+    for (pair, key) in group.monotone_pairs.iter_mut().zip(keys) {
+        if let Some(c) = pairs.get(&key) {
+            pair.last_emitted_ms = c.last_emitted_ms;
+            restore_latches(&mut pair.emission, &c.emission);
+            restored += 1;
+        }
+    }
+
+    let parity: HashMap<String, ParityCooldownSnapshot> =
+        saved.parity.into_iter().map(|p| (p.market_id.clone(), p)).collect();
+    let keys: Vec<String> = group.parity_states.iter().map(|p| p.meta(group).market_id.clone()).collect();
+    for (state, key) in group.parity_states.iter_mut().zip(keys) {
+        if let Some(c) = parity.get(&key) {
+            state.last_emitted_buy_ms = c.last_emitted_buy_ms;
+            state.last_emitted_sell_ms = c.last_emitted_sell_ms;
+            restore_latches(&mut state.emission, &c.emission);
+            restored += 1;
+        }
+    }
+
+    restored
+}
+
+/// Open latches of one structure, by strategy name.
+fn latch_snapshots(emission: &[EmissionLatch; StrategyKind::COUNT]) -> Vec<LatchSnapshot> {
+    emission
+        .iter()
+        .zip(StrategyKind::ALL)
+        .filter(|(latch, _)| latch.open)
+        .map(|(latch, strategy)| LatchSnapshot {
+            strategy: strategy.as_str().to_string(),
+            last_emitted_bps: latch.last_emitted_bps.is_finite().then_some(latch.last_emitted_bps),
+        })
+        .collect()
+}
+
+/// Reopen saved latches; unknown strategy names are ignored.
+fn restore_latches(emission: &mut [EmissionLatch; StrategyKind::COUNT], saved: &[LatchSnapshot]) {
+    for l in saved {
+        let Some(strategy) = StrategyKind::parse(&l.strategy) else {
+            continue;
+        };
+        let latch = &mut emission[strategy.index()];
+        match l.last_emitted_bps {
+            Some(bps) => latch.emitted(bps),
+            None => *latch = EmissionLatch::restored(),
+        }
+    }
+}
+
+// =============================================================================
+// FILE I/O
+// =============================================================================
+
+/// Write a snapshot atomically (temp file + rename).
+pub fn save_to_file(path: &Path, snapshot: &EngineSnapshot) -> Result<(), String> {
+    let json = serde_json::to_vec(snapshot).map_err(|e| format!("Serialize snapshot: {}", e))?;
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("Write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Rename to {}: {}", path.display(), e))
+}
+
+/// Read a snapshot, rejecting unknown versions.
+pub fn load_from_file(path: &Path) -> Result<EngineSnapshot, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Read {}: {}", path.display(), e))?;
+    let snapshot: EngineSnapshot =
+        serde_json::from_slice(&bytes).map_err(|e| format!("Parse snapshot: {}", e))?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(format!(
+            "Unsupported snapshot version {} (expected {})",
+            snapshot.version, SNAPSHOT_VERSION
+        ));
+    }
+    Ok(snapshot)
+}
+
+fn market_snapshot(m: MarketDescriptorInput) -> MarketSnapshot {
+    MarketSnapshot {
+        market_id: m.market_id,
+        slug: m.slug,
+        clob_token_ids: m.clob_token_ids,
+        bounds_lower: m.bounds_lower,
+        bounds_upper: m.bounds_upper,
+        kind: m.kind,
+        neg_risk: m.neg_risk,
+        fee_rate_bps: m.fee_rate_bps,
+    }
+}
+
+fn market_input(m: MarketSnapshot) -> MarketDescriptorInput {
+    MarketDescriptorInput {
+        market_id: m.market_id,
+        slug: m.slug,
+        clob_token_ids: m.clob_token_ids,
+        bounds_lower: m.bounds_lower,
+        bounds_upper: m.bounds_upper,
+        kind: m.kind,
+        neg_risk: m.neg_risk,
+        fee_rate_bps: m.fee_rate_bps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::state::{EngineClock, EngineConfig};
+    use crate::engine::test_support::{market, neg_risk_event};
+
+    fn make_engine(now_ms: i64) -> EngineState {
+        let mut engine = EngineState::new(EngineConfig {
+            min_profit_bps: 10.0,
+            min_profit_abs: 0.001,
+            cooldown_ms: 60_000,
+            clock: EngineClock::Manual(now_ms),
+            ..EngineConfig::default()
+        });
+        engine.update_market_structure(vec![RangeGroupInput {
+            group_key: "eth".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![market("range", "ry", "rn", Some(2800.0), Some(2900.0), "range")],
+            parents: vec![
+                market("above-2800", "ly", "ln", Some(2800.0), None, "above"),
+                market("above-2900", "uy", "un", Some(2900.0), None, "above"),
+            ],
+        }]);
+        engine
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("engine-snapshot-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_blocks_refire() {
+        let mut engine = make_engine(1_000);
+        engine.handle_top_of_book("ly", 0.58, 0.60, Some(100.0), Some(100.0), 900);
+        engine.handle_top_of_book("un", 0.48, 0.50, Some(100.0), Some(100.0), 950);
+        let signals = engine.handle_top_of_book("rn", 0.78, 0.80, Some(100.0), Some(100.0), 990);
+        assert_eq!(signals.len(), 1);
+
+        let path = temp_path("round-trip");
+        save_to_file(&path, &capture(&engine, 1_000)).unwrap();
+        let snapshot = load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        // Fresh process: restore, then the same quotes arrive again within the cooldown
+        let mut restarted = EngineState::new(engine.config);
+        let stats = restore(&mut restarted, snapshot, 1_500, 0);
+        assert_eq!(stats.groups, 1);
+        assert_eq!(stats.trios, 1);
+        assert_eq!(stats.prices_restored, 3);
+        assert_eq!(stats.cooldowns_restored, 5); // trio + monotone pair + 3 parity checks
+
+        let slot = restarted.price_table.token_to_slot["ly"];
+        assert_eq!(restarted.price_table.get(slot).timestamp_ms, 900);
+        let signals =
+            restarted.handle_top_of_book("rn", 0.78, 0.79, Some(100.0), Some(100.0), 1_400);
+        assert!(signals.is_empty());
+        assert_eq!(
+            restarted.groups[0].trio_states[0].last_emitted_buy_ms,
+            engine.groups[0].trio_states[0].last_emitted_buy_ms
+        );
+        let latch = restarted.groups[0].trio_states[0].emission[StrategyKind::Triangle.index()];
+        assert!(latch.open);
+        assert_eq!(
+            latch.last_emitted_bps,
+            engine.groups[0].trio_states[0].emission[StrategyKind::Triangle.index()].last_emitted_bps
+        );
+    }
+
+    /// Group with a two-child bracket (no middle parent), a complete ladder,
+    /// one monotonicity pair and a parity check per market.
+    fn make_structured_engine() -> EngineState {
+        let mut engine = EngineState::new(EngineConfig {
+            max_bracket_children: 2,
+            clock: EngineClock::Manual(1_000),
+            ..EngineConfig::default()
+        });
+        engine.update_market_structure(vec![RangeGroupInput {
+            group_key: "eth".to_string(),
+            event_slug: "eth-price".to_string(),
+            crypto: "ETH".to_string(),
+            children: vec![
+                market("below-2800", "by", "bn", None, Some(2800.0), "below"),
+                market("r2800", "r1y", "r1n", Some(2800.0), Some(2900.0), "range"),
+                market("r2900", "r2y", "r2n", Some(2900.0), Some(3000.0), "range"),
+                market("above-3000-c", "ay", "an", Some(3000.0), None, "above"),
+            ],
+            parents: vec![
+                market("above-2800", "ly", "ln", Some(2800.0), None, "above"),
+                market("above-3000", "uy", "un", Some(3000.0), None, "above"),
+            ],
+        }]);
+        let group = &engine.groups[0];
+        assert!(group.trio_states.is_empty());
+        assert_eq!(group.bracket_states.len(), 1);
+        assert!(group.ladder.is_some());
+        assert_eq!(group.monotone_pairs.len(), 1);
+        assert_eq!(group.parity_states.len(), 6);
+        engine
+    }
+
+    /// Save, reload and restore into a fresh engine with the same config.
+    fn round_trip(engine: &EngineState) -> (EngineState, RestoreStats) {
+        let json = serde_json::to_vec(&capture(engine, 1_000)).unwrap();
+        let snapshot: EngineSnapshot = serde_json::from_slice(&json).unwrap();
+        let mut restarted = EngineState::new(engine.config);
+        let stats = restore(&mut restarted, snapshot, 1_000, 0);
+        (restarted, stats)
+    }
+
+    fn emitted(ms: i64) -> EmissionLatch {
+        let mut latch = EmissionLatch::default();
+        latch.emitted(ms as f64 / 10.0);
+        latch
+    }
+
+    fn assert_latch(latch: EmissionLatch, expected: EmissionLatch) {
+        assert_eq!((latch.open, latch.last_emitted_bps), (expected.open, expected.last_emitted_bps));
+    }
+
+    #[test]
+    fn test_restore_bracket_cooldowns() {
+        let mut engine = make_structured_engine();
+        let bracket = &mut Arc::make_mut(&mut engine.groups[0]).bracket_states[0];
+        bracket.last_emitted_buy_ms = 900;
+        bracket.last_emitted_complement_ms = 950;
+        bracket.emission[StrategyKind::Triangle.index()] = emitted(900);
+        bracket.emission[StrategyKind::Complement.index()] = EmissionLatch::restored();
+
+        let (restarted, _) = round_trip(&engine);
+        let bracket = &restarted.groups[0].bracket_states[0];
+        assert_eq!((bracket.last_emitted_buy_ms, bracket.last_emitted_complement_ms), (900, 950));
+        assert_latch(bracket.emission[StrategyKind::Triangle.index()], emitted(900));
+        assert!(bracket.emission[StrategyKind::Complement.index()].open);
+        assert!(bracket.emission[StrategyKind::Complement.index()].last_emitted_bps.is_infinite());
+    }
+
+    #[test]
+    fn test_restore_ladder_cooldowns() {
+        let mut engine = make_structured_engine();
+        let ladder = Arc::make_mut(&mut engine.groups[0]).ladder.as_mut().unwrap();
+        ladder.last_emitted_buy_ms = 900;
+        ladder.last_emitted_sell_ms = 950;
+        ladder.emission[StrategyKind::LadderSell.index()] = emitted(950);
+
+        let (restarted, _) = round_trip(&engine);
+        let ladder = restarted.groups[0].ladder.as_ref().unwrap();
+        assert_eq!((ladder.last_emitted_buy_ms, ladder.last_emitted_sell_ms), (900, 950));
+        assert!(!ladder.emission[StrategyKind::LadderBuy.index()].open);
+        assert_latch(ladder.emission[StrategyKind::LadderSell.index()], emitted(950));
+    }
+
+    #[test]
+    fn test_restore_monotone_cooldowns() {
+        let mut engine = make_structured_engine();
+        let pair = &mut Arc::make_mut(&mut engine.groups[0]).monotone_pairs[0];
+        pair.last_emitted_ms = 900;
+        pair.emission[StrategyKind::Monotone.index()] = emitted(900);
+
+        let (restarted, _) = round_trip(&engine);
+        let pair = &restarted.groups[0].monotone_pairs[0];
+        assert_eq!(pair.last_emitted_ms, 900);
+        assert_latch(pair.emission[StrategyKind::Monotone.index()], emitted(900));
+    }
+
+    #[test]
+    fn test_restore_parity_cooldowns() {
+        let mut engine = make_structured_engine();
+        let parity = &mut Arc::make_mut(&mut engine.groups[0]).parity_states[2];
+        parity.last_emitted_buy_ms = 900;
+        parity.last_emitted_sell_ms = 950;
+        parity.emission[StrategyKind::ParityBuy.index()] = emitted(900);
+
+        let (restarted, stats) = round_trip(&engine);
+        // 1 bracket + 1 ladder + 1 pair + 6 parity checks
+        assert_eq!(stats.cooldowns_restored, 9);
+        let parity = &restarted.groups[0].parity_states[2];
+        assert_eq!(parity.meta(&restarted.groups[0]).market_id, "r2900");
+        assert_eq!((parity.last_emitted_buy_ms, parity.last_emitted_sell_ms), (900, 950));
+        assert_latch(parity.emission[StrategyKind::ParityBuy.index()], emitted(900));
+    }
+
+    #[test]
+    fn test_restore_neg_risk_events() {
+        let mut engine = make_engine(1_000);
+        let mut input = neg_risk_event(&["cut", "hold", "hike"]);
+        input.event_id = "0xfed".to_string();
+        for outcome in &mut input.outcomes {
+            outcome.fee_rate_bps = [0, 20];
+        }
+        engine.update_neg_risk_events(vec![input]);
+        engine.handle_top_of_book("hold_no", 0.60, 0.62, Some(10.0), Some(10.0), 900);
+        let event = Arc::make_mut(&mut engine.neg_risk_events[0]);
+        event.last_emitted_yes_buy_ms = 900;
+        event.last_emitted_convert_ms = 950;
+        event.emission[StrategyKind::NegRiskYesBuy.index()] = emitted(900);
+
+        let (restarted, stats) = round_trip(&engine);
+        assert_eq!(stats.neg_risk_events, 1);
+        assert_eq!(stats.prices_restored, 1);
+        let event = &restarted.neg_risk_events[0];
+        assert_eq!(event.event_id, "0xfed");
+        assert_eq!(event.outcomes.len(), 3);
+        assert_eq!(event.outcomes[1].fee_rate_bps, [0, 20]);
+        assert_eq!(
+            (event.last_emitted_yes_buy_ms, event.last_emitted_no_buy_ms, event.last_emitted_convert_ms),
+            (900, 0, 950)
+        );
+        assert_latch(event.emission[StrategyKind::NegRiskYesBuy.index()], emitted(900));
+        assert_eq!(restarted.price_table.get(event.outcomes[1].no_slot).best_ask, 0.62);
+    }
+
+    #[test]
+    fn test_restore_drops_stale_prices() {
+        let mut engine = make_engine(10_000);
+        engine.handle_top_of_book("ly", 0.58, 0.60, None, None, 1_000);
+        engine.handle_top_of_book("un", 0.48, 0.50, None, None, 9_500);
+        let snapshot = capture(&engine, 10_000);
+
+        let mut restarted = EngineState::new(engine.config);
+        let stats = restore(&mut restarted, snapshot, 10_000, 5_000);
+        assert_eq!(stats.prices_restored, 1);
+        assert_eq!(stats.prices_dropped_stale, 1);
+        let slot = restarted.price_table.token_to_slot["ly"];
+        assert!(restarted.price_table.get(slot).best_ask.is_nan());
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_version() {
+        let path = temp_path("version");
+        let mut snapshot = capture(&make_engine(0), 0);
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        std::fs::write(&path, serde_json::to_vec(&snapshot).unwrap()).unwrap();
+        let err = load_from_file(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert!(err.contains("Unsupported snapshot version"));
+    }
+}
@@ -2,19 +2,24 @@
 //!
 //! Reads from PriceTable: parent_lower YES + parent_upper YES + range YES.
 //! NOTE: These are YES token slots — different from Trio which uses NO for upper/range!
+//! Profit is net of each leg's taker fee.
 
-use std::time::{SystemTime, UNIX_EPOCH};
-
-use crate::engine::state::{EngineConfig, GroupState, PriceTable};
-use crate::types::signal::ArbSignal;
+use crate::engine::state::{
+    taker_fee, EngineConfig, GroupState, OpportunitySite, PriceSlot, PriceTable,
+};
+use crate::types::order::OrderSide;
+use crate::types::signal::{ArbSignal, LegRole};
+use crate::types::strategy::StrategyKind;
 
 /// Evaluate all trios affected by a token update for range arbitrage.
-/// Called after updating a token that is in `trio_lookup_by_asset`.
+/// Called after updating the YES token of a range child or parent of these trios.
+/// Each direction runs only if enabled in `cfg`.
 pub fn evaluate_trios_for_range_arbitrage(
     group: &mut GroupState,
     trio_indices: &[u16],
     pt: &PriceTable,
     cfg: &EngineConfig,
+    now_ms: i64,
 ) -> Vec<ArbSignal> {
     let mut signals = Vec::new();
 
@@ -25,13 +30,17 @@ pub fn evaluate_trios_for_range_arbitrage(
         }
 
         // Unbundling: Sell Parent Lower YES, Buy Range YES + Parent Upper YES
-        if let Some(sig) = evaluate_unbundling(group, trio_idx, pt, cfg) {
-            signals.push(sig);
+        if cfg.is_enabled(StrategyKind::Unbundle) {
+            if let Some(sig) = evaluate_unbundling(group, trio_idx, pt, cfg, now_ms) {
+                signals.push(sig);
+            }
         }
 
         // Bundling: Buy Parent Lower YES, Sell Range YES + Parent Upper YES
-        if let Some(sig) = evaluate_bundling(group, trio_idx, pt, cfg) {
-            signals.push(sig);
+        if cfg.is_enabled(StrategyKind::Bundle) {
+            if let Some(sig) = evaluate_bundling(group, trio_idx, pt, cfg, now_ms) {
+                signals.push(sig);
+            }
         }
     }
 
@@ -40,230 +49,239 @@ pub fn evaluate_trios_for_range_arbitrage(
 
 /// Unbundling: Sell Parent Lower YES, Buy Range YES + Parent Upper YES
 ///
-/// Profit = Bid(ParentLower YES) - (Ask(Range YES) + Ask(ParentUpper YES))
+/// Profit = Bid(ParentLower YES) - (Ask(Range YES) + Ask(ParentUpper YES)) - fees
 /// Strategy: SELL_PARENT_BUY_CHILDREN
 fn evaluate_unbundling(
     group: &mut GroupState,
     trio_idx: usize,
     pt: &PriceTable,
     cfg: &EngineConfig,
+    now_ms: i64,
 ) -> Option<ArbSignal> {
     let trio = &group.trio_states[trio_idx];
 
     // Read YES slots for all 3 markets
-    let parent_lower = pt.get(group.parent_metas[trio.parent_lower_idx as usize].yes_slot);
-    let parent_upper = pt.get(group.parent_metas[trio.parent_upper_idx as usize].yes_slot);
-    let range_child = pt.get(group.child_metas[trio.range_idx as usize].yes_slot);
+    let parent_lower_meta = &group.parent_metas[trio.parent_lower_idx as usize];
+    let parent_upper_meta = &group.parent_metas[trio.parent_upper_idx as usize];
+    let range_child_meta = &group.child_metas[trio.range_idx as usize];
+    let parent_lower = pt.get(parent_lower_meta.yes_slot);
+    let parent_upper = pt.get(parent_upper_meta.yes_slot);
+    let range_child = pt.get(range_child_meta.yes_slot);
 
     if parent_lower.best_bid.is_nan()
         || range_child.best_ask.is_nan()
         || parent_upper.best_ask.is_nan()
     {
+        let site = OpportunitySite::Trio(trio_idx);
+        group.track_opportunity(site, StrategyKind::Unbundle, false, f64::NAN, f64::NAN, now_ms);
         return None;
     }
 
     let cost = range_child.best_ask + parent_upper.best_ask;
-    let profit_abs = parent_lower.best_bid - cost;
-    let profit_bps = if cost > 0.0 {
-        (profit_abs / cost) * 10000.0
+    let fees = taker_fee(parent_lower.best_bid, parent_lower_meta.fee_rate_bps[0])
+        + taker_fee(range_child.best_ask, range_child_meta.fee_rate_bps[0])
+        + taker_fee(parent_upper.best_ask, parent_upper_meta.fee_rate_bps[0]);
+    let gross_profit_abs = parent_lower.best_bid - cost;
+    let profit_abs = gross_profit_abs - fees;
+    let (profit_bps, gross_profit_bps) = if cost > 0.0 {
+        ((profit_abs / cost) * 10000.0, (gross_profit_abs / cost) * 10000.0)
     } else {
-        0.0
+        (0.0, 0.0)
     };
 
-    if profit_abs < cfg.min_profit_abs || profit_bps < cfg.min_profit_bps {
+    group.trio_states[trio_idx].edge_stats[StrategyKind::Unbundle.index()]
+        .record(profit_abs, profit_bps, now_ms);
+    let above = profit_abs >= cfg.min_profit_abs && profit_bps >= cfg.min_profit_bps;
+    group.track_opportunity(OpportunitySite::Trio(trio_idx), StrategyKind::Unbundle, above, profit_abs, profit_bps, now_ms);
+    if !above {
+        group.trio_states[trio_idx].emission[StrategyKind::Unbundle.index()].close();
         return None;
     }
 
-    // Cooldown check
-    let now_ms = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64;
-
-    if group.trio_states[trio_idx].last_emitted_unbundle_ms > 0
-        && now_ms - group.trio_states[trio_idx].last_emitted_unbundle_ms < cfg.cooldown_ms
-    {
+    // Staleness check
+    if !cfg.quotes_fresh(StrategyKind::Unbundle, &[parent_lower, parent_upper, range_child], now_ms) {
+        group.trio_states[trio_idx].stale_skip_count += 1;
         return None;
     }
 
-    // Build signal using YES token data
+    // Emission check (cooldown / hysteresis)
     let trio = &group.trio_states[trio_idx];
-    let parent_lower_meta = &group.parent_metas[trio.parent_lower_idx as usize];
-    let parent_upper_meta = &group.parent_metas[trio.parent_upper_idx as usize];
-    let range_child_meta = &group.child_metas[trio.range_idx as usize];
+    if !cfg.should_emit(&trio.emission[StrategyKind::Unbundle.index()], trio.last_emitted_unbundle_ms, profit_bps, now_ms) {
+        return None;
+    }
 
+    // Build signal using YES token data
     let signal = build_range_signal(
         group,
-        "SELL_PARENT_BUY_CHILDREN",
+        trio_idx,
+        StrategyKind::Unbundle,
         profit_abs,
         profit_bps,
+        gross_profit_abs,
+        gross_profit_bps,
         now_ms,
-        parent_lower_meta,
-        parent_upper_meta,
-        range_child_meta,
         parent_lower,
         parent_upper,
         range_child,
-        trio.range_idx as i32,
     );
 
     // Mark cooldown
     group.trio_states[trio_idx].last_emitted_unbundle_ms = now_ms;
+    group.trio_states[trio_idx].emission[StrategyKind::Unbundle.index()].emitted(profit_bps);
 
     Some(signal)
 }
 
 /// Bundling: Buy Parent Lower YES, Sell Range YES + Parent Upper YES
 ///
-/// Profit = (Bid(Range YES) + Bid(ParentUpper YES)) - Ask(ParentLower YES)
+/// Profit = (Bid(Range YES) + Bid(ParentUpper YES)) - Ask(ParentLower YES) - fees
 /// Strategy: BUY_PARENT_SELL_CHILDREN
 fn evaluate_bundling(
     group: &mut GroupState,
     trio_idx: usize,
     pt: &PriceTable,
     cfg: &EngineConfig,
+    now_ms: i64,
 ) -> Option<ArbSignal> {
     let trio = &group.trio_states[trio_idx];
 
     // Read YES slots for all 3 markets
-    let parent_lower = pt.get(group.parent_metas[trio.parent_lower_idx as usize].yes_slot);
-    let parent_upper = pt.get(group.parent_metas[trio.parent_upper_idx as usize].yes_slot);
-    let range_child = pt.get(group.child_metas[trio.range_idx as usize].yes_slot);
+    let parent_lower_meta = &group.parent_metas[trio.parent_lower_idx as usize];
+    let parent_upper_meta = &group.parent_metas[trio.parent_upper_idx as usize];
+    let range_child_meta = &group.child_metas[trio.range_idx as usize];
+    let parent_lower = pt.get(parent_lower_meta.yes_slot);
+    let parent_upper = pt.get(parent_upper_meta.yes_slot);
+    let range_child = pt.get(range_child_meta.yes_slot);
 
     if parent_lower.best_ask.is_nan()
         || range_child.best_bid.is_nan()
         || parent_upper.best_bid.is_nan()
     {
+        let site = OpportunitySite::Trio(trio_idx);
+        group.track_opportunity(site, StrategyKind::Bundle, false, f64::NAN, f64::NAN, now_ms);
         return None;
     }
 
     let revenue = range_child.best_bid + parent_upper.best_bid;
-    let profit_abs = revenue - parent_lower.best_ask;
-    let profit_bps = if parent_lower.best_ask > 0.0 {
-        (profit_abs / parent_lower.best_ask) * 10000.0
+    let fees = taker_fee(parent_lower.best_ask, parent_lower_meta.fee_rate_bps[0])
+        + taker_fee(range_child.best_bid, range_child_meta.fee_rate_bps[0])
+        + taker_fee(parent_upper.best_bid, parent_upper_meta.fee_rate_bps[0]);
+    let gross_profit_abs = revenue - parent_lower.best_ask;
+    let profit_abs = gross_profit_abs - fees;
+    let (profit_bps, gross_profit_bps) = if parent_lower.best_ask > 0.0 {
+        (
+            (profit_abs / parent_lower.best_ask) * 10000.0,
+            (gross_profit_abs / parent_lower.best_ask) * 10000.0,
+        )
     } else {
-        0.0
+        (0.0, 0.0)
     };
 
-    if profit_abs < cfg.min_profit_abs || profit_bps < cfg.min_profit_bps {
+    group.trio_states[trio_idx].edge_stats[StrategyKind::Bundle.index()]
+        .record(profit_abs, profit_bps, now_ms);
+    let above = profit_abs >= cfg.min_profit_abs && profit_bps >= cfg.min_profit_bps;
+    group.track_opportunity(OpportunitySite::Trio(trio_idx), StrategyKind::Bundle, above, profit_abs, profit_bps, now_ms);
+    if !above {
+        group.trio_states[trio_idx].emission[StrategyKind::Bundle.index()].close();
         return None;
     }
 
-    // Cooldown check
-    let now_ms = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64;
-
-    if group.trio_states[trio_idx].last_emitted_bundle_ms > 0
-        && now_ms - group.trio_states[trio_idx].last_emitted_bundle_ms < cfg.cooldown_ms
-    {
+    // Staleness check
+    if !cfg.quotes_fresh(StrategyKind::Bundle, &[parent_lower, parent_upper, range_child], now_ms) {
+        group.trio_states[trio_idx].stale_skip_count += 1;
         return None;
     }
 
+    // Emission check (cooldown / hysteresis)
     let trio = &group.trio_states[trio_idx];
-    let parent_lower_meta = &group.parent_metas[trio.parent_lower_idx as usize];
-    let parent_upper_meta = &group.parent_metas[trio.parent_upper_idx as usize];
-    let range_child_meta = &group.child_metas[trio.range_idx as usize];
+    if !cfg.should_emit(&trio.emission[StrategyKind::Bundle.index()], trio.last_emitted_bundle_ms, profit_bps, now_ms) {
+        return None;
+    }
 
     let signal = build_range_signal(
         group,
-        "BUY_PARENT_SELL_CHILDREN",
+        trio_idx,
+        StrategyKind::Bundle,
         profit_abs,
         profit_bps,
+        gross_profit_abs,
+        gross_profit_bps,
         now_ms,
-        parent_lower_meta,
-        parent_upper_meta,
-        range_child_meta,
         parent_lower,
         parent_upper,
         range_child,
-        trio.range_idx as i32,
     );
 
     // Mark cooldown
     group.trio_states[trio_idx].last_emitted_bundle_ms = now_ms;
+    group.trio_states[trio_idx].emission[StrategyKind::Bundle.index()].emitted(profit_bps);
 
     Some(signal)
 }
 
 /// Build an ArbSignal for range arbitrage (unbundling or bundling).
-/// All prices come from YES token slots.
+/// All legs trade YES tokens.
 #[allow(clippy::too_many_arguments)]
 fn build_range_signal(
     group: &GroupState,
-    strategy: &str,
+    trio_idx: usize,
+    strategy: StrategyKind,
     profit_abs: f64,
     profit_bps: f64,
+    gross_profit_abs: f64,
+    gross_profit_bps: f64,
     now_ms: i64,
-    parent_lower_meta: &crate::engine::state::MarketMeta,
-    parent_upper_meta: &crate::engine::state::MarketMeta,
-    range_child_meta: &crate::engine::state::MarketMeta,
-    parent_lower: &crate::engine::state::PriceSlot,
-    parent_upper: &crate::engine::state::PriceSlot,
-    range_child: &crate::engine::state::PriceSlot,
-    range_index: i32,
+    parent_lower: &PriceSlot,
+    parent_upper: &PriceSlot,
+    range_child: &PriceSlot,
 ) -> ArbSignal {
+    let trio = &group.trio_states[trio_idx];
+    let parent_lower_meta = &group.parent_metas[trio.parent_lower_idx as usize];
+    let parent_upper_meta = &group.parent_metas[trio.parent_upper_idx as usize];
+    let range_child_meta = &group.child_metas[trio.range_idx as usize];
+    let range_index = trio.range_idx as i32;
+
+    // Unbundle sells the parent lower and buys the rest; Bundle the reverse
+    let (parent_side, children_side) = match strategy {
+        StrategyKind::Unbundle => (OrderSide::Sell, OrderSide::Buy),
+        _ => (OrderSide::Buy, OrderSide::Sell),
+    };
+
     ArbSignal {
         group_key: group.group_key.clone(),
         event_slug: group.event_slug.clone(),
         crypto: group.crypto.clone(),
-        strategy: strategy.to_string(),
+        strategy,
 
         profit_abs,
         profit_bps,
+        gross_profit_abs,
+        gross_profit_bps,
         timestamp_ms: now_ms,
 
-        // Parent Lower — YES token
-        parent_asset_id: parent_lower_meta.clob_token_ids[0].clone(),
-        parent_market_slug: parent_lower_meta.slug.clone(),
-        parent_best_bid: Some(parent_lower.best_bid),
-        parent_best_ask: Some(parent_lower.best_ask),
-        parent_best_bid_size: Some(parent_lower.best_bid_size),
-        parent_best_ask_size: Some(parent_lower.best_ask_size),
-        parent_neg_risk: parent_lower_meta.neg_risk,
-
-        // Parent Upper — YES token (NOT no_slot!)
-        parent_upper_asset_id: parent_upper_meta.clob_token_ids[0].clone(),
-        parent_upper_market_slug: parent_upper_meta.slug.clone(),
-        parent_upper_best_bid: Some(parent_upper.best_bid),
-        parent_upper_best_ask: Some(parent_upper.best_ask),
-        parent_upper_best_bid_size: Some(parent_upper.best_bid_size),
-        parent_upper_best_ask_size: Some(parent_upper.best_ask_size),
-        parent_upper_neg_risk: parent_upper_meta.neg_risk,
-
-        // Range Child — YES token
-        child_asset_id: range_child_meta.clob_token_ids[0].clone(),
-        child_market_slug: range_child_meta.slug.clone(),
-        child_best_bid: Some(range_child.best_bid),
-        child_best_ask: Some(range_child.best_ask),
-        child_best_bid_size: Some(range_child.best_bid_size),
-        child_best_ask_size: Some(range_child.best_ask_size),
-        child_neg_risk: range_child_meta.neg_risk,
+        // Parent upper and range child both trade YES (NOT no_slot!)
+        legs: vec![
+            parent_lower_meta.leg(0, LegRole::Parent, parent_side, parent_lower, -1),
+            parent_upper_meta.leg(0, LegRole::ParentUpper, children_side, parent_upper, -1),
+            range_child_meta.leg(0, LegRole::Child, children_side, range_child, range_index),
+        ],
         child_index: range_index,
 
-        // Aggregates
-        children_sum_ask: if range_child.best_ask.is_nan() { 0.0 } else { range_child.best_ask },
-        children_sum_bid: if range_child.best_bid.is_nan() { 0.0 } else { range_child.best_bid },
-        parent_best_bid_flat: Some(parent_lower.best_bid),
-        parent_best_ask_flat: Some(parent_lower.best_ask),
-        parent_upper_best_bid_flat: Some(parent_upper.best_bid),
-        parent_upper_best_ask_flat: Some(parent_upper.best_ask),
-
         // No triangle context for range arb
-        triangle_total_cost: None,
-        triangle_total_bid: None,
-        triangle_payout: None,
-        triangle_mode: None,
+        total_cost: None,
+        total_bid: None,
+        payout: None,
+        mode: None,
 
-        reason: String::new(),
+        reason: "",
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::state::{MarketKind, MarketMeta, PriceTable, TrioState};
+    use crate::types::signal::FlatArbSignal;
+    use crate::engine::state::{EdgeStats, EmissionLatch, OpportunityTracker, MarketKind, MarketMeta, PriceTable, TrioState};
 
     fn make_yes_price_table(
         pl: (f64, f64),
@@ -272,18 +290,18 @@ mod tests {
     ) -> (PriceTable, MarketMeta, MarketMeta, MarketMeta) {
         let mut pt = PriceTable::new();
         // Parent lower: slot 0 = YES
-        let pl_yes = pt.alloc_slot("pl_yes");
-        let pl_no = pt.alloc_slot("pl_no");
+        let pl_yes = pt.alloc_slot("pl_yes", 0);
+        let pl_no = pt.alloc_slot("pl_no", 1);
         pt.update(pl_yes, pl.0, pl.1, Some(100.0), Some(100.0), 1);
 
         // Parent upper: slot 2 = YES
-        let pu_yes = pt.alloc_slot("pu_yes");
-        let pu_no = pt.alloc_slot("pu_no");
+        let pu_yes = pt.alloc_slot("pu_yes", 2);
+        let pu_no = pt.alloc_slot("pu_no", 3);
         pt.update(pu_yes, pu.0, pu.1, Some(100.0), Some(100.0), 1);
 
         // Range child: slot 4 = YES
-        let rc_yes = pt.alloc_slot("rc_yes");
-        let rc_no = pt.alloc_slot("rc_no");
+        let rc_yes = pt.alloc_slot("rc_yes", 4);
+        let rc_no = pt.alloc_slot("rc_no", 5);
         pt.update(rc_yes, rc.0, rc.1, Some(100.0), Some(100.0), 1);
 
         let pl_meta = MarketMeta {
@@ -296,6 +314,8 @@ mod tests {
             neg_risk: false,
             yes_slot: pl_yes,
             no_slot: pl_no,
+            token_ids: [0, 1],
+            fee_rate_bps: [0, 0],
         };
 
         let pu_meta = MarketMeta {
@@ -308,6 +328,8 @@ mod tests {
             neg_risk: false,
             yes_slot: pu_yes,
             no_slot: pu_no,
+            token_ids: [2, 3],
+            fee_rate_bps: [0, 0],
         };
 
         let rc_meta = MarketMeta {
@@ -320,6 +342,8 @@ mod tests {
             neg_risk: false,
             yes_slot: rc_yes,
             no_slot: rc_no,
+            token_ids: [4, 5],
+            fee_rate_bps: [0, 0],
         };
 
         (pt, pl_meta, pu_meta, rc_meta)
@@ -348,10 +372,16 @@ mod tests {
             lower_no_token: "pl_no".to_string(),
             range_yes_token: "rc_yes".to_string(),
             upper_yes_token: "pu_yes".to_string(),
+            triangle_fee_bps: [0; 3],
+            complement_fee_bps: [0; 3],
             last_emitted_buy_ms: 0,
             last_emitted_complement_ms: 0,
             last_emitted_unbundle_ms: 0,
             last_emitted_bundle_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+            emission: [EmissionLatch::default(); StrategyKind::COUNT],
+            opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
         };
 
         let mut group = GroupState {
@@ -361,18 +391,28 @@ mod tests {
             child_metas: vec![rc_meta],
             parent_metas: vec![pl_meta, pu_meta],
             trio_states: vec![trio],
-            trio_lookup_by_asset: std::collections::HashMap::new(),
+            bracket_states: Vec::new(),
+            ladder: None,
+            monotone_pairs: Vec::new(),
+            parity_states: Vec::new(),
+            trios_by_child: Vec::new(),
+            trios_by_parent: Vec::new(),
+            config: EngineConfig::default(),
+            diagnostics: Default::default(),
+            opportunity_events: Vec::new(),
+            scratch_slots: Vec::new(),
         };
 
         let cfg = EngineConfig {
             min_profit_abs: 0.005,
             min_profit_bps: 30.0,
             cooldown_ms: 3000,
+            ..EngineConfig::default()
         };
 
-        let result = evaluate_unbundling(&mut group, 0, &pt, &cfg);
+        let result = evaluate_unbundling(&mut group, 0, &pt, &cfg, 1);
         assert!(result.is_some());
-        let sig = result.unwrap();
+        let sig = FlatArbSignal::from(&result.unwrap());
         assert!((sig.profit_abs - 0.10).abs() < 1e-10);
         assert_eq!(sig.strategy, "SELL_PARENT_BUY_CHILDREN");
         // Verify YES tokens are used (not NO)
@@ -404,10 +444,16 @@ mod tests {
             lower_no_token: "pl_no".to_string(),
             range_yes_token: "rc_yes".to_string(),
             upper_yes_token: "pu_yes".to_string(),
+            triangle_fee_bps: [0; 3],
+            complement_fee_bps: [0; 3],
             last_emitted_buy_ms: 0,
             last_emitted_complement_ms: 0,
             last_emitted_unbundle_ms: 0,
             last_emitted_bundle_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+            emission: [EmissionLatch::default(); StrategyKind::COUNT],
+            opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
         };
 
         let mut group = GroupState {
@@ -417,18 +463,28 @@ mod tests {
             child_metas: vec![rc_meta],
             parent_metas: vec![pl_meta, pu_meta],
             trio_states: vec![trio],
-            trio_lookup_by_asset: std::collections::HashMap::new(),
+            bracket_states: Vec::new(),
+            ladder: None,
+            monotone_pairs: Vec::new(),
+            parity_states: Vec::new(),
+            trios_by_child: Vec::new(),
+            trios_by_parent: Vec::new(),
+            config: EngineConfig::default(),
+            diagnostics: Default::default(),
+            opportunity_events: Vec::new(),
+            scratch_slots: Vec::new(),
         };
 
         let cfg = EngineConfig {
             min_profit_abs: 0.005,
             min_profit_bps: 30.0,
             cooldown_ms: 3000,
+            ..EngineConfig::default()
         };
 
-        let result = evaluate_bundling(&mut group, 0, &pt, &cfg);
+        let result = evaluate_bundling(&mut group, 0, &pt, &cfg, 1);
         assert!(result.is_some());
-        let sig = result.unwrap();
+        let sig = FlatArbSignal::from(&result.unwrap());
         assert!((sig.profit_abs - 0.10).abs() < 1e-10);
         assert_eq!(sig.strategy, "BUY_PARENT_SELL_CHILDREN");
         assert_eq!(sig.parent_asset_id, "pl_yes");
@@ -458,10 +514,16 @@ mod tests {
             lower_no_token: "pl_no".to_string(),
             range_yes_token: "rc_yes".to_string(),
             upper_yes_token: "pu_yes".to_string(),
+            triangle_fee_bps: [0; 3],
+            complement_fee_bps: [0; 3],
             last_emitted_buy_ms: 0,
             last_emitted_complement_ms: 0,
             last_emitted_unbundle_ms: 0,
             last_emitted_bundle_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+            emission: [EmissionLatch::default(); StrategyKind::COUNT],
+            opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
         };
 
         let mut group = GroupState {
@@ -471,10 +533,19 @@ mod tests {
             child_metas: vec![rc_meta],
             parent_metas: vec![pl_meta, pu_meta],
             trio_states: vec![trio],
-            trio_lookup_by_asset: std::collections::HashMap::new(),
+            bracket_states: Vec::new(),
+            ladder: None,
+            monotone_pairs: Vec::new(),
+            parity_states: Vec::new(),
+            trios_by_child: Vec::new(),
+            trios_by_parent: Vec::new(),
+            config: EngineConfig::default(),
+            diagnostics: Default::default(),
+            opportunity_events: Vec::new(),
+            scratch_slots: Vec::new(),
         };
 
         let cfg = EngineConfig::default();
-        assert!(evaluate_unbundling(&mut group, 0, &pt, &cfg).is_none());
+        assert!(evaluate_unbundling(&mut group, 0, &pt, &cfg, 1).is_none());
     }
 }
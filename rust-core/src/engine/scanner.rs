@@ -0,0 +1,124 @@
+//! Near-miss scanner — ranks the current edge of every structure per strategy.
+//!
+//! Evaluators record every computed edge into the `edge_stats` of the structure
+//! they evaluate (trio, bracket, ladder, monotone pair, parity, neg-risk event;
+//! below threshold and negative included). The scanner reads those running stats
+//! and returns the top-N structures by current `profit_bps`, so thresholds can be
+//! tuned from data.
+
+use crate::engine::state::{EdgeStats, EngineState};
+use crate::types::strategy::StrategyKind;
+use std::sync::Arc;
+
+/// One ranked structure edge.
+#[derive(Debug, Clone)]
+pub struct NearMiss {
+    pub strategy: StrategyKind,
+    /// Group key (event id for neg-risk events).
+    pub group_key: String,
+    pub event_slug: String,
+    pub crypto: String,
+    /// Same names as `OpportunityEvent.structure`.
+    pub structure: &'static str,
+    pub structure_index: usize,
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    pub last_update_ms: i64,
+    pub max_profit_abs: f64,
+    pub max_profit_bps: f64,
+    pub max_at_ms: i64,
+    pub time_above_zero_ms: i64,
+    pub samples: u64,
+}
+
+/// Top `n` structures by current edge for `strategy`, best first.
+/// Structures that have never been evaluated for the strategy are skipped.
+pub fn top_near_misses(
+    engine: &EngineState,
+    strategy: StrategyKind,
+    n: usize,
+    now_ms: i64,
+) -> Vec<NearMiss> {
+    let mut ranked = Vec::new();
+    let mut rank = |keys: (&str, &str, &str), structure: &'static str, index: usize, stats: &[EdgeStats]| {
+        let stats = &stats[strategy.index()];
+        if stats.samples == 0 || stats.last_profit_bps.is_nan() {
+            return;
+        }
+        ranked.push(NearMiss {
+            strategy,
+            group_key: keys.0.to_string(),
+            event_slug: keys.1.to_string(),
+            crypto: keys.2.to_string(),
+            structure,
+            structure_index: index,
+            profit_abs: stats.last_profit_abs,
+            profit_bps: stats.last_profit_bps,
+            last_update_ms: stats.last_update_ms,
+            max_profit_abs: stats.max_profit_abs,
+            max_profit_bps: stats.max_profit_bps,
+            max_at_ms: stats.max_at_ms,
+            time_above_zero_ms: stats.time_above_zero_ms(now_ms),
+            samples: stats.samples,
+        });
+    };
+
+    for g in &engine.groups {
+        let keys = (g.group_key.as_str(), g.event_slug.as_str(), g.crypto.as_str());
+        for (i, trio) in g.trio_states.iter().enumerate() {
+            rank(keys, "trio", i, &trio.edge_stats);
+        }
+        for (i, bracket) in g.bracket_states.iter().enumerate() {
+            rank(keys, "bracket", i, &bracket.edge_stats);
+        }
+        if let Some(ladder) = &g.ladder {
+            rank(keys, "ladder", 0, &ladder.edge_stats);
+        }
+        for (i, pair) in g.monotone_pairs.iter().enumerate() {
+            rank(keys, "monotone", i, &pair.edge_stats);
+        }
+        for (i, parity) in g.parity_states.iter().enumerate() {
+            rank(keys, "parity", i, &parity.edge_stats);
+        }
+    }
+    for e in &engine.neg_risk_events {
+        let keys = (e.event_id.as_str(), e.event_slug.as_str(), e.crypto.as_str());
+        rank(keys, "neg_risk", 0, &e.edge_stats);
+    }
+
+    ranked.sort_by(|a, b| b.profit_bps.total_cmp(&a.profit_bps));
+    ranked.truncate(n);
+    ranked
+}
+
+/// Top `n` per strategy, in `StrategyKind::ALL` order.
+pub fn top_near_misses_all(engine: &EngineState, n: usize, now_ms: i64) -> Vec<NearMiss> {
+    StrategyKind::ALL
+        .iter()
+        .flat_map(|&s| top_near_misses(engine, s, n, now_ms))
+        .collect()
+}
+
+/// Clear running edge statistics on every structure (e.g. after a threshold change).
+pub fn reset_edge_stats(engine: &mut EngineState) {
+    for group in engine.groups.iter_mut().map(Arc::make_mut) {
+        for trio in &mut group.trio_states {
+            trio.edge_stats = Default::default();
+        }
+        for bracket in &mut group.bracket_states {
+            bracket.edge_stats = Default::default();
+        }
+        if let Some(ladder) = group.ladder.as_mut() {
+            ladder.edge_stats = Default::default();
+        }
+        for pair in &mut group.monotone_pairs {
+            pair.edge_stats = Default::default();
+        }
+        for parity in &mut group.parity_states {
+            parity.edge_stats = Default::default();
+        }
+    }
+    for event in engine.neg_risk_events.iter_mut().map(Arc::make_mut) {
+        event.edge_stats = Default::default();
+    }
+}
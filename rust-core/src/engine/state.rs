@@ -7,7 +7,16 @@
 //!   Trio:  parent_lower YES + parent_upper NO + range NO
 //!   Range: parent_lower YES + parent_upper YES + range YES
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::engine::diagnostics::StructureDiagnostics;
+use crate::engine::intern::{TokenId, TokenInterner};
+use crate::types::market::TopOfBookUpdate;
+use crate::types::order::OrderSide;
+use crate::types::signal::{Leg, LegRole, OpportunityEvent, OpportunityPhase};
+use crate::types::strategy::StrategyKind;
 
 // =============================================================================
 // PRICE TABLE — Single source of truth for all token prices
@@ -36,32 +45,124 @@ impl Default for PriceSlot {
     }
 }
 
+/// Taker fee per share for one fill at `price`: `rate × min(price, 1 - price)`.
+/// Charged on BUY and SELL legs alike; 0 for an unquoted (NaN) price.
+#[inline(always)]
+pub fn taker_fee(price: f64, fee_rate_bps: u32) -> f64 {
+    if fee_rate_bps == 0 || price.is_nan() {
+        return 0.0;
+    }
+    (fee_rate_bps as f64 / 10000.0) * price.min(1.0 - price)
+}
+
+/// Sentinel for "no slot" in `PriceTable`'s id → slot array.
+const NO_SLOT: u32 = u32::MAX;
+
 /// Central price table — one slot per unique token ID.
 /// Allocated at `update_market_structure()` time, mutated on each TopOfBook update.
+///
+/// Slots are reference-counted by the markets that use them. A slot whose last
+/// market is retired is reset and recycled by the next `alloc_slot()`, so
+/// incremental structure updates never disturb prices of unchanged tokens.
+#[derive(Clone)]
 pub struct PriceTable {
     pub slots: Vec<PriceSlot>,
-    pub token_to_slot: HashMap<String, u32>,
+    /// Token string → slot. Changes only with the structure, so read snapshots share it.
+    pub token_to_slot: Arc<HashMap<String, u32>>,
+    /// Slot bookkeeping — also structure-only, shared the same way.
+    index: Arc<SlotIndex>,
+}
+
+#[derive(Clone, Default)]
+struct SlotIndex {
+    /// Interned token id → slot (`NO_SLOT` when the token has no slot).
+    id_to_slot: Vec<u32>,
+    /// Number of markets referencing each slot (parallel to `slots`).
+    slot_refs: Vec<u32>,
+    /// Reclaimed slots available for reuse.
+    free_slots: Vec<u32>,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PriceTable {
     pub fn new() -> Self {
         Self {
             slots: Vec::new(),
-            token_to_slot: HashMap::new(),
+            token_to_slot: Arc::new(HashMap::new()),
+            index: Arc::new(SlotIndex::default()),
         }
     }
 
     /// Allocate or return existing slot for a token ID.
-    pub fn alloc_slot(&mut self, token_id: &str) -> u32 {
+    /// Every call takes one reference; pair it with `release_slot()`.
+    pub fn alloc_slot(&mut self, token_id: &str, id: TokenId) -> u32 {
+        let index = Arc::make_mut(&mut self.index);
         if let Some(&slot) = self.token_to_slot.get(token_id) {
+            index.slot_refs[slot as usize] += 1;
             return slot;
         }
-        let slot = self.slots.len() as u32;
-        self.slots.push(PriceSlot::default());
-        self.token_to_slot.insert(token_id.to_string(), slot);
+        let slot = match index.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot as usize] = PriceSlot::default();
+                index.slot_refs[slot as usize] = 1;
+                slot
+            }
+            None => {
+                self.slots.push(PriceSlot::default());
+                index.slot_refs.push(1);
+                (self.slots.len() - 1) as u32
+            }
+        };
+        Arc::make_mut(&mut self.token_to_slot).insert(token_id.to_string(), slot);
+        let i = id as usize;
+        if i >= index.id_to_slot.len() {
+            index.id_to_slot.resize(i + 1, NO_SLOT);
+        }
+        index.id_to_slot[i] = slot;
         slot
     }
 
+    /// Drop one reference to a token's slot.
+    /// Returns true if the slot was reclaimed (no market uses the token anymore).
+    pub fn release_slot(&mut self, token_id: &str, id: TokenId) -> bool {
+        let slot = match self.token_to_slot.get(token_id) {
+            Some(&s) => s,
+            None => return false,
+        };
+        let index = Arc::make_mut(&mut self.index);
+        let refs = &mut index.slot_refs[slot as usize];
+        *refs = refs.saturating_sub(1);
+        if *refs > 0 {
+            return false;
+        }
+        Arc::make_mut(&mut self.token_to_slot).remove(token_id);
+        if let Some(s) = index.id_to_slot.get_mut(id as usize) {
+            *s = NO_SLOT;
+        }
+        self.slots[slot as usize] = PriceSlot::default();
+        index.free_slots.push(slot);
+        true
+    }
+
+    /// Slot of an interned token — flat array lookup for the hot path.
+    #[inline(always)]
+    pub fn slot_of(&self, id: TokenId) -> Option<u32> {
+        match self.index.id_to_slot.get(id as usize) {
+            Some(&slot) if slot != NO_SLOT => Some(slot),
+            _ => None,
+        }
+    }
+
+    /// Number of slots currently bound to a token.
+    pub fn active_slots(&self) -> usize {
+        self.slots.len() - self.index.free_slots.len()
+    }
+
     /// Update a price slot — called once per TopOfBook arrival.
     #[inline(always)]
     pub fn update(
@@ -112,6 +213,7 @@ pub enum MarketKind {
 }
 
 impl MarketKind {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
             "above" => MarketKind::Above,
@@ -119,6 +221,14 @@ impl MarketKind {
             _ => MarketKind::Range,
         }
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketKind::Range => "range",
+            MarketKind::Above => "above",
+            MarketKind::Below => "below",
+        }
+    }
 }
 
 /// Compact market descriptor — only fields needed for computation.
@@ -134,8 +244,47 @@ pub struct MarketMeta {
     pub neg_risk: bool,
     pub yes_slot: u32, // PriceTable slot for YES token (clobTokenIds[0])
     pub no_slot: u32,  // PriceTable slot for NO token  (clobTokenIds[1])
+    pub token_ids: [TokenId; 2], // Interned [YES, NO]
+    pub fee_rate_bps: [u32; 2],  // Taker fee rate [YES, NO]
 }
 
+impl MarketMeta {
+    /// Signal leg trading this market's YES (`token` 0) or NO (1) at quote `q`.
+    pub fn leg(&self, token: usize, role: LegRole, side: OrderSide, q: &PriceSlot, child_index: i32) -> Leg {
+        Leg {
+            neg_risk: self.neg_risk,
+            fee_rate_bps: self.fee_rate_bps[token],
+            child_index,
+            ..Leg::at_quote(role, side, self.clob_token_ids[token].clone(), self.slug.clone(), q)
+        }
+    }
+}
+
+impl Leg {
+    /// Leg trading `asset_id` at quote `q` — ask for BUY, bid for SELL.
+    /// Not neg-risk, no fee and no child index until set.
+    pub fn at_quote(role: LegRole, side: OrderSide, asset_id: String, market_slug: String, q: &PriceSlot) -> Self {
+        let (price, size_available) = match side {
+            OrderSide::Buy => (q.best_ask, q.best_ask_size),
+            OrderSide::Sell => (q.best_bid, q.best_bid_size),
+        };
+        Self {
+            role,
+            side,
+            asset_id,
+            market_slug,
+            price,
+            size_available,
+            best_bid: q.best_bid,
+            best_ask: q.best_ask,
+            best_bid_size: q.best_bid_size,
+            best_ask_size: q.best_ask_size,
+            neg_risk: false,
+            fee_rate_bps: 0,
+            child_index: -1,
+        }
+    }
+}
 
 // =============================================================================
 // TRIO STATE — References PriceTable slots, NOT prices
@@ -173,11 +322,444 @@ pub struct TrioState {
     pub range_yes_token: String, // range_child.clobTokenIds[0]
     pub upper_yes_token: String, // parent_upper.clobTokenIds[0]
 
+    // Taker fee rates, in leg order of each strategy
+    pub triangle_fee_bps: [u32; 3],   // lower YES, upper NO, range NO
+    pub complement_fee_bps: [u32; 3], // lower NO, range YES, upper YES
+
     // Cooldown timestamps (inline — no HashMap overhead)
     pub last_emitted_buy_ms: i64,
     pub last_emitted_complement_ms: i64,
     pub last_emitted_unbundle_ms: i64,
     pub last_emitted_bundle_ms: i64,
+
+    /// Opportunities suppressed because at least one leg quote was stale.
+    pub stale_skip_count: u64,
+
+    /// Running edge statistics per strategy (indexed by `StrategyKind::index()`).
+    pub edge_stats: [EdgeStats; StrategyKind::COUNT],
+    /// Emission hysteresis per strategy (indexed by `StrategyKind::index()`).
+    pub emission: [EmissionLatch; StrategyKind::COUNT],
+    /// Opportunity lifecycle per strategy (indexed by `StrategyKind::index()`).
+    pub opportunity: [OpportunityTracker; StrategyKind::COUNT],
+}
+
+// =============================================================================
+// BRACKET STATE — Parent pair (i, j) + consecutive range children between them
+// =============================================================================
+
+/// Bracket: parent pair (lower, upper) whose bounds are spanned by a chain of
+/// consecutive range children — the N-leg generalization of a trio.
+///
+/// Triangle BUY:   Parent[lower] YES + Parent[upper] NO + every child NO  (payout = children + 1)
+/// Complement BUY: Parent[lower] NO  + every child YES + Parent[upper] YES (payout = 1)
+/// Pairs already covered by a trio (adjacent parents, single child) are not brackets.
+/// Leg slots are read through the group's metas.
+#[derive(Debug, Clone)]
+pub struct BracketState {
+    pub parent_lower_idx: u16,
+    pub parent_upper_idx: u16,
+    /// Indices into `GroupState.child_metas`, ascending by bound.
+    pub child_idxs: Vec<u16>,
+
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_complement_ms: i64,
+    pub stale_skip_count: u64,
+    pub edge_stats: [EdgeStats; StrategyKind::COUNT],
+    /// Emission hysteresis per strategy (indexed by `StrategyKind::index()`).
+    pub emission: [EmissionLatch; StrategyKind::COUNT],
+    /// Opportunity lifecycle per strategy (indexed by `StrategyKind::index()`).
+    pub opportunity: [OpportunityTracker; StrategyKind::COUNT],
+}
+
+// =============================================================================
+// LADDER STATE — Range children that partition the whole outcome space
+// =============================================================================
+
+/// Full recompute of the incremental ladder sums after this many leg updates,
+/// so floating-point drift never accumulates.
+const LADDER_RESYNC_UPDATES: u32 = 4096;
+
+/// Ladder: range children whose bounds cover (-inf, +inf) with no gap or
+/// overlap — exactly one child resolves YES.
+///
+/// Ladder BUY:  every child YES at ask  (payout = 1)
+/// Ladder SELL: every child YES at bid  (against minted sets, cost = 1)
+/// YES bid/ask sums are maintained incrementally on each leg update.
+#[derive(Debug, Clone)]
+pub struct LadderState {
+    /// Indices into `GroupState.child_metas`, ascending by bound.
+    pub child_idxs: Vec<u16>,
+    /// Last YES bid / ask seen per leg (NaN = not quoted).
+    pub bids: Vec<f64>,
+    pub asks: Vec<f64>,
+    pub sum_bid: f64,
+    pub sum_ask: f64,
+    /// YES taker fee rate per leg, and the running fee sums at the current bids / asks.
+    pub fee_rates: Vec<u32>,
+    pub sum_bid_fee: f64,
+    pub sum_ask_fee: f64,
+    /// Legs without a bid / ask — sums are only meaningful at zero.
+    pub missing_bids: u16,
+    pub missing_asks: u16,
+    updates_since_resync: u32,
+
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_sell_ms: i64,
+    pub stale_skip_count: u64,
+    pub edge_stats: [EdgeStats; StrategyKind::COUNT],
+    /// Emission hysteresis per strategy (indexed by `StrategyKind::index()`).
+    pub emission: [EmissionLatch; StrategyKind::COUNT],
+    /// Opportunity lifecycle per strategy (indexed by `StrategyKind::index()`).
+    pub opportunity: [OpportunityTracker; StrategyKind::COUNT],
+}
+
+impl LadderState {
+    pub fn new(child_idxs: Vec<u16>, fee_rates: Vec<u32>) -> Self {
+        let n = child_idxs.len();
+        Self {
+            child_idxs,
+            bids: vec![f64::NAN; n],
+            asks: vec![f64::NAN; n],
+            sum_bid: 0.0,
+            sum_ask: 0.0,
+            fee_rates,
+            sum_bid_fee: 0.0,
+            sum_ask_fee: 0.0,
+            missing_bids: n as u16,
+            missing_asks: n as u16,
+            updates_since_resync: 0,
+            last_emitted_buy_ms: 0,
+            last_emitted_sell_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: Default::default(),
+            emission: Default::default(),
+            opportunity: Default::default(),
+        }
+    }
+
+    /// Apply a leg's new YES quote to the running sums.
+    #[inline(always)]
+    pub fn update_leg(&mut self, leg_idx: usize, bid: f64, ask: f64) {
+        let rate = self.fee_rates[leg_idx];
+        self.sum_bid_fee += taker_fee(bid, rate) - taker_fee(self.bids[leg_idx], rate);
+        self.sum_ask_fee += taker_fee(ask, rate) - taker_fee(self.asks[leg_idx], rate);
+        replace_term(&mut self.bids[leg_idx], bid, &mut self.sum_bid, &mut self.missing_bids);
+        replace_term(&mut self.asks[leg_idx], ask, &mut self.sum_ask, &mut self.missing_asks);
+        self.updates_since_resync += 1;
+        if self.updates_since_resync >= LADDER_RESYNC_UPDATES {
+            self.resync_sums();
+        }
+    }
+
+    /// Recompute sums and missing counts from the per-leg values.
+    pub fn resync_sums(&mut self) {
+        (self.sum_bid, self.missing_bids) = sum_quoted(&self.bids);
+        (self.sum_ask, self.missing_asks) = sum_quoted(&self.asks);
+        self.sum_bid_fee = sum_fees(&self.bids, &self.fee_rates);
+        self.sum_ask_fee = sum_fees(&self.asks, &self.fee_rates);
+        self.updates_since_resync = 0;
+    }
+}
+
+/// Sum of the quoted values and the number of unquoted (NaN) ones.
+fn sum_quoted(values: &[f64]) -> (f64, u16) {
+    let missing = values.iter().filter(|v| v.is_nan()).count() as u16;
+    (values.iter().filter(|v| !v.is_nan()).sum(), missing)
+}
+
+/// Taker fees over the quoted values.
+fn sum_fees(values: &[f64], fee_rates: &[u32]) -> f64 {
+    values.iter().zip(fee_rates).map(|(&v, &rate)| taker_fee(v, rate)).sum()
+}
+
+#[inline(always)]
+fn replace_term(value: &mut f64, new: f64, sum: &mut f64, missing: &mut u16) {
+    if value.is_nan() {
+        *missing -= 1;
+    } else {
+        *sum -= *value;
+    }
+    if new.is_nan() {
+        *missing += 1;
+    } else {
+        *sum += new;
+    }
+    *value = new;
+}
+
+// =============================================================================
+// MONOTONE PAIR STATE — Two above (or two below) parents at different strikes
+// =============================================================================
+
+/// Monotonicity pair: P(above k1) ≥ P(above k2) and P(below k1) ≤ P(below k2)
+/// for k1 < k2, so the YES that must be worth more is `buy_idx` and the one
+/// that must be worth less is `sell_idx`.
+///
+/// Arbitrage when Ask(buy YES) < Bid(sell YES): BUY buy-leg YES + SELL sell-leg YES.
+/// The position never loses at resolution.
+#[derive(Debug, Clone)]
+pub struct MonotonePairState {
+    /// Indices into `GroupState.parent_metas`.
+    pub buy_idx: u16,
+    pub sell_idx: u16,
+    /// `Above` or `Below` — both parents share it.
+    pub kind: MarketKind,
+
+    pub last_emitted_ms: i64,
+    pub stale_skip_count: u64,
+    pub edge_stats: [EdgeStats; StrategyKind::COUNT],
+    /// Emission hysteresis per strategy (indexed by `StrategyKind::index()`).
+    pub emission: [EmissionLatch; StrategyKind::COUNT],
+    /// Opportunity lifecycle per strategy (indexed by `StrategyKind::index()`).
+    pub opportunity: [OpportunityTracker; StrategyKind::COUNT],
+}
+
+// =============================================================================
+// PARITY STATE — YES + NO of a single market
+// =============================================================================
+
+/// Parity: the YES and NO of one market always pay exactly 1 together.
+///
+/// Parity BUY:  Ask(YES) + Ask(NO) < 1 → buy both, merge for 1
+/// Parity SELL: Bid(YES) + Bid(NO) > 1 → mint for 1, sell both
+#[derive(Debug, Clone)]
+pub struct ParityState {
+    /// Market is `parent_metas[market_idx]` if set, else `child_metas[market_idx]`.
+    pub is_parent: bool,
+    pub market_idx: u16,
+
+    pub last_emitted_buy_ms: i64,
+    pub last_emitted_sell_ms: i64,
+    pub stale_skip_count: u64,
+    pub edge_stats: [EdgeStats; StrategyKind::COUNT],
+    /// Emission hysteresis per strategy (indexed by `StrategyKind::index()`).
+    pub emission: [EmissionLatch; StrategyKind::COUNT],
+    /// Opportunity lifecycle per strategy (indexed by `StrategyKind::index()`).
+    pub opportunity: [OpportunityTracker; StrategyKind::COUNT],
+}
+
+impl ParityState {
+    /// The market this state checks.
+    #[inline(always)]
+    pub fn meta<'a>(&self, group: &'a GroupState) -> &'a MarketMeta {
+        if self.is_parent {
+            &group.parent_metas[self.market_idx as usize]
+        } else {
+            &group.child_metas[self.market_idx as usize]
+        }
+    }
+}
+
+/// Running statistics of one strategy's edge on one structure — recorded on every
+/// evaluation, including below-threshold and negative edges.
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeStats {
+    pub last_profit_abs: f64,
+    pub last_profit_bps: f64,
+    pub last_update_ms: i64,
+    pub max_profit_abs: f64,
+    pub max_profit_bps: f64,
+    pub max_at_ms: i64,
+    /// Start of the current positive-edge interval, if the edge is positive now.
+    pub above_zero_since_ms: Option<i64>,
+    /// Accumulated time of completed positive-edge intervals.
+    pub closed_above_zero_ms: i64,
+    pub samples: u64,
+}
+
+impl Default for EdgeStats {
+    fn default() -> Self {
+        Self {
+            last_profit_abs: f64::NAN,
+            last_profit_bps: f64::NAN,
+            last_update_ms: 0,
+            max_profit_abs: f64::NAN,
+            max_profit_bps: f64::NAN,
+            max_at_ms: 0,
+            above_zero_since_ms: None,
+            closed_above_zero_ms: 0,
+            samples: 0,
+        }
+    }
+}
+
+impl EdgeStats {
+    #[inline(always)]
+    pub fn record(&mut self, profit_abs: f64, profit_bps: f64, now_ms: i64) {
+        self.last_profit_abs = profit_abs;
+        self.last_profit_bps = profit_bps;
+        self.last_update_ms = now_ms;
+        self.samples += 1;
+
+        if self.samples == 1 || profit_bps > self.max_profit_bps {
+            self.max_profit_abs = profit_abs;
+            self.max_profit_bps = profit_bps;
+            self.max_at_ms = now_ms;
+        }
+
+        match (self.above_zero_since_ms, profit_abs > 0.0) {
+            (None, true) => self.above_zero_since_ms = Some(now_ms),
+            (Some(since), false) => {
+                self.closed_above_zero_ms += (now_ms - since).max(0);
+                self.above_zero_since_ms = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Total time the edge has been positive, including the open interval up to `now_ms`.
+    pub fn time_above_zero_ms(&self, now_ms: i64) -> i64 {
+        self.closed_above_zero_ms
+            + self.above_zero_since_ms.map_or(0, |since| (now_ms - since).max(0))
+    }
+}
+
+/// Emission state of one strategy's opportunity on one trio / structure,
+/// used by `EmissionPolicy::Hysteresis`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmissionLatch {
+    /// Emitted since the edge last dropped below threshold.
+    pub open: bool,
+    /// Edge of the last emission.
+    pub last_emitted_bps: f64,
+}
+
+impl EmissionLatch {
+    /// Latch for an opportunity emitted at an unknown edge (restored cooldowns):
+    /// only the cooldown or a close can re-emit it.
+    pub fn restored() -> Self {
+        Self {
+            open: true,
+            last_emitted_bps: f64::INFINITY,
+        }
+    }
+
+    #[inline(always)]
+    pub fn emitted(&mut self, profit_bps: f64) {
+        self.open = true;
+        self.last_emitted_bps = profit_bps;
+    }
+
+    /// The edge fell below threshold — the next crossing emits again.
+    #[inline(always)]
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+}
+
+/// Lifecycle of one strategy's opportunity on one trio / structure — open for
+/// as long as the edge meets the threshold, whether or not a signal went out.
+#[derive(Debug, Clone, Copy)]
+pub struct OpportunityTracker {
+    pub open: bool,
+    pub opened_at_ms: i64,
+    pub peak_profit_abs: f64,
+    pub peak_profit_bps: f64,
+    pub peak_at_ms: i64,
+    /// Edge of the latest evaluation (NaN when a leg was unquoted).
+    pub last_profit_abs: f64,
+    pub last_profit_bps: f64,
+}
+
+impl Default for OpportunityTracker {
+    fn default() -> Self {
+        Self {
+            open: false,
+            opened_at_ms: 0,
+            peak_profit_abs: f64::NAN,
+            peak_profit_bps: f64::NAN,
+            peak_at_ms: 0,
+            last_profit_abs: f64::NAN,
+            last_profit_bps: f64::NAN,
+        }
+    }
+}
+
+impl OpportunityTracker {
+    /// Feed one evaluation (`above` = edge meets the threshold); returns the
+    /// transition it caused, if any.
+    #[inline(always)]
+    pub fn observe(
+        &mut self,
+        above: bool,
+        profit_abs: f64,
+        profit_bps: f64,
+        now_ms: i64,
+    ) -> Option<OpportunityPhase> {
+        self.last_profit_abs = profit_abs;
+        self.last_profit_bps = profit_bps;
+        match (self.open, above) {
+            (false, false) => None,
+            (false, true) => {
+                self.open = true;
+                self.opened_at_ms = now_ms;
+                self.set_peak(profit_abs, profit_bps, now_ms);
+                Some(OpportunityPhase::Opened)
+            }
+            (true, false) => {
+                self.open = false;
+                Some(OpportunityPhase::Closed)
+            }
+            (true, true) if profit_bps > self.peak_profit_bps => {
+                self.set_peak(profit_abs, profit_bps, now_ms);
+                Some(OpportunityPhase::Updated)
+            }
+            (true, true) => None,
+        }
+    }
+
+    /// Close the opportunity because its structure or strategy went away;
+    /// returns true if it was open.
+    #[inline(always)]
+    pub fn force_close(&mut self) -> bool {
+        std::mem::replace(&mut self.open, false)
+    }
+
+    fn set_peak(&mut self, profit_abs: f64, profit_bps: f64, now_ms: i64) {
+        self.peak_profit_abs = profit_abs;
+        self.peak_profit_bps = profit_bps;
+        self.peak_at_ms = now_ms;
+    }
+
+    /// Lifecycle event describing this tracker right after `phase`.
+    fn event(
+        &self,
+        phase: OpportunityPhase,
+        keys: (&str, &str, &str),
+        strategy: StrategyKind,
+        structure: &str,
+        structure_index: u32,
+        now_ms: i64,
+    ) -> OpportunityEvent {
+        let (group_key, event_slug, crypto) = keys;
+        OpportunityEvent {
+            phase,
+            group_key: group_key.to_string(),
+            event_slug: event_slug.to_string(),
+            crypto: crypto.to_string(),
+            strategy: strategy.as_str().to_string(),
+            structure: structure.to_string(),
+            structure_index,
+            opened_at_ms: self.opened_at_ms,
+            timestamp_ms: now_ms,
+            duration_ms: (now_ms - self.opened_at_ms).max(0),
+            peak_profit_abs: self.peak_profit_abs,
+            peak_profit_bps: self.peak_profit_bps,
+            peak_at_ms: self.peak_at_ms,
+            profit_abs: self.last_profit_abs,
+            profit_bps: self.last_profit_bps,
+        }
+    }
+}
+
+/// Structure within a group whose opportunity is tracked.
+#[derive(Debug, Clone, Copy)]
+pub enum OpportunitySite {
+    Trio(usize),
+    Bracket(usize),
+    Ladder,
+    Monotone(usize),
+    Parity(usize),
 }
 
 // =============================================================================
@@ -185,7 +767,7 @@ pub struct TrioState {
 // =============================================================================
 
 /// Group state — flat arrays of markets + trios.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GroupState {
     pub group_key: String,
     pub event_slug: String,
@@ -195,12 +777,128 @@ pub struct GroupState {
     pub child_metas: Vec<MarketMeta>,  // range children
     pub parent_metas: Vec<MarketMeta>, // parent markets (sorted by bounds)
 
-
     // Trio states for triangle/range arbitrage
     pub trio_states: Vec<TrioState>,
 
-    // Lookup: token_id → Vec<trio_index>  (all 5 tokens per trio)
-    pub trio_lookup_by_asset: HashMap<String, Vec<u16>>,
+    // Multi-child / non-adjacent brackets (triangle + complement only)
+    pub bracket_states: Vec<BracketState>,
+
+    // Complete partition of the outcome space by range children, if any
+    pub ladder: Option<LadderState>,
+
+    // Same-kind parent pairs (above/above, below/below) ordered by strike
+    pub monotone_pairs: Vec<MonotonePairState>,
+
+    // YES + NO parity — one per market, children first, then parents
+    pub parity_states: Vec<ParityState>,
+
+    // Lookup: child index → trios it is the range leg of (range dispatch)
+    pub trios_by_child: Vec<Vec<u16>>,
+
+    // Lookup: parent index → trios it is the lower or upper leg of (range dispatch)
+    pub trios_by_parent: Vec<Vec<u16>>,
+
+    /// Effective config — global config with matching overrides applied.
+    /// Resolved at structure-build time so the hot path reads it in O(1).
+    pub config: EngineConfig,
+
+    /// What was wrong with the input this group was last built from.
+    pub diagnostics: StructureDiagnostics,
+
+    /// Lifecycle events raised by the evaluators, collected by the engine after each update.
+    pub opportunity_events: Vec<OpportunityEvent>,
+    /// Leg slot buffer reused by the bracket evaluator (no per-evaluation allocation).
+    pub(crate) scratch_slots: Vec<u32>,
+}
+
+impl GroupState {
+    /// Reload the ladder's leg quotes from the PriceTable and recompute its sums.
+    pub fn resync_ladder(&mut self, pt: &PriceTable) {
+        let Some(ladder) = self.ladder.as_mut() else {
+            return;
+        };
+        for (leg, &c) in ladder.child_idxs.iter().enumerate() {
+            let q = pt.get(self.child_metas[c as usize].yes_slot);
+            ladder.bids[leg] = q.best_bid;
+            ladder.asks[leg] = q.best_ask;
+        }
+        ladder.resync_sums();
+    }
+
+    /// Feed one evaluation of `strategy` on `site` to its opportunity tracker,
+    /// queueing a lifecycle event on every transition.
+    #[inline(always)]
+    pub fn track_opportunity(
+        &mut self,
+        site: OpportunitySite,
+        strategy: StrategyKind,
+        above: bool,
+        profit_abs: f64,
+        profit_bps: f64,
+        now_ms: i64,
+    ) {
+        let (tracker, structure, index) = match site {
+            OpportunitySite::Trio(i) => (&mut self.trio_states[i].opportunity, "trio", i),
+            OpportunitySite::Bracket(i) => (&mut self.bracket_states[i].opportunity, "bracket", i),
+            OpportunitySite::Ladder => match self.ladder.as_mut() {
+                Some(ladder) => (&mut ladder.opportunity, "ladder", 0),
+                None => return,
+            },
+            OpportunitySite::Monotone(i) => (&mut self.monotone_pairs[i].opportunity, "monotone", i),
+            OpportunitySite::Parity(i) => (&mut self.parity_states[i].opportunity, "parity", i),
+        };
+        let tracker = &mut tracker[strategy.index()];
+        if let Some(phase) = tracker.observe(above, profit_abs, profit_bps, now_ms) {
+            let event = tracker.event(
+                phase,
+                (&self.group_key, &self.event_slug, &self.crypto),
+                strategy,
+                structure,
+                index as u32,
+                now_ms,
+            );
+            self.opportunity_events.push(event);
+        }
+    }
+
+    /// Close every open opportunity whose strategy `keep` rejects, queueing a
+    /// `Closed` event for each — the group is being dropped or a strategy was disabled.
+    pub fn close_opportunities(&mut self, keep: impl Fn(StrategyKind) -> bool, now_ms: i64) {
+        let keys = (self.group_key.as_str(), self.event_slug.as_str(), self.crypto.as_str());
+        let sites = (self.trio_states.iter_mut().map(|t| ("trio", &mut t.opportunity)).enumerate())
+            .chain(self.bracket_states.iter_mut().map(|b| ("bracket", &mut b.opportunity)).enumerate())
+            .chain(self.ladder.iter_mut().map(|l| ("ladder", &mut l.opportunity)).enumerate())
+            .chain(self.monotone_pairs.iter_mut().map(|p| ("monotone", &mut p.opportunity)).enumerate())
+            .chain(self.parity_states.iter_mut().map(|p| ("parity", &mut p.opportunity)).enumerate());
+        for (index, (structure, trackers)) in sites {
+            close_trackers(trackers, &keep, keys, structure, index as u32, now_ms, &mut self.opportunity_events);
+        }
+    }
+}
+
+/// Force-close the open trackers of one structure whose strategy `keep`
+/// rejects, pushing a `Closed` event for each.
+fn close_trackers(
+    trackers: &mut [OpportunityTracker; StrategyKind::COUNT],
+    keep: &impl Fn(StrategyKind) -> bool,
+    keys: (&str, &str, &str),
+    structure: &str,
+    structure_index: u32,
+    now_ms: i64,
+    events: &mut Vec<OpportunityEvent>,
+) {
+    for (tracker, strategy) in trackers.iter_mut().zip(StrategyKind::ALL) {
+        if !keep(strategy) && tracker.force_close() {
+            events.push(tracker.event(
+                OpportunityPhase::Closed,
+                keys,
+                strategy,
+                structure,
+                structure_index,
+                now_ms,
+            ));
+        }
+    }
 }
 
 // =============================================================================
@@ -208,7 +906,7 @@ pub struct GroupState {
 // =============================================================================
 
 /// Role of a token in the engine — for dispatch routing.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum TrioLegRole {
     ParentLowerYes,
     ParentUpperNo,
@@ -220,7 +918,7 @@ pub enum TrioLegRole {
 }
 
 /// Where a token is used — for TopOfBook dispatch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum TokenRole {
     /// Token is a trio leg (for triangle arbitrage)
     TrioLeg {
@@ -238,18 +936,120 @@ pub enum TokenRole {
         group_idx: u16,
         parent_idx: u16,
     },
+    /// Token is a bracket leg — `strategy` is Triangle or Complement
+    BracketLeg {
+        group_idx: u16,
+        bracket_idx: u16,
+        strategy: StrategyKind,
+    },
+    /// Token is the YES token of a ladder (complete partition) child
+    LadderLeg {
+        group_idx: u16,
+        leg_idx: u16,
+    },
+    /// Token is the YES token of either parent of a monotonicity pair
+    MonotoneLeg {
+        group_idx: u16,
+        pair_idx: u16,
+    },
+    /// Token is the YES or NO token of a market checked for YES + NO parity
+    ParityLeg {
+        group_idx: u16,
+        parity_idx: u16,
+    },
+    /// Token is the YES or NO token of a neg-risk event outcome (not tied to a group)
+    NegRiskLeg {
+        event_idx: u16,
+        outcome_idx: u16,
+        is_no: bool,
+    },
+}
+
+impl TokenRole {
+    /// Group this role belongs to (`None` for neg-risk event roles).
+    #[inline(always)]
+    pub fn group_idx(&self) -> Option<u16> {
+        let group_idx = match self {
+            TokenRole::TrioLeg { group_idx, .. }
+            | TokenRole::RangeChild { group_idx, .. }
+            | TokenRole::Parent { group_idx, .. }
+            | TokenRole::BracketLeg { group_idx, .. }
+            | TokenRole::LadderLeg { group_idx, .. }
+            | TokenRole::MonotoneLeg { group_idx, .. }
+            | TokenRole::ParityLeg { group_idx, .. } => *group_idx,
+            TokenRole::NegRiskLeg { .. } => return None,
+        };
+        Some(group_idx)
+    }
 }
 
 // =============================================================================
 // ENGINE CONFIG
 // =============================================================================
 
-/// Engine configuration — profit thresholds + cooldown.
-#[derive(Debug, Clone)]
+/// Time source for quote staleness and cooldowns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineClock {
+    /// Local wall clock (epoch ms).
+    System,
+    /// Latest exchange timestamp seen on any update — immune to local clock skew.
+    Exchange,
+    /// Fixed time, set explicitly (tests, replays).
+    Manual(i64),
+}
+
+impl EngineClock {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "system" => Some(EngineClock::System),
+            "exchange" => Some(EngineClock::Exchange),
+            _ => None,
+        }
+    }
+}
+
+/// When an opportunity that stays above threshold is emitted again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmissionPolicy {
+    /// Re-emit every `cooldown_ms` while the edge persists.
+    Cooldown,
+    /// Emit when the edge opens; while it stays open, re-emit only when it improves
+    /// by `reemit_improvement_bps` over the last emission or `cooldown_ms` expires.
+    /// Re-arms once the edge drops below threshold.
+    Hysteresis,
+}
+
+impl EmissionPolicy {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "cooldown" => Some(EmissionPolicy::Cooldown),
+            "hysteresis" => Some(EmissionPolicy::Hysteresis),
+            _ => None,
+        }
+    }
+}
+
+/// Engine configuration — profit thresholds + cooldown + quote staleness + strategy switches.
+#[derive(Debug, Clone, Copy)]
 pub struct EngineConfig {
     pub min_profit_bps: f64,
     pub min_profit_abs: f64,
     pub cooldown_ms: i64,
+    /// Re-emission rule for persistent opportunities.
+    pub emission_policy: EmissionPolicy,
+    /// Hysteresis: edge gain over the last emission that re-emits before the cooldown.
+    pub reemit_improvement_bps: f64,
+    /// Maximum age of any leg quote for an opportunity to be emitted (0 = disabled).
+    pub max_quote_age_ms: i64,
+    /// Per-strategy overrides of `max_quote_age_ms`, indexed by `StrategyKind`.
+    pub strategy_max_quote_age_ms: [Option<i64>; StrategyKind::COUNT],
+    /// Clock that quote ages and cooldowns are measured against.
+    pub clock: EngineClock,
+    /// Strategy on/off switches, indexed by `StrategyKind`.
+    pub strategy_enabled: [bool; StrategyKind::COUNT],
+    /// Max range children per bracket (0 = brackets off). Structural: brackets
+    /// are built with the group, so changing it requires a group rebuild.
+    pub max_bracket_children: u16,
 }
 
 impl Default for EngineConfig {
@@ -258,8 +1058,258 @@ impl Default for EngineConfig {
             min_profit_bps: 30.0,
             min_profit_abs: 0.005,
             cooldown_ms: 3000,
+            emission_policy: EmissionPolicy::Cooldown,
+            reemit_improvement_bps: 10.0,
+            max_quote_age_ms: 0,
+            strategy_max_quote_age_ms: [None; StrategyKind::COUNT],
+            clock: EngineClock::System,
+            strategy_enabled: StrategyKind::ALL.map(StrategyKind::enabled_by_default),
+            // Brackets multiply dispatch roles per child — opt-in
+            max_bracket_children: 0,
+        }
+    }
+}
+
+impl EngineConfig {
+    #[inline(always)]
+    pub fn is_enabled(&self, strategy: StrategyKind) -> bool {
+        self.strategy_enabled[strategy.index()]
+    }
+
+    /// Emission gate for an opportunity that meets the threshold. `last_emitted_ms`
+    /// is the state's cooldown timestamp for the strategy (0 = never emitted).
+    #[inline(always)]
+    pub fn should_emit(&self, latch: &EmissionLatch, last_emitted_ms: i64, profit_bps: f64, now_ms: i64) -> bool {
+        let cooldown_over = last_emitted_ms <= 0 || now_ms - last_emitted_ms >= self.cooldown_ms;
+        match self.emission_policy {
+            EmissionPolicy::Cooldown => cooldown_over,
+            EmissionPolicy::Hysteresis => {
+                !latch.open
+                    || cooldown_over
+                    || profit_bps >= latch.last_emitted_bps + self.reemit_improvement_bps
+            }
+        }
+    }
+
+    /// Effective staleness limit for a strategy (0 = disabled).
+    #[inline(always)]
+    pub fn max_quote_age_ms_for(&self, strategy: StrategyKind) -> i64 {
+        self.strategy_max_quote_age_ms[strategy.index()].unwrap_or(self.max_quote_age_ms)
+    }
+
+    /// True if every leg quote is younger than the strategy's staleness limit.
+    /// Quotes without a timestamp count as stale while the guard is enabled.
+    #[inline(always)]
+    pub fn quotes_fresh(&self, strategy: StrategyKind, legs: &[&PriceSlot], now_ms: i64) -> bool {
+        let max_age = self.max_quote_age_ms_for(strategy);
+        if max_age <= 0 {
+            return true;
+        }
+        legs.iter()
+            .all(|leg| leg.timestamp_ms > 0 && now_ms - leg.timestamp_ms <= max_age)
+    }
+
+    /// `quotes_fresh` over PriceTable slots, for evaluators that collect leg
+    /// slots in a reused buffer instead of a `Vec<&PriceSlot>`.
+    #[inline(always)]
+    pub fn slots_fresh(&self, strategy: StrategyKind, pt: &PriceTable, slots: &[u32], now_ms: i64) -> bool {
+        let max_age = self.max_quote_age_ms_for(strategy);
+        if max_age <= 0 {
+            return true;
+        }
+        slots.iter()
+            .map(|&slot| pt.get(slot))
+            .all(|leg| leg.timestamp_ms > 0 && now_ms - leg.timestamp_ms <= max_age)
+    }
+}
+
+/// Which groups a config override applies to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverrideSelector {
+    /// Groups of one underlying (e.g. "BTC"), case-insensitive.
+    Crypto(String),
+    /// Groups whose event slug matches a glob pattern (`*` = any run of characters).
+    EventSlug(String),
+    /// A single group by exact `group_key`.
+    GroupKey(String),
+}
+
+impl OverrideSelector {
+    /// Layer precedence — higher layers are applied later and win.
+    fn layer(&self) -> u8 {
+        match self {
+            OverrideSelector::Crypto(_) => 0,
+            OverrideSelector::EventSlug(_) => 1,
+            OverrideSelector::GroupKey(_) => 2,
+        }
+    }
+
+    fn matches(&self, group_key: &str, event_slug: &str, crypto: &str) -> bool {
+        match self {
+            OverrideSelector::Crypto(c) => c.eq_ignore_ascii_case(crypto),
+            OverrideSelector::EventSlug(pattern) => glob_match(pattern, event_slug),
+            OverrideSelector::GroupKey(k) => k == group_key,
+        }
+    }
+}
+
+/// Partial engine config applied on top of the global config for matching groups.
+#[derive(Debug, Clone)]
+pub struct ConfigOverride {
+    pub selector: OverrideSelector,
+    pub min_profit_bps: Option<f64>,
+    pub min_profit_abs: Option<f64>,
+    pub cooldown_ms: Option<i64>,
+    pub max_quote_age_ms: Option<i64>,
+    pub strategy_enabled: [Option<bool>; StrategyKind::COUNT],
+}
+
+impl ConfigOverride {
+    fn apply(&self, cfg: &mut EngineConfig) {
+        if let Some(v) = self.min_profit_bps {
+            cfg.min_profit_bps = v;
+        }
+        if let Some(v) = self.min_profit_abs {
+            cfg.min_profit_abs = v;
+        }
+        if let Some(v) = self.cooldown_ms {
+            cfg.cooldown_ms = v;
+        }
+        if let Some(v) = self.max_quote_age_ms {
+            // A group-level limit replaces any per-strategy global override
+            cfg.max_quote_age_ms = v;
+            cfg.strategy_max_quote_age_ms = [None; StrategyKind::COUNT];
+        }
+        for (enabled, value) in cfg.strategy_enabled.iter_mut().zip(self.strategy_enabled) {
+            if let Some(v) = value {
+                *enabled = v;
+            }
+        }
+    }
+}
+
+/// Resolve the effective config for a group: global → crypto → event slug → group key.
+/// Overrides within the same layer apply in the order given.
+pub fn resolve_group_config(
+    base: &EngineConfig,
+    overrides: &[ConfigOverride],
+    group_key: &str,
+    event_slug: &str,
+    crypto: &str,
+) -> EngineConfig {
+    let mut cfg = *base;
+    let mut matching: Vec<&ConfigOverride> = overrides
+        .iter()
+        .filter(|o| o.selector.matches(group_key, event_slug, crypto))
+        .collect();
+    matching.sort_by_key(|o| o.selector.layer()); // stable — keeps order within a layer
+    for o in matching {
+        o.apply(&mut cfg);
+    }
+    cfg
+}
+
+/// Minimal glob: `*` matches any (possibly empty) run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let first = parts[0];
+    let last = parts[parts.len() - 1];
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+// =============================================================================
+// NEG-RISK EVENT STATE — All outcomes of one mutually exclusive event
+// =============================================================================
+
+/// One outcome market of a neg-risk event.
+#[derive(Debug, Clone)]
+pub struct NegRiskOutcome {
+    pub market_id: String,
+    pub slug: String,
+    pub clob_token_ids: [String; 2], // [YES, NO]
+    pub yes_slot: u32,
+    pub no_slot: u32,
+    pub token_ids: [TokenId; 2], // Interned [YES, NO]
+    pub fee_rate_bps: [u32; 2],  // Taker fee rate [YES, NO]
+}
+
+/// Neg-risk event: exactly one outcome resolves YES, and the neg-risk adapter
+/// converts 1 NO of an outcome into 1 YES of every other outcome.
+///
+/// YES BUY:  Σ Ask(YES)  < 1      → buy every YES (payout = 1)
+/// NO BUY:   Σ Ask(NO)   < n - 1  → buy every NO  (payout = n - 1)
+/// CONVERT:  Ask(NO_i) < Σ_{j≠i} Bid(YES_j) → buy NO_i, convert, sell the YESes
+/// Tracked in `EngineState.neg_risk_events`, independent of range groups; the
+/// outcome list must be complete or the sums are meaningless.
+#[derive(Debug, Clone)]
+pub struct NegRiskEventState {
+    /// Neg-risk market id shared by every outcome of the event.
+    pub event_id: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub outcomes: Vec<NegRiskOutcome>,
+
+    /// Effective config — resolved with `event_id` as the group key.
+    pub config: EngineConfig,
+
+    pub last_emitted_yes_buy_ms: i64,
+    pub last_emitted_no_buy_ms: i64,
+    pub last_emitted_convert_ms: i64,
+    pub stale_skip_count: u64,
+    pub edge_stats: [EdgeStats; StrategyKind::COUNT],
+    /// Emission hysteresis per strategy (indexed by `StrategyKind::index()`).
+    pub emission: [EmissionLatch; StrategyKind::COUNT],
+    /// Opportunity lifecycle per strategy (indexed by `StrategyKind::index()`).
+    pub opportunity: [OpportunityTracker; StrategyKind::COUNT],
+
+    /// Lifecycle events raised by the evaluator, collected by the engine after each update.
+    pub opportunity_events: Vec<OpportunityEvent>,
+}
+
+impl NegRiskEventState {
+    /// Same as `GroupState::track_opportunity`, for the event as a whole.
+    #[inline(always)]
+    pub fn track_opportunity(
+        &mut self,
+        strategy: StrategyKind,
+        above: bool,
+        profit_abs: f64,
+        profit_bps: f64,
+        now_ms: i64,
+    ) {
+        let tracker = &mut self.opportunity[strategy.index()];
+        if let Some(phase) = tracker.observe(above, profit_abs, profit_bps, now_ms) {
+            let event = tracker.event(
+                phase,
+                (&self.event_id, &self.event_slug, &self.crypto),
+                strategy,
+                "neg_risk",
+                0,
+                now_ms,
+            );
+            self.opportunity_events.push(event);
         }
     }
+    /// Same as `GroupState::close_opportunities`, for the event as a whole.
+    pub fn close_opportunities(&mut self, keep: impl Fn(StrategyKind) -> bool, now_ms: i64) {
+        let keys = (self.event_id.as_str(), self.event_slug.as_str(), self.crypto.as_str());
+        close_trackers(&mut self.opportunity, &keep, keys, "neg_risk", 0, now_ms, &mut self.opportunity_events);
+    }
 }
 
 // =============================================================================
@@ -271,73 +1321,449 @@ impl Default for EngineConfig {
 pub struct LastPrice {
     pub bid: f64,
     pub ask: f64,
+    pub bid_size: f64,
+    pub ask_size: f64,
     pub timestamp_ms: i64,
 }
 
+// =============================================================================
+// PRICE FEED — Throttled top-of-book updates for Node.js
+// =============================================================================
+
+/// Throttle state of one watched token.
+#[derive(Debug, Clone, Copy)]
+struct PriceFeedToken {
+    last_sent_ms: i64,
+    /// Changed since `last_sent_ms` but throttled — sent once the window passes.
+    pending: bool,
+}
+
+/// Opt-in price feed: the watched tokens and their per-token throttle.
+///
+/// Each watched token is sent at most once per `throttle_ms`; a change inside
+/// the window is held back and sent (at its latest value) when the window ends.
+#[derive(Debug, Clone, Default)]
+pub struct PriceFeed {
+    pub throttle_ms: i64,
+    tokens: HashMap<TokenId, PriceFeedToken>,
+    /// Tokens due to be sent, in change order.
+    due: Vec<TokenId>,
+    /// Number of tokens with a held-back change.
+    held: usize,
+}
+
+impl PriceFeed {
+    /// Replace the watched tokens (empty disables the feed).
+    pub fn set(&mut self, tokens: impl IntoIterator<Item = TokenId>, throttle_ms: i64) {
+        self.throttle_ms = throttle_ms.max(0);
+        self.tokens = tokens
+            .into_iter()
+            .map(|id| (id, PriceFeedToken { last_sent_ms: i64::MIN, pending: false }))
+            .collect();
+        self.due.clear();
+        self.held = 0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Whether a throttled change is waiting for its window to end.
+    pub fn has_pending(&self) -> bool {
+        self.held > 0
+    }
+
+    /// Whether `take_due` has anything to return (now or once a window ends).
+    pub fn has_queued(&self) -> bool {
+        !self.due.is_empty() || self.held > 0
+    }
+
+    pub fn watches(&self, id: TokenId) -> bool {
+        self.tokens.contains_key(&id)
+    }
+
+    /// Record a price change of `id`: due now, held back, or ignored if unwatched.
+    #[inline]
+    pub fn observe(&mut self, id: TokenId, now_ms: i64) {
+        let Some(t) = self.tokens.get_mut(&id) else { return };
+        if now_ms.saturating_sub(t.last_sent_ms) >= self.throttle_ms {
+            if t.pending {
+                t.pending = false;
+                self.held -= 1;
+            }
+            t.last_sent_ms = now_ms;
+            self.due.push(id);
+        } else if !t.pending {
+            t.pending = true;
+            self.held += 1;
+        }
+    }
+
+    /// Take the tokens due at `now_ms`, including held-back changes whose window ended.
+    pub fn take_due(&mut self, now_ms: i64) -> Vec<TokenId> {
+        if self.held > 0 {
+            for (&id, t) in self.tokens.iter_mut() {
+                if t.pending && now_ms.saturating_sub(t.last_sent_ms) >= self.throttle_ms {
+                    t.last_sent_ms = now_ms;
+                    t.pending = false;
+                    self.held -= 1;
+                    self.due.push(id);
+                }
+            }
+        }
+        std::mem::take(&mut self.due)
+    }
+}
+
 // =============================================================================
 // ENGINE STATE — Top-level state container
 // =============================================================================
 
+/// Closed opportunities kept in `EngineState.closed_opportunities`.
+pub const CLOSED_OPPORTUNITY_LOG_CAP: usize = 256;
+
+/// Queue one lifecycle event, keeping closed ones in the bounded log.
+fn queue_opportunity_event(
+    pending: &mut Vec<OpportunityEvent>,
+    closed: &mut Arc<VecDeque<OpportunityEvent>>,
+    event: OpportunityEvent,
+) {
+    if event.phase == OpportunityPhase::Closed {
+        let closed = Arc::make_mut(closed);
+        if closed.len() >= CLOSED_OPPORTUNITY_LOG_CAP {
+            closed.pop_front();
+        }
+        closed.push_back(event.clone());
+    }
+    pending.push(event);
+}
+
 /// Main engine state — owns PriceTable and all groups.
+///
+/// Read snapshots published by the engine thread (`engine::owner`) come from
+/// `snapshot()`. Structure (token index, key index, slot bookkeeping), overrides,
+/// the closed-opportunity log and the price feed sit behind `Arc`s, and groups /
+/// neg-risk events are copy-on-write (`Arc::make_mut`), so a snapshot copies only
+/// the price slots and whatever changed since the previous one.
 pub struct EngineState {
     /// Single source of truth for all token prices.
     pub price_table: PriceTable,
 
-    /// All groups (indexed by position).
-    pub groups: Vec<GroupState>,
+    /// All groups (indexed by position), copy-on-write.
+    pub groups: Vec<Arc<GroupState>>,
 
     /// group_key → group index.
-    pub group_key_index: HashMap<String, u16>,
+    pub group_key_index: Arc<HashMap<String, u16>>,
+
+    /// Token string ↔ dense id — shared with the socket parser.
+    pub interner: Arc<TokenInterner>,
 
-    /// token_id → Vec<TokenRole> (one token can have multiple roles).
-    pub token_index: HashMap<String, Vec<TokenRole>>,
+    /// TokenId → Vec<TokenRole> (one token can have multiple roles).
+    pub token_index: Arc<Vec<Vec<TokenRole>>>,
 
-    /// Dirty checking — last seen price per token.
-    pub last_price_cache: HashMap<String, LastPrice>,
+    /// Dirty checking — last seen price per TokenId. Engine thread only (not in snapshots).
+    pub last_price_cache: Vec<Option<LastPrice>>,
 
-    /// Engine configuration.
+    /// Engine configuration (global defaults).
     pub config: EngineConfig,
+
+    /// Layered per-crypto / event-slug / group overrides of `config`.
+    pub config_overrides: Arc<Vec<ConfigOverride>>,
+
+    /// Neg-risk events (indexed by position, copy-on-write), replaced as a whole.
+    pub neg_risk_events: Vec<Arc<NegRiskEventState>>,
+
+    /// Opportunity lifecycle events not yet taken (`take_opportunity_events`).
+    /// Engine thread only (not in snapshots).
+    pub pending_opportunity_events: Vec<OpportunityEvent>,
+
+    /// Most recently closed opportunities, oldest first (at most `CLOSED_OPPORTUNITY_LOG_CAP`).
+    pub closed_opportunities: Arc<VecDeque<OpportunityEvent>>,
+
+    /// Opt-in throttled price feed for Node.js (`take_price_updates`).
+    pub price_feed: Arc<PriceFeed>,
+
+    /// Latest exchange timestamp seen (drives `EngineClock::Exchange`).
+    pub last_exchange_ts_ms: i64,
+
+    /// Reused buffer for trio indices on the range dispatch path.
+    pub(crate) scratch_trios: Vec<u16>,
 }
 
 impl EngineState {
     pub fn new(config: EngineConfig) -> Self {
+        Self::with_interner(config, Arc::new(TokenInterner::new()))
+    }
+
+    /// Engine sharing an interner with other components (e.g. the socket parser).
+    pub fn with_interner(config: EngineConfig, interner: Arc<TokenInterner>) -> Self {
         Self {
             price_table: PriceTable::new(),
             groups: Vec::new(),
-            group_key_index: HashMap::new(),
-            token_index: HashMap::new(),
-            last_price_cache: HashMap::new(),
+            group_key_index: Arc::new(HashMap::new()),
+            interner,
+            token_index: Arc::new(Vec::new()),
+            last_price_cache: Vec::new(),
             config,
+            config_overrides: Arc::new(Vec::new()),
+            neg_risk_events: Vec::new(),
+            pending_opportunity_events: Vec::new(),
+            closed_opportunities: Arc::new(VecDeque::new()),
+            price_feed: Arc::new(PriceFeed::default()),
+            last_exchange_ts_ms: 0,
+            scratch_trios: Vec::new(),
+        }
+    }
+
+    /// Read copy for publishing. Shares every `Arc` with `self` and leaves the
+    /// engine-thread working state (dirty cache, untaken lifecycle events,
+    /// scratch buffers) empty — readers never consult it.
+    pub fn snapshot(&self) -> Self {
+        Self {
+            price_table: self.price_table.clone(),
+            groups: self.groups.clone(),
+            group_key_index: self.group_key_index.clone(),
+            interner: self.interner.clone(),
+            token_index: self.token_index.clone(),
+            last_price_cache: Vec::new(),
+            config: self.config,
+            config_overrides: self.config_overrides.clone(),
+            neg_risk_events: self.neg_risk_events.clone(),
+            pending_opportunity_events: Vec::new(),
+            closed_opportunities: self.closed_opportunities.clone(),
+            price_feed: self.price_feed.clone(),
+            last_exchange_ts_ms: self.last_exchange_ts_ms,
+            scratch_trios: Vec::new(),
+        }
+    }
+
+    /// Effective config for a group described by its keys.
+    pub fn resolve_config(&self, group_key: &str, event_slug: &str, crypto: &str) -> EngineConfig {
+        resolve_group_config(&self.config, &self.config_overrides, group_key, event_slug, crypto)
+    }
+
+    /// Re-resolve every group's (and neg-risk event's) config — call after
+    /// changing `config` or `config_overrides`. Opportunities of strategies
+    /// the new config disables are closed.
+    pub fn refresh_group_configs(&mut self) {
+        let now_ms = self.now_ms();
+        for group in self.groups.iter_mut().map(Arc::make_mut) {
+            let config = resolve_group_config(
+                &self.config,
+                &self.config_overrides,
+                &group.group_key,
+                &group.event_slug,
+                &group.crypto,
+            );
+            // Ladder sums are not maintained while both ladder strategies are off
+            if config.is_enabled(StrategyKind::LadderBuy) || config.is_enabled(StrategyKind::LadderSell) {
+                group.resync_ladder(&self.price_table);
+            }
+            group.config = config;
+            group.close_opportunities(|s| config.is_enabled(s), now_ms);
+            for event in group.opportunity_events.drain(..) {
+                queue_opportunity_event(&mut self.pending_opportunity_events, &mut self.closed_opportunities, event);
+            }
+        }
+        for event in self.neg_risk_events.iter_mut().map(Arc::make_mut) {
+            let config = resolve_group_config(
+                &self.config,
+                &self.config_overrides,
+                &event.event_id,
+                &event.event_slug,
+                &event.crypto,
+            );
+            event.config = config;
+            event.close_opportunities(|s| config.is_enabled(s), now_ms);
+            for e in event.opportunity_events.drain(..) {
+                queue_opportunity_event(&mut self.pending_opportunity_events, &mut self.closed_opportunities, e);
+            }
+        }
+    }
+
+    /// Move the lifecycle events raised by a role's evaluators into the engine
+    /// queue, keeping closed ones in the bounded log.
+    pub(crate) fn collect_opportunity_events(&mut self, role: TokenRole) {
+        // Only touch (and so possibly copy) structures that raised something
+        let events = match role {
+            TokenRole::NegRiskLeg { event_idx, .. } => self
+                .neg_risk_events
+                .get_mut(event_idx as usize)
+                .filter(|e| !e.opportunity_events.is_empty())
+                .map(|e| &mut Arc::make_mut(e).opportunity_events),
+            _ => role
+                .group_idx()
+                .and_then(|gi| self.groups.get_mut(gi as usize))
+                .filter(|g| !g.opportunity_events.is_empty())
+                .map(|g| &mut Arc::make_mut(g).opportunity_events),
+        };
+        let Some(events) = events else { return };
+        for event in events.drain(..) {
+            queue_opportunity_event(&mut self.pending_opportunity_events, &mut self.closed_opportunities, event);
+        }
+    }
+
+    /// Queue lifecycle events raised outside the dispatch path (e.g. the
+    /// `Closed` events of a dropped group).
+    pub(crate) fn queue_opportunity_events(&mut self, events: Vec<OpportunityEvent>) {
+        for event in events {
+            queue_opportunity_event(&mut self.pending_opportunity_events, &mut self.closed_opportunities, event);
+        }
+    }
+
+    /// Take the queued lifecycle events (oldest first). The engine thread drains
+    /// them after every update; direct users of `EngineState` must do the same.
+    pub fn take_opportunity_events(&mut self) -> Vec<OpportunityEvent> {
+        std::mem::take(&mut self.pending_opportunity_events)
+    }
+
+    /// Watch `tokens` on the price feed, each sent at most once per `throttle_ms`
+    /// (empty disables the feed).
+    pub fn set_price_feed(&mut self, tokens: &[String], throttle_ms: i64) {
+        let ids: Vec<TokenId> = tokens.iter().map(|t| self.interner.intern(t)).collect();
+        Arc::make_mut(&mut self.price_feed).set(ids, throttle_ms);
+    }
+
+    /// Take the price feed updates due now, at each token's latest top of book.
+    /// The engine thread drains them after every update and on idle ticks.
+    pub fn take_price_updates(&mut self) -> Vec<TopOfBookUpdate> {
+        if !self.price_feed.has_queued() {
+            return Vec::new();
         }
+        let now_ms = self.now_ms();
+        Arc::make_mut(&mut self.price_feed)
+            .take_due(now_ms)
+            .into_iter()
+            .filter_map(|id| {
+                let q = self.price_table.get(self.price_table.slot_of(id)?);
+                Some(TopOfBookUpdate {
+                    asset_id: self.interner.name(id)?.to_string(),
+                    market_hash: String::new(),
+                    best_bid: q.best_bid,
+                    best_ask: q.best_ask,
+                    best_bid_size: Some(q.best_bid_size),
+                    best_ask_size: Some(q.best_ask_size),
+                    last_price: None,
+                    timestamp_ms: q.timestamp_ms,
+                })
+            })
+            .collect()
+    }
+
+    /// Replace all config overrides and re-resolve group configs.
+    pub fn set_config_overrides(&mut self, overrides: Vec<ConfigOverride>) {
+        self.config_overrides = Arc::new(overrides);
+        self.refresh_group_configs();
     }
 
-    /// Check if price actually changed (dirty check).
+    /// Current time according to the configured clock.
+    pub fn now_ms(&self) -> i64 {
+        match self.config.clock {
+            EngineClock::System => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64,
+            EngineClock::Exchange => self.last_exchange_ts_ms,
+            EngineClock::Manual(ts) => ts,
+        }
+    }
+
+    /// Check if price or top-of-book size actually changed (dirty check).
+    /// A `None` size means "not reported" and never counts as a change.
     /// Returns true if the update should be processed.
-    pub fn is_price_changed(&mut self, asset_id: &str, bid: f64, ask: f64, ts: i64) -> bool {
-        if let Some(cached) = self.last_price_cache.get(asset_id) {
+    pub fn is_price_changed(
+        &mut self,
+        asset_id: &str,
+        bid: f64,
+        ask: f64,
+        bid_size: Option<f64>,
+        ask_size: Option<f64>,
+        ts: i64,
+    ) -> bool {
+        match self.interner.get(asset_id) {
+            Some(id) => self.is_price_changed_id(id, bid, ask, bid_size, ask_size, ts),
+            None => true,
+        }
+    }
+
+    /// Dirty check by interned token id.
+    #[inline(always)]
+    pub fn is_price_changed_id(
+        &mut self,
+        id: TokenId,
+        bid: f64,
+        ask: f64,
+        bid_size: Option<f64>,
+        ask_size: Option<f64>,
+        ts: i64,
+    ) -> bool {
+        let i = id as usize;
+        if i >= self.last_price_cache.len() {
+            self.last_price_cache.resize(i + 1, None);
+        }
+        let entry = &mut self.last_price_cache[i];
+
+        if let Some(cached) = entry {
             // Skip if timestamp is older or equal
             if cached.timestamp_ms > 0 && ts > 0 && ts <= cached.timestamp_ms {
                 return false;
             }
-            // Skip if price unchanged
-            if cached.bid == bid && cached.ask == ask {
+            let size_changed = bid_size.is_some_and(|s| s != cached.bid_size)
+                || ask_size.is_some_and(|s| s != cached.ask_size);
+            // Skip if price and size unchanged
+            if cached.bid == bid && cached.ask == ask && !size_changed {
                 // Update timestamp only
-                if let Some(c) = self.last_price_cache.get_mut(asset_id) {
-                    if ts > 0 {
-                        c.timestamp_ms = ts;
-                    }
+                if ts > 0 {
+                    cached.timestamp_ms = ts;
                 }
                 return false;
             }
+            cached.bid = bid;
+            cached.ask = ask;
+            cached.bid_size = bid_size.unwrap_or(cached.bid_size);
+            cached.ask_size = ask_size.unwrap_or(cached.ask_size);
+            cached.timestamp_ms = ts;
+            return true;
         }
-        self.last_price_cache.insert(
-            asset_id.to_string(),
-            LastPrice {
-                bid,
-                ask,
-                timestamp_ms: ts,
-            },
-        );
+        *entry = Some(LastPrice {
+            bid,
+            ask,
+            bid_size: bid_size.unwrap_or(0.0),
+            ask_size: ask_size.unwrap_or(0.0),
+            timestamp_ms: ts,
+        });
         true
     }
+
+    /// Forget the dirty-check entry of a token so its next update is always processed.
+    pub fn clear_dirty_cache(&mut self, asset_id: &str) {
+        if let Some(id) = self.interner.get(asset_id) {
+            if let Some(entry) = self.last_price_cache.get_mut(id as usize) {
+                *entry = None;
+            }
+        }
+    }
+
+    /// Dispatch roles registered for a token string (empty when unknown).
+    pub fn token_roles(&self, asset_id: &str) -> &[TokenRole] {
+        self.interner
+            .get(asset_id)
+            .and_then(|id| self.token_index.get(id as usize))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Latest top of book for a token string (None when unknown or unslotted).
+    pub fn quote(&self, asset_id: &str) -> Option<PriceSlot> {
+        let id = self.interner.get(asset_id)?;
+        Some(*self.price_table.get(self.price_table.slot_of(id)?))
+    }
+
+    /// Number of tokens with at least one dispatch role.
+    pub fn indexed_token_count(&self) -> usize {
+        self.token_index.iter().filter(|roles| !roles.is_empty()).count()
+    }
 }
@@ -0,0 +1,50 @@
+//! Shared fixtures for the engine tests.
+
+use crate::engine::engine::{MarketDescriptorInput, NegRiskEventInput, NegRiskOutcomeInput};
+use crate::engine::state::EngineState;
+
+/// Fee-free, non-neg-risk market with the given YES / NO token IDs; `slug` = `id`.
+pub fn market(
+    id: &str,
+    yes: &str,
+    no: &str,
+    lower: Option<f64>,
+    upper: Option<f64>,
+    kind: &str,
+) -> MarketDescriptorInput {
+    MarketDescriptorInput {
+        market_id: id.to_string(),
+        slug: id.to_string(),
+        clob_token_ids: vec![yes.to_string(), no.to_string()],
+        bounds_lower: lower,
+        bounds_upper: upper,
+        kind: kind.to_string(),
+        neg_risk: false,
+        fee_rate_bps: [0, 0],
+    }
+}
+
+/// Fee-free neg-risk event `0xnegrisk` with `<outcome>_yes` / `<outcome>_no` tokens.
+pub fn neg_risk_event(outcomes: &[&str]) -> NegRiskEventInput {
+    NegRiskEventInput {
+        event_id: "0xnegrisk".to_string(),
+        event_slug: "fed-decision".to_string(),
+        crypto: String::new(),
+        outcomes: outcomes
+            .iter()
+            .map(|o| NegRiskOutcomeInput {
+                market_id: o.to_string(),
+                slug: o.to_string(),
+                clob_token_ids: vec![format!("{}_yes", o), format!("{}_no", o)],
+                fee_rate_bps: [0, 0],
+            })
+            .collect(),
+    }
+}
+
+/// Write a top of book straight into the token's slot (sizes 100, timestamp 1) —
+/// no dispatch, so evaluators can be called directly on the result.
+pub fn quote(engine: &mut EngineState, token: &str, bid: f64, ask: f64) {
+    let slot = engine.price_table.token_to_slot[token];
+    engine.price_table.update(slot, bid, ask, Some(100.0), Some(100.0), 1);
+}
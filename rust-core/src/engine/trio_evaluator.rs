@@ -3,24 +3,36 @@
 //! Triangle BUY:  Reads parent_lower YES + parent_upper NO + range NO. Payout = 2.
 //! Complement BUY: Reads parent_lower NO  + range YES + parent_upper YES. Payout = 1.
 //! Emits `POLYMARKET_TRIANGLE_BUY` and `POLYMARKET_COMPLEMENT_BUY` signals.
+//! Profit is net of each leg's taker fee; the gross edge rides along.
 
-use std::time::{SystemTime, UNIX_EPOCH};
-
-use crate::engine::state::{EngineConfig, GroupState, PriceTable, TrioState};
-use crate::types::signal::ArbSignal;
+use crate::engine::state::{
+    taker_fee, EngineConfig, GroupState, OpportunitySite, PriceTable, TrioState,
+};
+use crate::types::order::OrderSide;
+use crate::types::signal::{ArbSignal, LegRole};
+use crate::types::strategy::StrategyKind;
 
 /// Internal profit result from trio calculation.
 pub struct TrioProfit {
     pub total_ask: f64,
     pub total_bid: f64,
+    /// Net of taker fees.
     pub profit_abs: f64,
     pub profit_bps: f64,
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
     pub payout: f64,
 }
 
 /// Pure profit calculation — reads 3 slots from PriceTable.
 #[inline(always)]
 pub fn calc_trio_profit_only(trio: &TrioState, pt: &PriceTable, cfg: &EngineConfig) -> Option<TrioProfit> {
+    calc_trio_edge(trio, pt).filter(|p| meets_threshold(p.profit_abs, p.profit_bps, cfg))
+}
+
+/// Raw triangle edge — no threshold applied. `None` only when a leg has no quote.
+#[inline(always)]
+pub fn calc_trio_edge(trio: &TrioState, pt: &PriceTable) -> Option<TrioProfit> {
     let ly = pt.get(trio.lower_yes_slot);
     let un = pt.get(trio.upper_no_slot);
     let rn = pt.get(trio.range_no_slot);
@@ -39,46 +51,67 @@ pub fn calc_trio_profit_only(trio: &TrioState, pt: &PriceTable, cfg: &EngineConf
     let payout = 2.0_f64;
     let total_ask = ly.best_ask + un.best_ask + rn.best_ask;
     let total_bid = ly.best_bid + un.best_bid + rn.best_bid;
+    let [ly_fee, un_fee, rn_fee] = trio.triangle_fee_bps;
+    let fees = taker_fee(ly.best_ask, ly_fee) + taker_fee(un.best_ask, un_fee) + taker_fee(rn.best_ask, rn_fee);
 
-    let profit_buy = payout - total_ask;
-    let profit_bps_buy = (profit_buy / total_ask) * 10000.0;
-
-    let meets_buy =
-        profit_buy >= cfg.min_profit_abs && profit_bps_buy >= cfg.min_profit_bps;
-
-    if !meets_buy {
-        return None;
-    }
+    let gross_buy = payout - total_ask;
+    let profit_buy = gross_buy - fees;
 
     Some(TrioProfit {
         total_ask,
         total_bid,
         profit_abs: profit_buy,
-        profit_bps: profit_bps_buy,
+        profit_bps: (profit_buy / total_ask) * 10000.0,
+        gross_profit_abs: gross_buy,
+        gross_profit_bps: (gross_buy / total_ask) * 10000.0,
         payout,
     })
 }
 
-/// Evaluate a single trio: calc profit → check cooldown → build signal.
+#[inline(always)]
+pub(crate) fn meets_threshold(profit_abs: f64, profit_bps: f64, cfg: &EngineConfig) -> bool {
+    profit_abs >= cfg.min_profit_abs && profit_bps >= cfg.min_profit_bps
+}
+
+/// Evaluate a single trio: calc profit → check staleness → check cooldown → build signal.
 pub fn evaluate_single_trio(
     group: &mut GroupState,
     trio_idx: usize,
     pt: &PriceTable,
     cfg: &EngineConfig,
+    now_ms: i64,
 ) -> Option<ArbSignal> {
     let trio = &group.trio_states[trio_idx];
 
-    let calc = calc_trio_profit_only(trio, pt, cfg)?;
+    let site = OpportunitySite::Trio(trio_idx);
+    let Some(calc) = calc_trio_edge(trio, pt) else {
+        group.track_opportunity(site, StrategyKind::Triangle, false, f64::NAN, f64::NAN, now_ms);
+        return None;
+    };
+    group.trio_states[trio_idx].edge_stats[StrategyKind::Triangle.index()]
+        .record(calc.profit_abs, calc.profit_bps, now_ms);
+    let above = meets_threshold(calc.profit_abs, calc.profit_bps, cfg);
+    group.track_opportunity(site, StrategyKind::Triangle, above, calc.profit_abs, calc.profit_bps, now_ms);
+    if !above {
+        group.trio_states[trio_idx].emission[StrategyKind::Triangle.index()].close();
+        return None;
+    }
+    let trio = &group.trio_states[trio_idx];
 
-    // Cooldown check
-    let now_ms = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64;
+    // Staleness check
+    let legs = [
+        pt.get(trio.lower_yes_slot),
+        pt.get(trio.upper_no_slot),
+        pt.get(trio.range_no_slot),
+    ];
+    if !cfg.quotes_fresh(StrategyKind::Triangle, &legs, now_ms) {
+        group.trio_states[trio_idx].stale_skip_count += 1;
+        return None;
+    }
 
-    if group.trio_states[trio_idx].last_emitted_buy_ms > 0
-        && now_ms - group.trio_states[trio_idx].last_emitted_buy_ms < cfg.cooldown_ms
-    {
+    // Emission check (cooldown / hysteresis)
+    let trio = &group.trio_states[trio_idx];
+    if !cfg.should_emit(&trio.emission[StrategyKind::Triangle.index()], trio.last_emitted_buy_ms, calc.profit_bps, now_ms) {
         return None;
     }
 
@@ -96,60 +129,34 @@ pub fn evaluate_single_trio(
         group_key: group.group_key.clone(),
         event_slug: group.event_slug.clone(),
         crypto: group.crypto.clone(),
-        strategy: "POLYMARKET_TRIANGLE_BUY".to_string(),
+        strategy: StrategyKind::Triangle,
 
         profit_abs: calc.profit_abs,
         profit_bps: calc.profit_bps,
+        gross_profit_abs: calc.gross_profit_abs,
+        gross_profit_bps: calc.gross_profit_bps,
         timestamp_ms: now_ms,
 
-        // Parent Lower — YES token
-        parent_asset_id: trio.lower_yes_token.clone(),
-        parent_market_slug: parent_lower_meta.slug.clone(),
-        parent_best_bid: Some(ly.best_bid),
-        parent_best_ask: Some(ly.best_ask),
-        parent_best_bid_size: Some(ly.best_bid_size),
-        parent_best_ask_size: Some(ly.best_ask_size),
-        parent_neg_risk: parent_lower_meta.neg_risk,
-
-
-        // Parent Upper — NO token
-        parent_upper_asset_id: trio.upper_no_token.clone(),
-        parent_upper_market_slug: parent_upper_meta.slug.clone(),
-        parent_upper_best_bid: Some(un.best_bid),
-        parent_upper_best_ask: Some(un.best_ask),
-        parent_upper_best_bid_size: Some(un.best_bid_size),
-        parent_upper_best_ask_size: Some(un.best_ask_size),
-        parent_upper_neg_risk: parent_upper_meta.neg_risk,
-
-        // Range Child — NO token
-        child_asset_id: trio.range_no_token.clone(),
-        child_market_slug: range_child_meta.slug.clone(),
-        child_best_bid: Some(rn.best_bid),
-        child_best_ask: Some(rn.best_ask),
-        child_best_bid_size: Some(rn.best_bid_size),
-        child_best_ask_size: Some(rn.best_ask_size),
-        child_neg_risk: range_child_meta.neg_risk,
+        // BUY all three: parent lower YES, parent upper NO, range NO
+        legs: vec![
+            parent_lower_meta.leg(0, LegRole::Parent, OrderSide::Buy, ly, -1),
+            parent_upper_meta.leg(1, LegRole::ParentUpper, OrderSide::Buy, un, -1),
+            range_child_meta.leg(1, LegRole::Child, OrderSide::Buy, rn, trio.range_idx as i32),
+        ],
         child_index: trio.range_idx as i32,
 
-        // Aggregates
-        children_sum_ask: rn.best_ask,
-        children_sum_bid: rn.best_bid,
-        parent_best_bid_flat: Some(ly.best_bid),
-        parent_best_ask_flat: Some(ly.best_ask),
-        parent_upper_best_bid_flat: Some(un.best_bid),
-        parent_upper_best_ask_flat: Some(un.best_ask),
-
         // Triangle context
-        triangle_total_cost: Some(calc.total_ask),
-        triangle_total_bid: Some(calc.total_bid),
-        triangle_payout: Some(calc.payout),
-        triangle_mode: Some("BUY".to_string()),
+        total_cost: Some(calc.total_ask),
+        total_bid: Some(calc.total_bid),
+        payout: Some(calc.payout),
+        mode: Some("BUY"),
 
-        reason: "POLYMARKET_TRIANGLE_BUY_COST_LT_PAYOUT".to_string(),
+        reason: "POLYMARKET_TRIANGLE_BUY_COST_LT_PAYOUT",
     };
 
     // Mark cooldown
     group.trio_states[trio_idx].last_emitted_buy_ms = now_ms;
+    group.trio_states[trio_idx].emission[StrategyKind::Triangle.index()].emitted(calc.profit_bps);
 
     Some(signal)
 }
@@ -162,8 +169,11 @@ pub fn evaluate_single_trio(
 pub struct ComplementProfit {
     pub total_ask: f64,
     pub total_bid: f64,
+    /// Net of taker fees.
     pub profit_abs: f64,
     pub profit_bps: f64,
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
     pub payout: f64,
 }
 
@@ -176,6 +186,12 @@ pub fn calc_complement_profit_only(
     pt: &PriceTable,
     cfg: &EngineConfig,
 ) -> Option<ComplementProfit> {
+    calc_complement_edge(trio, pt).filter(|p| meets_threshold(p.profit_abs, p.profit_bps, cfg))
+}
+
+/// Raw complement edge — no threshold applied. `None` only when a leg has no quote.
+#[inline(always)]
+pub fn calc_complement_edge(trio: &TrioState, pt: &PriceTable) -> Option<ComplementProfit> {
     let ln = pt.get(trio.lower_no_slot);
     let ry = pt.get(trio.range_yes_slot);
     let uy = pt.get(trio.upper_yes_slot);
@@ -194,46 +210,62 @@ pub fn calc_complement_profit_only(
     let payout = 1.0_f64;
     let total_ask = ln.best_ask + ry.best_ask + uy.best_ask;
     let total_bid = ln.best_bid + ry.best_bid + uy.best_bid;
+    let [ln_fee, ry_fee, uy_fee] = trio.complement_fee_bps;
+    let fees = taker_fee(ln.best_ask, ln_fee) + taker_fee(ry.best_ask, ry_fee) + taker_fee(uy.best_ask, uy_fee);
 
-    let profit_buy = payout - total_ask;
-    let profit_bps_buy = (profit_buy / total_ask) * 10000.0;
-
-    let meets_buy =
-        profit_buy >= cfg.min_profit_abs && profit_bps_buy >= cfg.min_profit_bps;
-
-    if !meets_buy {
-        return None;
-    }
+    let gross_buy = payout - total_ask;
+    let profit_buy = gross_buy - fees;
 
     Some(ComplementProfit {
         total_ask,
         total_bid,
         profit_abs: profit_buy,
-        profit_bps: profit_bps_buy,
+        profit_bps: (profit_buy / total_ask) * 10000.0,
+        gross_profit_abs: gross_buy,
+        gross_profit_bps: (gross_buy / total_ask) * 10000.0,
         payout,
     })
 }
 
-/// Evaluate complement triangle: calc profit → check cooldown → build signal.
+/// Evaluate complement triangle: calc profit → check staleness → check cooldown → build signal.
 pub fn evaluate_complement_trio(
     group: &mut GroupState,
     trio_idx: usize,
     pt: &PriceTable,
     cfg: &EngineConfig,
+    now_ms: i64,
 ) -> Option<ArbSignal> {
     let trio = &group.trio_states[trio_idx];
 
-    let calc = calc_complement_profit_only(trio, pt, cfg)?;
+    let site = OpportunitySite::Trio(trio_idx);
+    let Some(calc) = calc_complement_edge(trio, pt) else {
+        group.track_opportunity(site, StrategyKind::Complement, false, f64::NAN, f64::NAN, now_ms);
+        return None;
+    };
+    group.trio_states[trio_idx].edge_stats[StrategyKind::Complement.index()]
+        .record(calc.profit_abs, calc.profit_bps, now_ms);
+    let above = meets_threshold(calc.profit_abs, calc.profit_bps, cfg);
+    group.track_opportunity(site, StrategyKind::Complement, above, calc.profit_abs, calc.profit_bps, now_ms);
+    if !above {
+        group.trio_states[trio_idx].emission[StrategyKind::Complement.index()].close();
+        return None;
+    }
+    let trio = &group.trio_states[trio_idx];
 
-    // Cooldown check
-    let now_ms = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64;
+    // Staleness check
+    let legs = [
+        pt.get(trio.lower_no_slot),
+        pt.get(trio.range_yes_slot),
+        pt.get(trio.upper_yes_slot),
+    ];
+    if !cfg.quotes_fresh(StrategyKind::Complement, &legs, now_ms) {
+        group.trio_states[trio_idx].stale_skip_count += 1;
+        return None;
+    }
 
-    if group.trio_states[trio_idx].last_emitted_complement_ms > 0
-        && now_ms - group.trio_states[trio_idx].last_emitted_complement_ms < cfg.cooldown_ms
-    {
+    // Emission check (cooldown / hysteresis)
+    let trio = &group.trio_states[trio_idx];
+    if !cfg.should_emit(&trio.emission[StrategyKind::Complement.index()], trio.last_emitted_complement_ms, calc.profit_bps, now_ms) {
         return None;
     }
 
@@ -251,59 +283,34 @@ pub fn evaluate_complement_trio(
         group_key: group.group_key.clone(),
         event_slug: group.event_slug.clone(),
         crypto: group.crypto.clone(),
-        strategy: "POLYMARKET_COMPLEMENT_BUY".to_string(),
+        strategy: StrategyKind::Complement,
 
         profit_abs: calc.profit_abs,
         profit_bps: calc.profit_bps,
+        gross_profit_abs: calc.gross_profit_abs,
+        gross_profit_bps: calc.gross_profit_bps,
         timestamp_ms: now_ms,
 
-        // Parent Lower — NO token
-        parent_asset_id: trio.lower_no_token.clone(),
-        parent_market_slug: parent_lower_meta.slug.clone(),
-        parent_best_bid: Some(ln.best_bid),
-        parent_best_ask: Some(ln.best_ask),
-        parent_best_bid_size: Some(ln.best_bid_size),
-        parent_best_ask_size: Some(ln.best_ask_size),
-        parent_neg_risk: parent_lower_meta.neg_risk,
-
-        // Parent Upper — YES token
-        parent_upper_asset_id: trio.upper_yes_token.clone(),
-        parent_upper_market_slug: parent_upper_meta.slug.clone(),
-        parent_upper_best_bid: Some(uy.best_bid),
-        parent_upper_best_ask: Some(uy.best_ask),
-        parent_upper_best_bid_size: Some(uy.best_bid_size),
-        parent_upper_best_ask_size: Some(uy.best_ask_size),
-        parent_upper_neg_risk: parent_upper_meta.neg_risk,
-
-        // Range Child — YES token
-        child_asset_id: trio.range_yes_token.clone(),
-        child_market_slug: range_child_meta.slug.clone(),
-        child_best_bid: Some(ry.best_bid),
-        child_best_ask: Some(ry.best_ask),
-        child_best_bid_size: Some(ry.best_bid_size),
-        child_best_ask_size: Some(ry.best_ask_size),
-        child_neg_risk: range_child_meta.neg_risk,
+        // BUY all three: parent lower NO, parent upper YES, range YES
+        legs: vec![
+            parent_lower_meta.leg(1, LegRole::Parent, OrderSide::Buy, ln, -1),
+            parent_upper_meta.leg(0, LegRole::ParentUpper, OrderSide::Buy, uy, -1),
+            range_child_meta.leg(0, LegRole::Child, OrderSide::Buy, ry, trio.range_idx as i32),
+        ],
         child_index: trio.range_idx as i32,
 
-        // Aggregates
-        children_sum_ask: ry.best_ask,
-        children_sum_bid: ry.best_bid,
-        parent_best_bid_flat: Some(ln.best_bid),
-        parent_best_ask_flat: Some(ln.best_ask),
-        parent_upper_best_bid_flat: Some(uy.best_bid),
-        parent_upper_best_ask_flat: Some(uy.best_ask),
-
         // Triangle context
-        triangle_total_cost: Some(calc.total_ask),
-        triangle_total_bid: Some(calc.total_bid),
-        triangle_payout: Some(calc.payout),
-        triangle_mode: Some("COMPLEMENT_BUY".to_string()),
+        total_cost: Some(calc.total_ask),
+        total_bid: Some(calc.total_bid),
+        payout: Some(calc.payout),
+        mode: Some("COMPLEMENT_BUY"),
 
-        reason: "POLYMARKET_COMPLEMENT_BUY_COST_LT_PAYOUT".to_string(),
+        reason: "POLYMARKET_COMPLEMENT_BUY_COST_LT_PAYOUT",
     };
 
     // Mark cooldown
     group.trio_states[trio_idx].last_emitted_complement_ms = now_ms;
+    group.trio_states[trio_idx].emission[StrategyKind::Complement.index()].emitted(calc.profit_bps);
 
     Some(signal)
 }
@@ -311,7 +318,7 @@ pub fn evaluate_complement_trio(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::state::PriceSlot;
+    use crate::engine::state::{EdgeStats, EmissionLatch, OpportunityTracker, PriceSlot};
 
     fn make_price_table(ly: (f64, f64), un: (f64, f64), rn: (f64, f64)) -> PriceTable {
         let mut pt = PriceTable::new();
@@ -339,10 +346,16 @@ mod tests {
             lower_no_token: "ln_token".to_string(),
             range_yes_token: "ry_token".to_string(),
             upper_yes_token: "uy_token".to_string(),
+            triangle_fee_bps: [0; 3],
+            complement_fee_bps: [0; 3],
             last_emitted_buy_ms: 0,
             last_emitted_complement_ms: 0,
             last_emitted_unbundle_ms: 0,
             last_emitted_bundle_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+            emission: [EmissionLatch::default(); StrategyKind::COUNT],
+            opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
         }
     }
 
@@ -358,6 +371,7 @@ mod tests {
             min_profit_abs: 0.005,
             min_profit_bps: 30.0,
             cooldown_ms: 3000,
+            ..EngineConfig::default()
         };
 
         let result = calc_trio_profit_only(&trio, &pt, &cfg);
@@ -379,6 +393,28 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_calc_trio_profit_net_of_fees() {
+        // Gross = 2.0 - 1.90 = 0.10
+        // Fees at 200 bps: 0.02 × (0.40 + 0.50 + 0.20) = 0.022 → net 0.078
+        let pt = make_price_table((0.62, 0.60), (0.52, 0.50), (0.82, 0.80));
+        let mut trio = make_trio();
+        trio.triangle_fee_bps = [200; 3];
+
+        let r = calc_trio_edge(&trio, &pt).unwrap();
+        assert!((r.gross_profit_abs - 0.10).abs() < 1e-10);
+        assert!((r.profit_abs - 0.078).abs() < 1e-10);
+        assert!((r.profit_bps - 0.078 / 1.90 * 10000.0).abs() < 1e-6);
+
+        // Threshold applies to the net edge
+        let cfg = EngineConfig {
+            min_profit_abs: 0.08,
+            min_profit_bps: 0.0,
+            ..EngineConfig::default()
+        };
+        assert!(calc_trio_profit_only(&trio, &pt, &cfg).is_none());
+    }
+
     #[test]
     fn test_calc_trio_profit_nan_input() {
         let mut pt = PriceTable::new();
@@ -426,10 +462,16 @@ mod tests {
             lower_no_token: "ln_token".to_string(),
             range_yes_token: "ry_token".to_string(),
             upper_yes_token: "uy_token".to_string(),
+            triangle_fee_bps: [0; 3],
+            complement_fee_bps: [0; 3],
             last_emitted_buy_ms: 0,
             last_emitted_complement_ms: 0,
             last_emitted_unbundle_ms: 0,
             last_emitted_bundle_ms: 0,
+            stale_skip_count: 0,
+            edge_stats: [EdgeStats::default(); StrategyKind::COUNT],
+            emission: [EmissionLatch::default(); StrategyKind::COUNT],
+            opportunity: [OpportunityTracker::default(); StrategyKind::COUNT],
         }
     }
 
@@ -446,6 +488,7 @@ mod tests {
             min_profit_abs: 0.005,
             min_profit_bps: 30.0,
             cooldown_ms: 3000,
+            ..EngineConfig::default()
         };
 
         let result = calc_complement_profit_only(&trio, &pt, &cfg);
@@ -8,7 +8,7 @@ use sha2::Sha256;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::types::order::SignedClobOrder;
+use crate::types::order::{OrderSide, SignedClobOrder};
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -34,6 +34,27 @@ pub struct OrderResponse {
     pub status: Option<String>,
     #[serde(rename = "errorMsg")]
     pub error_msg: Option<String>,
+    /// What the order gave at submission (USDC for BUY, shares for SELL), decimal string.
+    #[serde(rename = "makingAmount")]
+    pub making_amount: Option<String>,
+    /// What the order got at submission (shares for BUY, USDC for SELL), decimal string.
+    #[serde(rename = "takingAmount")]
+    pub taking_amount: Option<String>,
+}
+
+impl OrderResponse {
+    /// Immediate match of an accepted order as `(shares, average price)`.
+    /// None when nothing matched (e.g. the order rests on the book).
+    pub fn matched(&self, side: OrderSide) -> Option<(f64, f64)> {
+        let amount = |a: &Option<String>| -> f64 {
+            a.as_deref().and_then(|s| s.parse().ok()).unwrap_or(0.0)
+        };
+        let (usdc, shares) = match side {
+            OrderSide::Buy => (amount(&self.making_amount), amount(&self.taking_amount)),
+            OrderSide::Sell => (amount(&self.taking_amount), amount(&self.making_amount)),
+        };
+        (shares > 0.0).then(|| (shares, usdc / shares))
+    }
 }
 
 /// Batch order payload item (matches Polymarket API format).
@@ -323,6 +344,26 @@ mod tests {
         assert!(!sig.is_empty());
     }
 
+    #[test]
+    fn test_order_response_matched_amounts() {
+        let body = r#"[
+            {"orderID":"0x1","status":"matched","makingAmount":"4.9","takingAmount":"10"},
+            {"orderID":"0x2","status":"matched","makingAmount":"10","takingAmount":"5.2"},
+            {"orderID":"0x3","status":"live","makingAmount":"","takingAmount":""},
+            {"errorMsg":"not enough balance / allowance"}
+        ]"#;
+        let responses: Vec<OrderResponse> = serde_json::from_str(body).unwrap();
+
+        let (shares, price) = responses[0].matched(OrderSide::Buy).unwrap();
+        assert_eq!(shares, 10.0);
+        assert!((price - 0.49).abs() < 1e-12);
+        let (shares, price) = responses[1].matched(OrderSide::Sell).unwrap();
+        assert_eq!(shares, 10.0);
+        assert!((price - 0.52).abs() < 1e-12);
+        assert!(responses[2].matched(OrderSide::Buy).is_none());
+        assert!(responses[3].matched(OrderSide::Sell).is_none());
+    }
+
     #[test]
     fn test_hmac_deterministic() {
         let client = ClobApiClient::new(
@@ -2,25 +2,37 @@
 //!
 //! Signal → Validate → Prepare → Sign → POST → TradeResult callback
 //!
-//! Runs as a tokio task, receives signals via mpsc channel from the engine.
+//! In paper mode the POST is replaced by simulated fills against the live
+//! book (see `paper`).
+//!
+//! Runs as a tokio task, receives signals via mpsc channel from the engine and
+//! processes each on its own task — independent groups execute in parallel.
 
 pub mod api_client;
+pub mod paper;
 pub mod signer;
 pub mod validator;
 
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Instant;
 
 use tokio::sync::mpsc;
 
 use crate::bridge::callbacks::get_registry;
-use crate::types::order::{NapiExecutorConfigInput, NapiFailedOrder, NapiSuccessOrder, OrderSide, TradeResult};
-use crate::types::signal::ArbSignal;
+use crate::types::order::{
+    NapiExecutorConfigInput, NapiFailedOrder, NapiSuccessOrder, OrderSide, OrderType,
+    SignedClobOrder, TradeResult,
+};
+use crate::types::signal::{ArbSignal, Leg, LegRole, NapiSignalLeg};
+use crate::types::strategy::StrategyKind;
 
 use self::api_client::ClobApiClient;
+use self::paper::{realized_pnl, PaperFill, PaperState, QuoteSource, DEFAULT_PAPER_LATENCY_MS};
 use self::signer::SignerState;
-use self::validator::{ExecutorConfig, ValidationState};
+use self::validator::{
+    leg_cost, ExecutorConfig, OrderTypePolicy, OrderTypeRule, ValidationResult, ValidationState,
+    DEFAULT_MAX_CONCURRENT_EXECUTIONS, DEFAULT_MIN_TRADE_SIZE,
+};
 
 // =============================================================================
 // EXECUTOR STATE
@@ -33,10 +45,12 @@ pub struct ExecutorState {
     pub config: ExecutorConfig,
     pub validation: Arc<ValidationState>,
     pub api_key: String, // Used as `owner` in batch order payload
+    pub paper: Option<PaperState>, // Set in paper mode — orders are simulated, never POSTed
 }
 
 impl ExecutorState {
-    pub fn from_config(input: &NapiExecutorConfigInput) -> Result<Self, String> {
+    /// `quotes` feeds paper-mode fills; it is unused unless `paper_trading` is set.
+    pub fn from_config(input: &NapiExecutorConfigInput, quotes: QuoteSource) -> Result<Self, String> {
         let signer = SignerState::new(
             &input.private_key,
             &input.proxy_address,
@@ -59,24 +73,65 @@ impl ExecutorState {
         let config = ExecutorConfig {
             min_pnl_threshold_percent: input.min_pnl_threshold_percent,
             default_size: input.default_size,
+            min_size: input.min_trade_size.unwrap_or(DEFAULT_MIN_TRADE_SIZE),
             slippage_enabled: input.slippage_enabled,
             opportunity_timeout_ms: input.opportunity_timeout_ms.unwrap_or(5000) as u64,
+            max_concurrent: input
+                .max_concurrent_executions
+                .map_or(DEFAULT_MAX_CONCURRENT_EXECUTIONS, |n| n.max(1) as usize),
+            order_types: order_type_policy(input)?,
             maker_address: input.proxy_address.clone(),
             signer_address: input.signer_address.clone(),
         };
 
         let validation = Arc::new(ValidationState::new());
 
+        let paper = input.paper_trading.unwrap_or(false).then(|| {
+            let latency_ms = input
+                .paper_latency_ms
+                .map_or(DEFAULT_PAPER_LATENCY_MS, |ms| ms.max(0) as u64);
+            PaperState::new(latency_ms, quotes)
+        });
+
         Ok(Self {
             signer,
             api_client,
             config,
             validation,
             api_key: input.api_key.clone(),
+            paper,
         })
     }
 }
 
+/// Parse the order type default and per-strategy / per-leg rules.
+fn order_type_policy(input: &NapiExecutorConfigInput) -> Result<OrderTypePolicy, String> {
+    let parse_type = |s: &str| OrderType::parse(s).ok_or_else(|| format!("Unknown order type: {}", s));
+    let mut policy = OrderTypePolicy::default();
+    if let Some(ref default) = input.order_type {
+        policy.default = parse_type(default)?;
+    }
+    if let Some(ttl) = input.gtd_ttl_secs {
+        policy.gtd_ttl_secs = ttl.max(0) as u64;
+    }
+    for rule in input.order_type_rules.iter().flatten() {
+        let strategy = match rule.strategy {
+            Some(ref s) => Some(StrategyKind::parse(s).ok_or_else(|| format!("Unknown strategy: {}", s))?),
+            None => None,
+        };
+        let leg_role = match rule.leg_role {
+            Some(ref r) => Some(LegRole::parse(r).ok_or_else(|| format!("Unknown leg role: {}", r))?),
+            None => None,
+        };
+        policy.rules.push(OrderTypeRule {
+            strategy,
+            leg_role,
+            order_type: parse_type(&rule.order_type)?,
+        });
+    }
+    Ok(policy)
+}
+
 // =============================================================================
 // EXECUTOR LOOP — tokio task
 // =============================================================================
@@ -84,14 +139,16 @@ impl ExecutorState {
 /// Spawn the executor loop. Returns the sender for signals.
 pub fn spawn_executor(
     state: Arc<ExecutorState>,
-) -> mpsc::Sender<ArbSignal> {
-    let (tx, rx) = mpsc::channel::<ArbSignal>(16);
+) -> mpsc::Sender<Arc<ArbSignal>> {
+    let (tx, rx) = mpsc::channel::<Arc<ArbSignal>>(16);
 
     let runtime = crate::bridge::napi_exports::get_runtime();
     runtime.spawn(executor_loop(Arc::clone(&state), rx));
 
-    // Spawn background connection warmer to keep TCP+TLS alive
-    runtime.spawn(connection_warmer(Arc::clone(&state)));
+    // Spawn background connection warmer to keep TCP+TLS alive (paper mode never POSTs)
+    if state.paper.is_none() {
+        runtime.spawn(connection_warmer(Arc::clone(&state)));
+    }
 
     tx
 }
@@ -116,24 +173,35 @@ async fn connection_warmer(state: Arc<ExecutorState>) {
 /// Main executor loop — receives signals and processes them.
 async fn executor_loop(
     state: Arc<ExecutorState>,
-    mut rx: mpsc::Receiver<ArbSignal>,
+    mut rx: mpsc::Receiver<Arc<ArbSignal>>,
 ) {
     tracing::info!("[Executor] Loop started, waiting for signals...");
 
     while let Some(signal) = rx.recv().await {
+        // Busy or cooling-down groups and a full concurrency cap are dropped
+        // here, before a task is spawned; `should_skip` re-checks atomically
+        let now_ms = validator::get_epoch_ms();
+        if let Some(reason) =
+            state.validation.execution_blocked(&signal.group_key, now_ms, &state.config)
+        {
+            tracing::debug!("[Executor] Skip signal {}: {:?}", signal.group_key, reason);
+            continue;
+        }
         let state = Arc::clone(&state);
-        // Process each signal sequentially (is_submitting guard ensures this)
-        process_signal(&state, signal).await;
+        // Groups execute in parallel; per-group leases and the global cap gate each task
+        tokio::spawn(async move {
+            process_signal(&state, signal).await;
+        });
     }
 
     tracing::warn!("[Executor] Loop ended — channel closed");
 }
 
 /// Process a single signal: validate → prepare → sign → POST → callback.
-async fn process_signal(state: &ExecutorState, signal: ArbSignal) {
+async fn process_signal(state: &ExecutorState, signal: Arc<ArbSignal>) {
     let start = Instant::now();
 
-    // === VALIDATE ===
+    // === VALIDATE: claims the group's slot and reserves the batch's USDC ===
     let validation_result = match validator::should_skip(&signal, &state.validation, &state.config)
     {
         Ok(result) => result,
@@ -147,39 +215,8 @@ async fn process_signal(state: &ExecutorState, signal: ArbSignal) {
         }
     };
 
-    // === LOCK: Mark as submitting ===
-    if state
-        .validation
-        .is_submitting
-        .compare_exchange(false, true, Ordering::SeqCst, Ordering::Relaxed)
-        .is_err()
-    {
-        tracing::debug!("[Executor] Already submitting, skip");
-        return;
-    }
-
-    // === OPTIMISTIC BALANCE DEDUCTION ===
-    if !state
-        .validation
-        .try_deduct_balance(validation_result.required_cost)
-    {
-        state
-            .validation
-            .is_submitting
-            .store(false, Ordering::Release);
-        tracing::debug!("[Executor] Insufficient balance after deduction attempt");
-        return;
-    }
-
-    // === UPDATE: Mark execution timestamp ===
-    let now_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_millis() as u64;
-    state
-        .validation
-        .last_executed_at
-        .store(now_ms, Ordering::Relaxed);
+    // === UPDATE: Start the group's cooldown ===
+    validation_result.lease.mark_executed(validator::get_epoch_ms());
 
     // === PREPARE ORDERS ===
     let orders = validator::prepare_batch_orders(
@@ -201,14 +238,7 @@ async fn process_signal(state: &ExecutorState, signal: ArbSignal) {
         Err(e) => {
             tracing::error!("[Executor] Signing failed: {}", e);
             // Restore balance
-            let balance = state.validation.get_balance();
-            state
-                .validation
-                .set_balance(balance + validation_result.required_cost);
-            state
-                .validation
-                .is_submitting
-                .store(false, Ordering::Release);
+            state.validation.refund_balance(validation_result.required_cost);
             return;
         }
     };
@@ -220,42 +250,195 @@ async fn process_signal(state: &ExecutorState, signal: ArbSignal) {
         sign_elapsed.as_micros()
     );
 
-    // === HTTP POST ===
-    let post_result = state
-        .api_client
-        .post_batch_orders(&signed_orders, &state.api_key)
-        .await;
+    // === SUBMIT: HTTP POST, or simulated fills in paper mode ===
+    let submission = match &state.paper {
+        Some(paper) => {
+            submit_paper(state, paper, &signal, &validation_result, &signed_orders).await
+        }
+        None => submit_live(state, &signal, &validation_result, &signed_orders).await,
+    };
 
     let total_elapsed = start.elapsed();
 
     // === UNLOCK ===
-    state
-        .validation
-        .is_submitting
-        .store(false, Ordering::Release);
+    drop(validation_result.lease);
 
     // === BUILD TRADE RESULT ===
+    let Submission {
+        posted,
+        order_ids,
+        successful_orders,
+        failed_orders,
+        realized_pnl,
+    } = submission;
+    let expected_pnl = validation_result.size * signal.profit_abs;
+
+    // Snapshot of the signal — first leg of each fixed role, empty when absent
+    let parent = signal.legs_with_role(LegRole::Parent).next();
+    let upper = signal.legs_with_role(LegRole::ParentUpper).next();
+    let child = signal.legs_with_role(LegRole::Child).next();
+    let asset_id = |l: Option<&Leg>| l.map_or_else(String::new, |l| l.asset_id.clone());
+    let market_slug = |l: Option<&Leg>| l.map_or_else(String::new, |l| l.market_slug.clone());
+    let trade_result = TradeResult {
+        success: posted && !order_ids.is_empty(),
+        order_ids,
+        successful_orders,
+        failed_orders,
+        total_cost: validation_result.required_cost,
+        expected_pnl,
+        realized_pnl,
+        latency_us: total_elapsed.as_micros() as i64,
+        signal_group_key: signal.group_key.clone(),
+        signal_event_slug: signal.event_slug.clone(),
+        signal_crypto: signal.crypto.clone(),
+        signal_strategy: signal.strategy.signal_name().to_string(),
+        signal_profit_abs: signal.profit_abs,
+        signal_profit_bps: signal.profit_bps,
+        signal_timestamp_ms: signal.timestamp_ms,
+
+        // Snapshot: Parent
+        signal_parent_asset_id: asset_id(parent),
+        signal_parent_market_slug: market_slug(parent),
+        signal_parent_best_bid: parent.map(|l| l.best_bid),
+        signal_parent_best_ask: parent.map(|l| l.best_ask),
+        signal_parent_best_bid_size: parent.map(|l| l.best_bid_size),
+        signal_parent_best_ask_size: parent.map(|l| l.best_ask_size),
+        signal_parent_neg_risk: parent.is_some_and(|l| l.neg_risk),
+
+        // Snapshot: Parent Upper
+        signal_parent_upper_asset_id: asset_id(upper),
+        signal_parent_upper_market_slug: market_slug(upper),
+        signal_parent_upper_best_bid: upper.map(|l| l.best_bid),
+        signal_parent_upper_best_ask: upper.map(|l| l.best_ask),
+        signal_parent_upper_best_bid_size: upper.map(|l| l.best_bid_size),
+        signal_parent_upper_best_ask_size: upper.map(|l| l.best_ask_size),
+        signal_parent_upper_neg_risk: upper.is_some_and(|l| l.neg_risk),
+
+        // Snapshot: Child
+        signal_child_asset_id: asset_id(child),
+        signal_child_market_slug: market_slug(child),
+        signal_child_best_bid: child.map(|l| l.best_bid),
+        signal_child_best_ask: child.map(|l| l.best_ask),
+        signal_child_best_bid_size: child.map(|l| l.best_bid_size),
+        signal_child_best_ask_size: child.map(|l| l.best_ask_size),
+        signal_child_neg_risk: child.is_some_and(|l| l.neg_risk),
+        signal_child_index: signal.child_index,
+
+        // Aggregates
+        signal_children_sum_ask: signal.children_sum(|l| l.best_ask),
+        signal_children_sum_bid: signal.children_sum(|l| l.best_bid),
+
+        // Triangle context
+        signal_triangle_total_cost: signal.total_cost,
+        signal_triangle_total_bid: signal.total_bid,
+        signal_triangle_payout: signal.payout,
+        signal_triangle_mode: signal.mode.map(str::to_string),
+
+        signal_reason: signal.reason.to_string(),
+        signal_legs: signal.legs.iter().map(NapiSignalLeg::from).collect(),
+        paper: state.paper.is_some(),
+    };
+
+    tracing::info!(
+        "[{}] [{}] orders={} failed={} | validate+prepare={:.0}µs sign={:.0}µs post={:.0}µs total={:.1}ms | PnL: ${:.4} (filled ${:.4})",
+        if trade_result.paper { "PAPER" } else { "EXEC" },
+        if trade_result.success { "OK" } else { "FAIL" },
+        trade_result.order_ids.len(),
+        trade_result.failed_orders.len(),
+        prepare_elapsed.as_micros(),
+        (sign_elapsed - prepare_elapsed).as_micros(),
+        (total_elapsed - sign_elapsed).as_micros(),
+        total_elapsed.as_secs_f64() * 1000.0,
+        trade_result.expected_pnl,
+        trade_result.realized_pnl,
+    );
+
+    // === CALLBACK TO NODE.JS ===
+    let registry = get_registry();
+    registry.emit_trade_result(trade_result).await;
+}
+
+// =============================================================================
+// SUBMISSION — Live POST or paper fills
+// =============================================================================
+
+/// Outcome of submitting one signal's signed orders.
+struct Submission {
+    /// Batch accepted by the CLOB (always true in paper mode).
+    posted: bool,
+    order_ids: Vec<String>,
+    successful_orders: Vec<NapiSuccessOrder>,
+    failed_orders: Vec<NapiFailedOrder>,
+    /// PnL of what filled at submission.
+    realized_pnl: f64,
+}
+
+/// POST the batch and book each order's status and immediate match from the response.
+/// USDC reserved for BUY size that was rejected or killed (FOK/FAK) goes back.
+async fn submit_live(
+    state: &ExecutorState,
+    signal: &ArbSignal,
+    validation_result: &ValidationResult<'_>,
+    signed_orders: &[SignedClobOrder],
+) -> Submission {
+    let order_types: Vec<String> = validation_result
+        .candidates
+        .iter()
+        .map(|c| c.order_type.as_str().to_string())
+        .collect();
+    let post_result = state
+        .api_client
+        .post_batch_orders_with_types(signed_orders, &state.api_key, &order_types)
+        .await;
+
     let mut order_ids = Vec::new();
     let mut successful_orders = Vec::new();
     let mut failed_orders = Vec::new();
+    let mut fills: Vec<Option<PaperFill>> = vec![None; validation_result.candidates.len()];
+    // USDC reserved for BUY size that will never fill
+    let mut unspent = 0.0;
 
     if post_result.success {
         for (i, resp) in post_result.responses.iter().enumerate() {
             let candidate = validation_result.candidates.get(i);
             if let Some(ref oid) = resp.order_id {
                 order_ids.push(oid.clone());
-                // Track successful order details for notification
+                // Book what matched at submission, as reported per order
                 if let Some(c) = candidate {
+                    let matched = resp.matched(c.side);
+                    fills[i] = matched.map(|(size, price)| PaperFill { price, size });
                     successful_orders.push(NapiSuccessOrder {
                         token_id: c.token_id.clone(),
                         market_slug: c.market_slug.clone(),
                         side: c.side.as_str().to_string(),
-                        price: c.price,
-                        size: validation_result.size,
+                        price: matched.map_or(c.price, |(_, price)| price),
+                        size: matched.map_or(0.0, |(size, _)| size),
                         neg_risk: c.neg_risk,
+                        order_type: c.order_type.as_str().to_string(),
+                        status: resp.status.clone().unwrap_or_default(),
                     });
+                    // Minted inventory leaves with the match — or all of it
+                    // sits on the book behind an order that is not done yet
+                    if c.side == OrderSide::Sell {
+                        let committed = match resp.status.as_deref() {
+                            Some("matched") => matched.map_or(0.0, |(size, _)| size),
+                            _ => validation_result.size,
+                        };
+                        state
+                            .validation
+                            .deduct_minted(&signal.group_key, &c.token_id, committed);
+                    }
+                    // FOK/FAK remainders are killed; GTC/GTD ones rest on the book
+                    if c.order_type.is_immediate() {
+                        let unmatched =
+                            (validation_result.size - matched.map_or(0.0, |(size, _)| size)).max(0.0);
+                        unspent += leg_cost(c, unmatched, state.config.slippage_enabled);
+                    }
                 }
             } else {
+                if let Some(c) = candidate {
+                    unspent += leg_cost(c, validation_result.size, state.config.slippage_enabled);
+                }
                 failed_orders.push(NapiFailedOrder {
                     token_id: candidate.map(|c| c.token_id.clone()).unwrap_or_default(),
                     market_slug: candidate.map(|c| c.market_slug.clone()).unwrap_or_default(),
@@ -263,6 +446,9 @@ async fn process_signal(state: &ExecutorState, signal: ArbSignal) {
                         .map(|c| c.side.as_str().to_string())
                         .unwrap_or_default(),
                     price: candidate.map(|c| c.price).unwrap_or(0.0),
+                    order_type: candidate
+                        .map(|c| c.order_type.as_str().to_string())
+                        .unwrap_or_default(),
                     error_msg: resp
                         .error_msg
                         .clone()
@@ -270,103 +456,94 @@ async fn process_signal(state: &ExecutorState, signal: ArbSignal) {
                 });
             }
         }
-
-        // Deduct minted assets for successful SELL legs
-        for (i, candidate) in validation_result.candidates.iter().enumerate() {
-            if candidate.side == OrderSide::Sell {
-                // Check if this order succeeded (has order_id in response)
-                let succeeded = post_result.responses.get(i)
-                    .and_then(|r| r.order_id.as_ref())
-                    .is_some();
-                if succeeded {
-                    state.validation.deduct_minted(
-                        &signal.group_key,
-                        &candidate.token_id,
-                        validation_result.size,
-                    );
-                }
-            }
-        }
     } else {
         // Restore balance on total failure
-        let balance = state.validation.get_balance();
-        state
-            .validation
-            .set_balance(balance + validation_result.required_cost);
+        unspent = validation_result.required_cost;
     }
+    state
+        .validation
+        .refund_balance(unspent.min(validation_result.required_cost));
 
-    let actual_pnl = validation_result.size * signal.profit_abs;
-
-    let trade_result = TradeResult {
-        success: post_result.success && !order_ids.is_empty(),
+    Submission {
+        posted: post_result.success,
         order_ids,
         successful_orders,
         failed_orders,
-        total_cost: validation_result.required_cost,
-        expected_pnl: actual_pnl,
-        latency_us: total_elapsed.as_micros() as i64,
-        signal_group_key: signal.group_key.clone(),
-        signal_event_slug: signal.event_slug.clone(),
-        signal_crypto: signal.crypto.clone(),
-        signal_strategy: signal.strategy.clone(),
-        signal_profit_abs: signal.profit_abs,
-        signal_profit_bps: signal.profit_bps,
-        signal_timestamp_ms: signal.timestamp_ms,
-
-        // Snapshot: Parent
-        signal_parent_asset_id: signal.parent_asset_id.clone(),
-        signal_parent_market_slug: signal.parent_market_slug.clone(),
-        signal_parent_best_bid: signal.parent_best_bid,
-        signal_parent_best_ask: signal.parent_best_ask,
-        signal_parent_best_bid_size: signal.parent_best_bid_size,
-        signal_parent_best_ask_size: signal.parent_best_ask_size,
-        signal_parent_neg_risk: signal.parent_neg_risk,
-
-        // Snapshot: Parent Upper
-        signal_parent_upper_asset_id: signal.parent_upper_asset_id.clone(),
-        signal_parent_upper_market_slug: signal.parent_upper_market_slug.clone(),
-        signal_parent_upper_best_bid: signal.parent_upper_best_bid,
-        signal_parent_upper_best_ask: signal.parent_upper_best_ask,
-        signal_parent_upper_best_bid_size: signal.parent_upper_best_bid_size,
-        signal_parent_upper_best_ask_size: signal.parent_upper_best_ask_size,
-        signal_parent_upper_neg_risk: signal.parent_upper_neg_risk,
-
-        // Snapshot: Child
-        signal_child_asset_id: signal.child_asset_id.clone(),
-        signal_child_market_slug: signal.child_market_slug.clone(),
-        signal_child_best_bid: signal.child_best_bid,
-        signal_child_best_ask: signal.child_best_ask,
-        signal_child_best_bid_size: signal.child_best_bid_size,
-        signal_child_best_ask_size: signal.child_best_ask_size,
-        signal_child_neg_risk: signal.child_neg_risk,
-        signal_child_index: signal.child_index,
+        realized_pnl: realized_pnl(&validation_result.candidates, &fills, signal.profit_abs),
+    }
+}
 
-        // Aggregates
-        signal_children_sum_ask: signal.children_sum_ask,
-        signal_children_sum_bid: signal.children_sum_bid,
+/// Wait out the simulated latency, then fill every order against the live book.
+///
+/// Balance and minted assets move exactly as for a live submission, so the
+/// validator sees the same constraints; paper order IDs carry the signed salt.
+async fn submit_paper(
+    state: &ExecutorState,
+    paper: &PaperState,
+    signal: &ArbSignal,
+    validation_result: &ValidationResult<'_>,
+    signed_orders: &[SignedClobOrder],
+) -> Submission {
+    if paper.latency_ms > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(paper.latency_ms)).await;
+    }
 
-        // Triangle context
-        signal_triangle_total_cost: signal.triangle_total_cost,
-        signal_triangle_total_bid: signal.triangle_total_bid,
-        signal_triangle_payout: signal.triangle_payout,
-        signal_triangle_mode: signal.triangle_mode.clone(),
+    let exec = paper.execute(
+        &validation_result.candidates,
+        validation_result.size,
+        state.config.slippage_enabled,
+        signal.profit_abs,
+    );
 
-        signal_reason: signal.reason.clone(),
-    };
+    let mut order_ids = Vec::new();
+    let mut successful_orders = Vec::new();
+    let mut failed_orders = Vec::new();
+    // Paper orders never rest — whatever did not fill is unspent
+    let mut unspent = 0.0;
+
+    for (i, (c, fill)) in validation_result.candidates.iter().zip(&exec.fills).enumerate() {
+        let filled = fill.as_ref().map_or(0.0, |f| f.size);
+        unspent += leg_cost(c, (validation_result.size - filled).max(0.0), state.config.slippage_enabled);
+        match fill {
+            Ok(fill) => {
+                let salt = signed_orders.get(i).map_or(0, |o| o.salt);
+                order_ids.push(format!("paper-{}", salt));
+                successful_orders.push(NapiSuccessOrder {
+                    token_id: c.token_id.clone(),
+                    market_slug: c.market_slug.clone(),
+                    side: c.side.as_str().to_string(),
+                    price: fill.price,
+                    size: fill.size,
+                    neg_risk: c.neg_risk,
+                    order_type: c.order_type.as_str().to_string(),
+                    status: "matched".to_string(),
+                });
+                if c.side == OrderSide::Sell {
+                    state
+                        .validation
+                        .deduct_minted(&signal.group_key, &c.token_id, fill.size);
+                }
+            }
+            Err(reason) => failed_orders.push(NapiFailedOrder {
+                token_id: c.token_id.clone(),
+                market_slug: c.market_slug.clone(),
+                side: c.side.as_str().to_string(),
+                price: c.price,
+                order_type: c.order_type.as_str().to_string(),
+                error_msg: (*reason).to_string(),
+            }),
+        }
+    }
 
-    tracing::info!(
-        "[EXEC] [{}] orders={} failed={} | validate+prepare={:.0}µs sign={:.0}µs post={:.0}µs total={:.1}ms | PnL: ${:.4}",
-        if trade_result.success { "OK" } else { "FAIL" },
-        trade_result.order_ids.len(),
-        trade_result.failed_orders.len(),
-        prepare_elapsed.as_micros(),
-        (sign_elapsed - prepare_elapsed).as_micros(),
-        (total_elapsed - sign_elapsed).as_micros(),
-        total_elapsed.as_secs_f64() * 1000.0,
-        actual_pnl,
-    );
+    state
+        .validation
+        .refund_balance(unspent.min(validation_result.required_cost));
 
-    // === CALLBACK TO NODE.JS ===
-    let registry = get_registry();
-    registry.emit_trade_result(trade_result).await;
+    Submission {
+        posted: true,
+        order_ids,
+        successful_orders,
+        failed_orders,
+        realized_pnl: exec.pnl,
+    }
 }
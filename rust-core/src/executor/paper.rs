@@ -0,0 +1,359 @@
+//! Paper trading — simulated fills for the executor's paper mode.
+//!
+//! Paper mode runs the same validate → prepare → sign pipeline as live trading
+//! but never POSTs. After `latency_ms` each signed order is matched against the
+//! engine's live top of book (the engine keeps no L2 book), and the fills are
+//! booked into an in-memory ledger of positions and PnL.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::engine::state::{taker_fee, PriceSlot};
+use crate::types::order::{
+    NapiPaperPortfolio, NapiPaperPosition, OrderCandidate, OrderSide, OrderType,
+};
+
+use super::validator::order_limit;
+
+/// Default delay between signing and the simulated match (mirrors JS paper mode).
+pub const DEFAULT_PAPER_LATENCY_MS: u64 = 260;
+
+/// Live top-of-book lookup by token ID — the engine snapshot in production.
+pub type QuoteSource = Arc<dyn Fn(&str) -> Option<PriceSlot> + Send + Sync>;
+
+// =============================================================================
+// FILL SIMULATION
+// =============================================================================
+
+/// One simulated order fill.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaperFill {
+    pub price: f64,
+    pub size: f64,
+}
+
+/// PnL of a batch: the signal's per-set edge, adjusted by each leg's price and
+/// fee drift, times the size filled on every leg (None = the leg did not fill).
+pub fn realized_pnl(
+    candidates: &[OrderCandidate],
+    fills: &[Option<PaperFill>],
+    profit_per_set: f64,
+) -> f64 {
+    let matched = fills
+        .iter()
+        .map(|f| f.map_or(0.0, |f| f.size))
+        .fold(f64::INFINITY, f64::min)
+        .max(0.0);
+    let drift: f64 = candidates
+        .iter()
+        .zip(fills)
+        .filter_map(|(c, f)| f.map(|f| (c, f)))
+        .map(|(c, f)| {
+            let price_drift = match c.side {
+                OrderSide::Buy => c.price - f.price,
+                OrderSide::Sell => f.price - c.price,
+            };
+            price_drift + taker_fee(c.price, c.fee_rate_bps) - taker_fee(f.price, c.fee_rate_bps)
+        })
+        .sum();
+    if matched.is_finite() && matched > 0.0 {
+        matched * (profit_per_set + drift)
+    } else {
+        0.0
+    }
+}
+
+/// Match one limit order against the top of book.
+///
+/// BUY fills at the ask while `ask <= limit`, SELL at the bid while `bid >= limit`.
+/// Size is capped at the top-of-book size when the book reports one (0 = unknown);
+/// FOK orders that would only partly fill are killed instead.
+pub fn simulate_fill(
+    side: OrderSide,
+    order_type: OrderType,
+    limit_price: f64,
+    size: f64,
+    quote: Option<&PriceSlot>,
+) -> Result<PaperFill, &'static str> {
+    let q = quote.ok_or("No live quote")?;
+    let (price, depth) = match side {
+        OrderSide::Buy => (q.best_ask, q.best_ask_size),
+        OrderSide::Sell => (q.best_bid, q.best_bid_size),
+    };
+    if !price.is_finite() {
+        return Err("Empty book side");
+    }
+    let crosses = match side {
+        OrderSide::Buy => price <= limit_price,
+        OrderSide::Sell => price >= limit_price,
+    };
+    if !crosses {
+        return Err("Book moved through limit");
+    }
+    if depth > 0.0 && depth < size && order_type == OrderType::Fok {
+        return Err("FOK not fully fillable");
+    }
+    let size = if depth > 0.0 { size.min(depth) } else { size };
+    Ok(PaperFill { price, size })
+}
+
+// =============================================================================
+// PAPER LEDGER — Positions and PnL
+// =============================================================================
+
+/// Net paper holding of one token.
+#[derive(Debug, Clone, Default)]
+pub struct PaperPosition {
+    pub market_slug: String,
+    /// Net shares (negative after selling minted shares).
+    pub size: f64,
+    /// Net USDC spent, fees included.
+    pub cost: f64,
+}
+
+/// Paper positions plus running totals.
+#[derive(Debug, Default)]
+pub struct PaperLedger {
+    positions: HashMap<String, PaperPosition>,
+    /// Edge locked in by fully matched sets, at fill prices, net of fees.
+    pnl: f64,
+    fees_paid: f64,
+    trade_count: u64,
+    fill_count: u64,
+}
+
+impl PaperLedger {
+    /// Book one fill into the token's position.
+    fn book_fill(&mut self, c: &OrderCandidate, fill: PaperFill) {
+        let fee = taker_fee(fill.price, c.fee_rate_bps) * fill.size;
+        let notional = fill.price * fill.size;
+        let pos = self
+            .positions
+            .entry(c.token_id.clone())
+            .or_insert_with(|| PaperPosition {
+                market_slug: c.market_slug.clone(),
+                ..Default::default()
+            });
+        match c.side {
+            OrderSide::Buy => {
+                pos.size += fill.size;
+                pos.cost += notional + fee;
+            }
+            OrderSide::Sell => {
+                pos.size -= fill.size;
+                pos.cost -= notional - fee;
+            }
+        }
+        self.fees_paid += fee;
+        self.fill_count += 1;
+    }
+
+    pub fn pnl(&self) -> f64 {
+        self.pnl
+    }
+
+    pub fn position(&self, token_id: &str) -> Option<&PaperPosition> {
+        self.positions.get(token_id)
+    }
+
+    /// N-API projection of the ledger.
+    pub fn to_napi(&self) -> NapiPaperPortfolio {
+        NapiPaperPortfolio {
+            positions: self
+                .positions
+                .iter()
+                .map(|(token_id, p)| NapiPaperPosition {
+                    token_id: token_id.clone(),
+                    market_slug: p.market_slug.clone(),
+                    size: p.size,
+                    cost: p.cost,
+                })
+                .collect(),
+            pnl: self.pnl,
+            fees_paid: self.fees_paid,
+            trade_count: self.trade_count as i64,
+            fill_count: self.fill_count as i64,
+        }
+    }
+}
+
+// =============================================================================
+// PAPER STATE
+// =============================================================================
+
+/// Outcome of one simulated submission — one fill result per candidate.
+pub struct PaperExecution {
+    pub fills: Vec<Result<PaperFill, &'static str>>,
+    pub pnl: f64,
+}
+
+/// Paper-mode executor state: quote source, latency and ledger.
+pub struct PaperState {
+    pub latency_ms: u64,
+    quotes: QuoteSource,
+    ledger: Mutex<PaperLedger>,
+}
+
+impl PaperState {
+    pub fn new(latency_ms: u64, quotes: QuoteSource) -> Self {
+        Self {
+            latency_ms,
+            quotes,
+            ledger: Mutex::new(PaperLedger::default()),
+        }
+    }
+
+    /// Match every candidate against the live book and book the fills.
+    ///
+    /// Limits are the prices `prepare_batch_orders` signs (slippage applied when
+    /// enabled). PnL comes from `realized_pnl`; unmatched remainders stay in the
+    /// positions as open exposure.
+    pub fn execute(
+        &self,
+        candidates: &[OrderCandidate],
+        size: f64,
+        slippage_enabled: bool,
+        profit_per_set: f64,
+    ) -> PaperExecution {
+        let fills: Vec<_> = candidates
+            .iter()
+            .map(|c| {
+                let limit = order_limit(c, slippage_enabled);
+                simulate_fill(c.side, c.order_type, limit, size, (self.quotes)(&c.token_id).as_ref())
+            })
+            .collect();
+
+        let matched: Vec<Option<PaperFill>> = fills.iter().map(|f| f.ok()).collect();
+        let pnl = realized_pnl(candidates, &matched, profit_per_set);
+
+        let mut ledger = self.ledger.lock().unwrap();
+        for (c, f) in candidates.iter().zip(&fills) {
+            if let Ok(f) = f {
+                ledger.book_fill(c, *f);
+            }
+        }
+        if fills.iter().any(Result::is_ok) {
+            ledger.pnl += pnl;
+            ledger.trade_count += 1;
+        }
+
+        PaperExecution { fills, pnl }
+    }
+
+    /// Snapshot of positions and totals.
+    pub fn portfolio(&self) -> NapiPaperPortfolio {
+        self.ledger.lock().unwrap().to_napi()
+    }
+
+    /// Drop all positions and zero the totals.
+    pub fn reset(&self) {
+        *self.ledger.lock().unwrap() = PaperLedger::default();
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(bid: f64, ask: f64, bid_size: f64, ask_size: f64) -> PriceSlot {
+        PriceSlot {
+            best_bid: bid,
+            best_ask: ask,
+            best_bid_size: bid_size,
+            best_ask_size: ask_size,
+            timestamp_ms: 1,
+        }
+    }
+
+    fn candidate(token: &str, side: OrderSide, price: f64) -> OrderCandidate {
+        OrderCandidate {
+            token_id: token.into(),
+            market_slug: format!("{}-slug", token),
+            price,
+            side,
+            orderbook_size: None,
+            neg_risk: false,
+            fee_rate_bps: 0,
+            order_type: OrderType::Gtc,
+        }
+    }
+
+    fn paper_with(book: Vec<(&'static str, PriceSlot)>) -> PaperState {
+        let book: HashMap<&'static str, PriceSlot> = book.into_iter().collect();
+        PaperState::new(0, Arc::new(move |t: &str| book.get(t).copied()))
+    }
+
+    #[test]
+    fn test_simulate_fill_crosses_and_caps_at_depth() {
+        let q = quote(0.40, 0.42, 30.0, 8.0);
+        let buy = simulate_fill(OrderSide::Buy, OrderType::Gtc, 0.43, 10.0, Some(&q)).unwrap();
+        assert_eq!(buy, PaperFill { price: 0.42, size: 8.0 });
+        let sell = simulate_fill(OrderSide::Sell, OrderType::Gtc, 0.40, 10.0, Some(&q)).unwrap();
+        assert_eq!(sell, PaperFill { price: 0.40, size: 10.0 });
+        // FOK is all-or-nothing; FAK takes the partial
+        assert!(simulate_fill(OrderSide::Buy, OrderType::Fok, 0.43, 10.0, Some(&q)).is_err());
+        assert_eq!(simulate_fill(OrderSide::Buy, OrderType::Fak, 0.43, 10.0, Some(&q)).unwrap().size, 8.0);
+
+        // Unknown depth fills the full size
+        let q = quote(0.40, 0.42, 0.0, 0.0);
+        assert_eq!(simulate_fill(OrderSide::Buy, OrderType::Gtc, 0.42, 10.0, Some(&q)).unwrap().size, 10.0);
+
+        assert!(simulate_fill(OrderSide::Buy, OrderType::Gtc, 0.41, 10.0, Some(&q)).is_err());
+        assert!(simulate_fill(OrderSide::Sell, OrderType::Gtc, 0.41, 10.0, Some(&q)).is_err());
+        assert!(simulate_fill(OrderSide::Buy, OrderType::Gtc, 0.50, 10.0, None).is_err());
+        assert!(simulate_fill(OrderSide::Buy, OrderType::Gtc, 0.50, 10.0, Some(&PriceSlot::default())).is_err());
+    }
+
+    #[test]
+    fn test_execute_books_positions_and_matched_pnl() {
+        // Signal: buy YES@0.45 + NO@0.50 → 0.05 edge per set; YES ask improved to 0.44
+        let paper = paper_with(vec![
+            ("yes", quote(0.43, 0.44, 100.0, 100.0)),
+            ("no", quote(0.49, 0.50, 100.0, 6.0)),
+        ]);
+        let candidates = vec![
+            candidate("yes", OrderSide::Buy, 0.45),
+            candidate("no", OrderSide::Buy, 0.50),
+        ];
+
+        let exec = paper.execute(&candidates, 10.0, false, 0.05);
+        assert_eq!(exec.fills[0].unwrap().size, 10.0);
+        assert_eq!(exec.fills[1].unwrap().size, 6.0);
+        // 6 matched sets × (0.05 + 0.01 price improvement)
+        assert!((exec.pnl - 0.36).abs() < 1e-9);
+
+        let ledger = paper.ledger.lock().unwrap();
+        let yes = ledger.position("yes").unwrap();
+        assert_eq!(yes.size, 10.0);
+        assert!((yes.cost - 4.4).abs() < 1e-9);
+        assert_eq!(ledger.position("no").unwrap().size, 6.0);
+        assert!((ledger.pnl() - 0.36).abs() < 1e-9);
+        drop(ledger);
+
+        let portfolio = paper.portfolio();
+        assert_eq!(portfolio.trade_count, 1);
+        assert_eq!(portfolio.fill_count, 2);
+        paper.reset();
+        assert!(paper.portfolio().positions.is_empty());
+    }
+
+    #[test]
+    fn test_execute_unmatched_leg_books_no_pnl() {
+        let paper = paper_with(vec![("yes", quote(0.43, 0.44, 100.0, 100.0))]);
+        let candidates = vec![
+            candidate("yes", OrderSide::Buy, 0.45),
+            candidate("no", OrderSide::Buy, 0.50),
+        ];
+
+        let exec = paper.execute(&candidates, 10.0, false, 0.05);
+        assert!(exec.fills[0].is_ok());
+        assert!(exec.fills[1].is_err());
+        assert_eq!(exec.pnl, 0.0);
+        // The filled leg stays as open exposure
+        assert_eq!(paper.portfolio().positions.len(), 1);
+    }
+}
@@ -104,7 +104,7 @@ impl SignerState {
             let maker_amount = parse_u256(&order.maker_amount)?;
             let taker_amount = parse_u256(&order.taker_amount)?;
             let fee_rate_bps = U256::from(order.fee_rate_bps);
-            let expiration = U256::zero();
+            let expiration = U256::from(order.expiration);
             let nonce = U256::zero();
 
             // Pick pre-computed domain separator
@@ -157,7 +157,7 @@ impl SignerState {
                 maker_amount: order.maker_amount.clone(),
                 taker_amount: order.taker_amount.clone(),
                 side: if order.side == 0 { "BUY".to_string() } else { "SELL".to_string() },
-                expiration: "0".to_string(),
+                expiration: order.expiration.to_string(),
                 nonce: "0".to_string(),
                 fee_rate_bps: order.fee_rate_bps.to_string(),
                 signature_type: SIGNATURE_TYPE_POLY_GNOSIS_SAFE,
@@ -211,6 +211,7 @@ fn compute_domain_separator(neg_risk: bool) -> [u8; 32] {
     keccak256(&encoded)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn compute_order_struct_hash(
     salt: U256,
     maker: Address,
@@ -334,9 +335,10 @@ mod tests {
             side: 0,
             neg_risk: false,
             fee_rate_bps: 0,
+            expiration: 0,
         };
 
-        let result1 = state.sign_batch_orders(&[order.clone()]).unwrap();
+        let result1 = state.sign_batch_orders(std::slice::from_ref(&order)).unwrap();
         let result2 = state.sign_batch_orders(&[order]).unwrap();
 
         // Same inputs should produce same signature
@@ -363,6 +365,7 @@ mod tests {
             side: 0,          // BUY
             neg_risk: false,
             fee_rate_bps: 0,
+            expiration: 0,
         };
 
         let result = state.sign_batch_orders(&[order]).unwrap();
@@ -417,6 +420,7 @@ mod tests {
             side: 0, // BUY
             neg_risk: true,
             fee_rate_bps: 0,
+            expiration: 0,
         };
 
         // ─── Build OrderToSign: Executor style (validator.rs logic) ───
@@ -441,6 +445,7 @@ mod tests {
             side: 0, // BUY
             neg_risk: true,
             fee_rate_bps: 0,
+            expiration: 0,
         };
 
         // ─── Verify: amounts must be identical ───
@@ -505,6 +510,7 @@ mod tests {
             side: 1,
             neg_risk: false,
             fee_rate_bps: 0,
+            expiration: 0,
         };
 
         // ─── Executor style (SELL) ───
@@ -519,6 +525,7 @@ mod tests {
             side: 1,
             neg_risk: false,
             fee_rate_bps: 0,
+            expiration: 0,
         };
 
         let signed_api = state.sign_batch_orders(&[order_api]).unwrap();
@@ -1,17 +1,20 @@
 //! Order validation and preparation — port of RealExecutionService logic.
 //!
 //! Handles:
-//! - `should_skip()`: balance, cooldown, PnL threshold checks
+//! - `should_skip()`: PnL threshold checks, then the per-group lease and the balance reservation
+//! - `SizeCaps`: depth / max / balance / minted-aware trade sizing
 //! - `apply_slippage()`: price adjustment for BUY/SELL
 //! - `prepare_batch_orders()`: build OrderToSign from signal candidates
 
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::types::order::{OrderCandidate, OrderSide, OrderToSign};
-use crate::types::signal::ArbSignal;
+use crate::engine::state::taker_fee;
+use crate::types::order::{OrderCandidate, OrderSide, OrderToSign, OrderType};
+use crate::types::signal::{ArbSignal, LegRole};
+use crate::types::strategy::StrategyKind;
 
 // =============================================================================
 // CONSTANTS (ported from real-execution.service.ts)
@@ -21,6 +24,18 @@ use crate::types::signal::ArbSignal;
 const MAX_PRICE: f64 = 0.99;
 const MIN_PRICE: f64 = 0.01;
 
+/// Default smallest size worth trading (shares).
+pub const DEFAULT_MIN_TRADE_SIZE: f64 = 5.0;
+
+/// Default cap on batches in flight at once, across all groups.
+pub const DEFAULT_MAX_CONCURRENT_EXECUTIONS: usize = 4;
+
+/// The CLOB rejects GTD expirations less than a minute out.
+const GTD_MIN_LEAD_SECS: u64 = 60;
+
+/// Default GTD lifetime beyond the exchange minimum.
+pub const DEFAULT_GTD_TTL_SECS: u64 = 30;
+
 // Slippage thresholds
 const SLIPPAGE_EXTREME_THRESHOLD_HIGH: f64 = 0.96;
 const SLIPPAGE_EXTREME_THRESHOLD_LOW: f64 = 0.04;
@@ -38,23 +53,99 @@ const DECIMALS: f64 = 1_000_000.0;
 #[derive(Debug, Clone)]
 pub struct ExecutorConfig {
     pub min_pnl_threshold_percent: f64,
+    /// Max size per trade — depth, balance and minted inventory can only shrink it.
     pub default_size: f64,
+    /// Smallest size worth trading; smaller opportunities are skipped.
+    pub min_size: f64,
     pub slippage_enabled: bool,
+    /// Per-group cooldown after an execution starts.
     pub opportunity_timeout_ms: u64,
+    /// Batches in flight at once, across all groups.
+    pub max_concurrent: usize,
+    pub order_types: OrderTypePolicy,
     pub maker_address: String,
     pub signer_address: String,
 }
 
+/// Order type for one strategy and/or leg role (None = any).
+#[derive(Debug, Clone)]
+pub struct OrderTypeRule {
+    pub strategy: Option<StrategyKind>,
+    pub leg_role: Option<LegRole>,
+    pub order_type: OrderType,
+}
+
+impl OrderTypeRule {
+    /// Specificity — strategy + role beats strategy beats role.
+    fn rank(&self) -> u8 {
+        (self.strategy.is_some() as u8) * 2 + self.leg_role.is_some() as u8
+    }
+
+    fn matches(&self, strategy: StrategyKind, role: LegRole) -> bool {
+        self.strategy.is_none_or(|s| s == strategy) && self.leg_role.is_none_or(|r| r == role)
+    }
+}
+
+/// Order type selection for executor orders.
+#[derive(Debug, Clone)]
+pub struct OrderTypePolicy {
+    pub default: OrderType,
+    pub rules: Vec<OrderTypeRule>,
+    /// GTD lifetime beyond the exchange's one-minute minimum.
+    pub gtd_ttl_secs: u64,
+}
+
+impl Default for OrderTypePolicy {
+    fn default() -> Self {
+        Self {
+            default: OrderType::Gtc,
+            rules: Vec::new(),
+            gtd_ttl_secs: DEFAULT_GTD_TTL_SECS,
+        }
+    }
+}
+
+impl OrderTypePolicy {
+    /// Order type of one leg: the most specific matching rule, later rules
+    /// winning ties, else the default.
+    pub fn resolve(&self, strategy: StrategyKind, role: LegRole) -> OrderType {
+        self.rules
+            .iter()
+            .filter(|r| r.matches(strategy, role))
+            .max_by_key(|r| r.rank())
+            .map_or(self.default, |r| r.order_type)
+    }
+
+    /// Expiration to sign for an order (GTD only, 0 otherwise).
+    pub fn expiration(&self, order_type: OrderType, now_secs: u64) -> u64 {
+        match order_type {
+            OrderType::Gtd => now_secs + GTD_MIN_LEAD_SECS + self.gtd_ttl_secs,
+            _ => 0,
+        }
+    }
+}
+
 // =============================================================================
 // VALIDATION
 // =============================================================================
 
-/// Validation state — shared atomics for hot path, Mutex for minted cache.
+/// Per-group execution state — in-flight lock + cooldown clock.
+#[derive(Debug, Default, Clone, Copy)]
+struct GroupExecution {
+    in_flight: bool,
+    last_executed_at: u64, // epoch ms
+    /// Group dropped by the engine while in flight — forgotten when the lease drops.
+    retired: bool,
+}
+
+/// Validation state — shared atomics for hot path, Mutex for group and minted caches.
 pub struct ValidationState {
     pub usdc_balance: AtomicU64,   // f64 bits stored as u64
     pub trading_enabled: AtomicBool,
-    pub is_submitting: AtomicBool,
-    pub last_executed_at: AtomicU64, // epoch ms
+    /// Batches currently in flight, across all groups.
+    pub in_flight: AtomicUsize,
+    /// groupKey → in-flight lock + cooldown. Unrelated groups execute in parallel.
+    groups: Mutex<HashMap<String, GroupExecution>>,
     /// Minted asset cache: groupKey → (tokenId → mintedAmount)
     /// Used to cap SELL leg size — can't sell more than minted.
     /// Updated by Node.js via `update_minted_assets()` N-API call.
@@ -66,8 +157,8 @@ impl ValidationState {
         Self {
             usdc_balance: AtomicU64::new(0),
             trading_enabled: AtomicBool::new(false),
-            is_submitting: AtomicBool::new(false),
-            last_executed_at: AtomicU64::new(0),
+            in_flight: AtomicUsize::new(0),
+            groups: Mutex::new(HashMap::new()),
             minted_assets: Mutex::new(HashMap::new()),
         }
     }
@@ -102,6 +193,97 @@ impl ValidationState {
         }
     }
 
+    /// Atomically take up to `amount` from the balance (never below zero).
+    /// Returns the amount taken.
+    pub fn reserve_balance(&self, amount: f64) -> f64 {
+        let mut reserved = 0.0;
+        let _ = self
+            .usdc_balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                let current = f64::from_bits(bits);
+                reserved = amount.min(current).max(0.0);
+                Some((current - reserved).to_bits())
+            });
+        reserved
+    }
+
+    /// Atomically return `amount` to the balance (unused reservation, failed batch).
+    pub fn refund_balance(&self, amount: f64) {
+        let _ = self
+            .usdc_balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some((f64::from_bits(bits) + amount).to_bits())
+            });
+    }
+
+    /// Why `group_key` can't start a batch now (None = free to execute).
+    pub fn execution_blocked(
+        &self,
+        group_key: &str,
+        now_ms: u64,
+        config: &ExecutorConfig,
+    ) -> Option<SkipReason> {
+        let groups = self.groups.lock().unwrap();
+        Self::blocked(&groups, self.in_flight.load(Ordering::Relaxed), group_key, now_ms, config)
+    }
+
+    /// Claim `group_key`'s execution slot — re-checks the lock, cooldown and
+    /// global cap atomically. The slot is released when the lease drops.
+    pub fn try_begin(
+        &self,
+        group_key: &str,
+        now_ms: u64,
+        config: &ExecutorConfig,
+    ) -> Result<ExecutionLease<'_>, SkipReason> {
+        let mut groups = self.groups.lock().unwrap();
+        let in_flight = self.in_flight.load(Ordering::Relaxed);
+        if let Some(reason) = Self::blocked(&groups, in_flight, group_key, now_ms, config) {
+            return Err(reason);
+        }
+        groups.entry(group_key.to_string()).or_default().in_flight = true;
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        Ok(ExecutionLease {
+            state: self,
+            group_key: group_key.to_string(),
+        })
+    }
+
+    /// Drop a group's lock and cooldown once the engine no longer tracks it.
+    /// A batch still in flight keeps the entry until its lease drops.
+    pub fn forget_group(&self, group_key: &str) {
+        let mut groups = self.groups.lock().unwrap();
+        match groups.get_mut(group_key) {
+            Some(g) if g.in_flight => g.retired = true,
+            Some(_) => {
+                groups.remove(group_key);
+            }
+            None => {}
+        }
+    }
+
+    fn blocked(
+        groups: &HashMap<String, GroupExecution>,
+        in_flight: usize,
+        group_key: &str,
+        now_ms: u64,
+        config: &ExecutorConfig,
+    ) -> Option<SkipReason> {
+        if let Some(g) = groups.get(group_key) {
+            if g.in_flight {
+                return Some(SkipReason::AlreadySubmitting);
+            }
+            if g.last_executed_at > 0
+                && now_ms.saturating_sub(g.last_executed_at) < config.opportunity_timeout_ms
+            {
+                return Some(SkipReason::CooldownActive);
+            }
+        }
+        if in_flight >= config.max_concurrent.max(1) {
+            return Some(SkipReason::ConcurrencyLimit);
+        }
+        None
+    }
+
     /// Set minted assets for a group (called from Node.js via N-API).
     pub fn set_minted_assets(&self, group_key: &str, assets: HashMap<String, f64>) {
         let mut cache = self.minted_assets.lock().unwrap();
@@ -129,72 +311,93 @@ impl ValidationState {
     }
 }
 
+impl Default for ValidationState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A group's claimed execution slot — releases the group lock and the global
+/// in-flight count on drop.
+pub struct ExecutionLease<'a> {
+    state: &'a ValidationState,
+    group_key: String,
+}
+
+impl ExecutionLease<'_> {
+    /// Start the group's cooldown (called once the balance is reserved).
+    pub fn mark_executed(&self, now_ms: u64) {
+        let mut groups = self.state.groups.lock().unwrap();
+        if let Some(g) = groups.get_mut(&self.group_key) {
+            g.last_executed_at = now_ms;
+        }
+    }
+}
+
+impl Drop for ExecutionLease<'_> {
+    fn drop(&mut self) {
+        let mut groups = self.state.groups.lock().unwrap();
+        if let Some(g) = groups.get_mut(&self.group_key) {
+            g.in_flight = false;
+            if g.retired {
+                groups.remove(&self.group_key);
+            }
+        }
+        self.state.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// Skip reason for logging.
 #[derive(Debug)]
 pub enum SkipReason {
     TradingDisabled,
     AlreadySubmitting,
     CooldownActive,
+    ConcurrencyLimit,
     PnlBelowThreshold,
     InsufficientBalance,
     InvalidSize,
     NoCandidates,
     InsufficientOrderbookSize,
     InsufficientMintedAssets,
+    /// Info-only strategy (see `StrategyKind::is_executable`).
+    NotExecutable,
 }
 
-/// Validation result.
-pub struct ValidationResult {
+/// Validation result — holds the group's execution slot, with `required_cost`
+/// already taken from the balance.
+pub struct ValidationResult<'a> {
     pub candidates: Vec<OrderCandidate>,
     pub size: f64,
     pub required_cost: f64,
     pub total_cost: f64,
+    pub lease: ExecutionLease<'a>,
 }
 
-/// Check if signal should be skipped. Returns Ok(ValidationResult) if valid.
-pub fn should_skip(
+/// Check if signal should be skipped. Returns Ok(ValidationResult) if valid,
+/// after claiming the group's slot and reserving the batch's USDC.
+pub fn should_skip<'a>(
     signal: &ArbSignal,
-    state: &ValidationState,
+    state: &'a ValidationState,
     config: &ExecutorConfig,
-) -> Result<ValidationResult, SkipReason> {
+) -> Result<ValidationResult<'a>, SkipReason> {
     // 1. Trading enabled?
     if !state.trading_enabled.load(Ordering::Relaxed) {
         return Err(SkipReason::TradingDisabled);
     }
 
-    // 2. Already submitting?
-    if state.is_submitting.load(Ordering::Relaxed) {
-        return Err(SkipReason::AlreadySubmitting);
-    }
-
-    // 3. Cooldown check
-    let now_ms = get_epoch_ms();
-    let last = state.last_executed_at.load(Ordering::Relaxed);
-    if last > 0 && now_ms.saturating_sub(last) < config.opportunity_timeout_ms {
-        return Err(SkipReason::CooldownActive);
+    // 1b. Info-only strategies never reach the CLOB
+    if !signal.strategy.is_executable() {
+        return Err(SkipReason::NotExecutable);
     }
 
-    // 4. Build candidates from signal
-    let candidates = build_order_candidates(signal);
+    // 2. Build candidates from signal
+    let candidates = build_order_candidates(signal, &config.order_types);
     if candidates.is_empty() {
         return Err(SkipReason::NoCandidates);
     }
 
-    // 4b. Check orderbook size — skip if any candidate's size < default_size
-    let default_size = config.default_size;
-    for c in &candidates {
-        if let Some(ob_size) = c.orderbook_size {
-            if ob_size < default_size {
-                tracing::debug!(
-                    "[Executor] InsufficientOrderbookSize: strategy={} token={} ob_size={:.2} < default_size={:.2} price={:.4}",
-                    signal.strategy, c.token_id, ob_size, default_size, c.price
-                );
-                return Err(SkipReason::InsufficientOrderbookSize);
-            }
-        }
-    }
-
-    // 5. Calculate total cost and PnL
+    // 3. Calculate total cost and PnL
     let total_cost = calculate_total_cost(signal);
     let pnl_percent = if total_cost > 0.0 {
         (signal.profit_abs / total_cost) * 100.0
@@ -207,41 +410,40 @@ pub fn should_skip(
         return Err(SkipReason::PnlBelowThreshold);
     }
 
-
-    // return Err(SkipReason::PnlBelowThreshold);
-
-    // 6. Calculate size
-    let size = config.default_size;
-    if !size.is_finite() || size < 5.0 {
-        return Err(SkipReason::InvalidSize);
-    }
-
-    // 6b. Check minted assets for SELL legs — can't sell more than minted
-    let sell_legs: Vec<&OrderCandidate> = candidates.iter()
-        .filter(|c| c.side == OrderSide::Sell)
-        .collect();
-    if !sell_legs.is_empty() {
-        for leg in &sell_legs {
-            let minted = state.get_minted_amount(&signal.group_key, &leg.token_id);
-            if minted < size {
-                return Err(SkipReason::InsufficientMintedAssets);
-            }
+    // 4. Group in flight or cooling down, or global concurrency cap reached?
+    //    Claims the group's slot (released when the lease drops)
+    let lease = state.try_begin(&signal.group_key, get_epoch_ms(), config)?;
+
+    // 5. Reserve USDC for the largest size the other caps allow — concurrent
+    //    groups share the balance, so size against what was actually reserved
+    let mut caps = SizeCaps::new(signal, &candidates, state, config);
+    let cost_per_set = estimate_required_cost(&candidates, 1.0, config.slippage_enabled);
+    let reserved = caps.reserve(state, cost_per_set);
+
+    // 6. Size — largest size depth, max size, reserved balance and minted inventory allow
+    let size = match caps.resolve(config.min_size) {
+        Ok(size) => size,
+        Err(reason) => {
+            state.refund_balance(reserved);
+            tracing::debug!(
+                "[Executor] {:?}: strategy={} caps={:?} min_size={:.2}",
+                reason, signal.strategy.signal_name(), caps, config.min_size
+            );
+            return Err(reason);
         }
-    }
+    };
 
-    // 7. Balance check
-    let required_cost = estimate_required_cost(&candidates, size);
-    let balance = state.get_balance();
-    if balance < required_cost {
-        tracing::debug!("Insufficient balance: {} < {}", balance, required_cost);
-        return Err(SkipReason::InsufficientBalance);
-    }
+    // 7. Required USDC at the chosen size — keep it, return the rest
+    let required_cost =
+        estimate_required_cost(&candidates, size, config.slippage_enabled).min(reserved);
+    state.refund_balance(reserved - required_cost);
 
     Ok(ValidationResult {
         candidates,
         size,
         required_cost,
         total_cost,
+        lease,
     })
 }
 
@@ -249,118 +451,96 @@ pub fn should_skip(
 // ORDER PREPARATION
 // =============================================================================
 
-/// Build order candidates from ArbSignal.
-fn build_order_candidates(signal: &ArbSignal) -> Vec<OrderCandidate> {
-    let strategy = signal.strategy.as_str();
-    let mut candidates = Vec::with_capacity(3);
-
-    match strategy {
-        // Triangle BUY: parent_lower YES + parent_upper NO + range NO
-        // Complement BUY: parent_lower NO + range YES + parent_upper YES
-        // Both are BUY-all-3-legs — signal fields already point to correct tokens
-        "POLYMARKET_TRIANGLE_BUY" | "POLYMARKET_COMPLEMENT_BUY" => {
-            // BUY all 3 legs
-            if let Some(ask) = signal.parent_best_ask {
-                candidates.push(OrderCandidate {
-                    token_id: signal.parent_asset_id.clone(),
-                    market_slug: signal.parent_market_slug.clone(),
-                    price: ask,
-                    side: OrderSide::Buy,
-                    orderbook_size: signal.parent_best_ask_size,
-                    neg_risk: signal.parent_neg_risk,
-                });
-            }
-            if let Some(ask) = signal.parent_upper_best_ask {
-                candidates.push(OrderCandidate {
-                    token_id: signal.parent_upper_asset_id.clone(),
-                    market_slug: signal.parent_upper_market_slug.clone(),
-                    price: ask,
-                    side: OrderSide::Buy,
-                    orderbook_size: signal.parent_upper_best_ask_size,
-                    neg_risk: signal.parent_upper_neg_risk,
-                });
-            }
-            if let Some(ask) = signal.child_best_ask {
-                candidates.push(OrderCandidate {
-                    token_id: signal.child_asset_id.clone(),
-                    market_slug: signal.child_market_slug.clone(),
-                    price: ask,
-                    side: OrderSide::Buy,
-                    orderbook_size: signal.child_best_ask_size,
-                    neg_risk: signal.child_neg_risk,
-                });
-            }
+/// Upper bounds on one trade's size, in shares (`INFINITY` = unconstrained).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeCaps {
+    /// Smallest top-of-book size across legs.
+    pub depth: f64,
+    /// Configured max size per trade.
+    pub max: f64,
+    /// Sets the reserved USDC can pay for (see `reserve`).
+    pub balance: f64,
+    /// Smallest minted inventory across SELL legs.
+    pub minted: f64,
+}
+
+impl SizeCaps {
+    /// Collect the depth, max and minted caps for a signal's candidates; the
+    /// balance cap stays open until `reserve`.
+    pub fn new(
+        signal: &ArbSignal,
+        candidates: &[OrderCandidate],
+        state: &ValidationState,
+        config: &ExecutorConfig,
+    ) -> Self {
+        let depth = candidates
+            .iter()
+            .filter_map(|c| c.orderbook_size)
+            .fold(f64::INFINITY, f64::min);
+        let minted = candidates
+            .iter()
+            .filter(|c| c.side == OrderSide::Sell)
+            .map(|c| state.get_minted_amount(&signal.group_key, &c.token_id))
+            .fold(f64::INFINITY, f64::min);
+        Self {
+            depth,
+            max: config.default_size,
+            balance: f64::INFINITY,
+            minted,
         }
-        "SELL_PARENT_BUY_CHILDREN" => {
-            // Unbundle: SELL parent, BUY children
-            if let Some(bid) = signal.parent_best_bid_flat {
-                candidates.push(OrderCandidate {
-                    token_id: signal.parent_asset_id.clone(),
-                    market_slug: signal.parent_market_slug.clone(),
-                    price: bid,
-                    side: OrderSide::Sell,
-                    orderbook_size: signal.parent_best_bid_size,
-                    neg_risk: signal.parent_neg_risk,
-                });
-            }
-            if let Some(ask) = signal.parent_upper_best_ask {
-                candidates.push(OrderCandidate {
-                    token_id: signal.parent_upper_asset_id.clone(),
-                    market_slug: signal.parent_upper_market_slug.clone(),
-                    price: ask,
-                    side: OrderSide::Buy,
-                    orderbook_size: signal.parent_upper_best_ask_size,
-                    neg_risk: signal.parent_upper_neg_risk,
-                });
-            }
-            if let Some(ask) = signal.child_best_ask {
-                candidates.push(OrderCandidate {
-                    token_id: signal.child_asset_id.clone(),
-                    market_slug: signal.child_market_slug.clone(),
-                    price: ask,
-                    side: OrderSide::Buy,
-                    orderbook_size: signal.child_best_ask_size,
-                    neg_risk: signal.child_neg_risk,
-                });
-            }
+    }
+
+    /// Reserve USDC for the largest size the other caps allow and cap the
+    /// balance at what was reserved. Returns the amount taken from `state`.
+    pub fn reserve(&mut self, state: &ValidationState, cost_per_set: f64) -> f64 {
+        if cost_per_set <= 0.0 {
+            return 0.0;
         }
-        "BUY_PARENT_SELL_CHILDREN" => {
-            // Bundle: BUY parent, SELL children
-            if let Some(ask) = signal.parent_best_ask_flat {
-                candidates.push(OrderCandidate {
-                    token_id: signal.parent_asset_id.clone(),
-                    market_slug: signal.parent_market_slug.clone(),
-                    price: ask,
-                    side: OrderSide::Buy,
-                    orderbook_size: signal.parent_best_ask_size,
-                    neg_risk: signal.parent_neg_risk,
-                });
-            }
-            if let Some(bid) = signal.parent_upper_best_bid {
-                candidates.push(OrderCandidate {
-                    token_id: signal.parent_upper_asset_id.clone(),
-                    market_slug: signal.parent_upper_market_slug.clone(),
-                    price: bid,
-                    side: OrderSide::Sell,
-                    orderbook_size: signal.parent_upper_best_bid_size,
-                    neg_risk: signal.parent_upper_neg_risk,
-                });
-            }
-            if let Some(bid) = signal.child_best_bid {
-                candidates.push(OrderCandidate {
-                    token_id: signal.child_asset_id.clone(),
-                    market_slug: signal.child_market_slug.clone(),
-                    price: bid,
-                    side: OrderSide::Sell,
-                    orderbook_size: signal.child_best_bid_size,
-                    neg_risk: signal.child_neg_risk,
-                });
-            }
+        let wanted = cost_per_set * self.depth.min(self.max).min(self.minted);
+        let reserved = state.reserve_balance(wanted);
+        self.balance = reserved / cost_per_set;
+        reserved
+    }
+
+    /// Largest size every cap allows, rounded down to exchange precision (0.01).
+    ///
+    /// Below `min_size` the signal is skipped with the binding cap's reason.
+    pub fn resolve(&self, min_size: f64) -> Result<f64, SkipReason> {
+        let caps = [
+            (self.depth, SkipReason::InsufficientOrderbookSize),
+            (self.balance, SkipReason::InsufficientBalance),
+            (self.minted, SkipReason::InsufficientMintedAssets),
+            (self.max, SkipReason::InvalidSize),
+        ];
+        let (cap, reason) = caps
+            .into_iter()
+            .fold((f64::INFINITY, SkipReason::InvalidSize), |best, (cap, reason)| {
+                if cap < best.0 { (cap, reason) } else { best }
+            });
+        let size = (cap * 100.0 + 1e-9).floor() / 100.0;
+        if !size.is_finite() || size < min_size.max(0.01) {
+            return Err(reason);
         }
-        _ => {}
+        Ok(size)
     }
+}
 
-    candidates
+/// Build order candidates from ArbSignal — one per leg, in leg order.
+fn build_order_candidates(signal: &ArbSignal, order_types: &OrderTypePolicy) -> Vec<OrderCandidate> {
+    signal
+        .legs
+        .iter()
+        .map(|leg| OrderCandidate {
+            token_id: leg.asset_id.clone(),
+            market_slug: leg.market_slug.clone(),
+            price: leg.price,
+            side: leg.side,
+            orderbook_size: Some(leg.size_available),
+            neg_risk: leg.neg_risk,
+            fee_rate_bps: leg.fee_rate_bps,
+            order_type: order_types.resolve(signal.strategy, leg.role),
+        })
+        .collect()
 }
 
 /// Calculate total cost for a signal (matches RealExecutionService.calculateTotalCost).
@@ -368,59 +548,62 @@ fn build_order_candidates(signal: &ArbSignal) -> Vec<OrderCandidate> {
 /// For SELL legs, cost = `1 - bid` (minting/collateral cost).
 /// For BUY legs, cost = `ask`.
 fn calculate_total_cost(signal: &ArbSignal) -> f64 {
-    match signal.strategy.as_str() {
-        "POLYMARKET_TRIANGLE_BUY" | "POLYMARKET_COMPLEMENT_BUY" => {
-            signal.triangle_total_cost.unwrap_or(0.0)
-        }
-
-        // Unbundling: SELL parent lower, BUY range child + BUY parent upper
-        // Cost = childrenSumAsk + parentUpperAsk + (1 - parentBid)
-        "SELL_PARENT_BUY_CHILDREN" => {
-            let children_buy_cost = signal.children_sum_ask;
-            let parent_upper_buy_cost = signal.parent_upper_best_ask.unwrap_or(0.0);
-            let parent_bid = signal.parent_best_bid.unwrap_or(0.0);
-            let parent_sell_cost = 1.0 - parent_bid;
-            children_buy_cost + parent_upper_buy_cost + parent_sell_cost
-        }
-
-        // Bundling: BUY parent lower, SELL range child + SELL parent upper
-        // Cost = parentAsk + (1 - childBid) + (1 - parentUpperBid)
-        "BUY_PARENT_SELL_CHILDREN" => {
-            let parent_buy_cost = signal.parent_best_ask_flat.unwrap_or(0.0);
-            let child_bid = signal.child_best_bid.unwrap_or(0.0);
-            let children_sell_cost = 1.0 - child_bid;
-            let parent_upper_bid = signal.parent_upper_best_bid.unwrap_or(0.0);
-            let parent_upper_sell_cost = 1.0 - parent_upper_bid;
-            parent_buy_cost + children_sell_cost + parent_upper_sell_cost
-        }
-
-        _ => 0.0,
+    match signal.strategy {
+        // Parity SELL: one minted set backs both legs
+        StrategyKind::ParitySell => 1.0,
+
+        // Everything else: Σ ask over BUY legs + Σ (1 - bid) over SELL legs
+        _ => signal
+            .legs
+            .iter()
+            .map(|l| match l.side {
+                OrderSide::Buy => l.price,
+                OrderSide::Sell => 1.0 - l.price,
+            })
+            .sum(),
     }
 }
 
-/// Estimate required cost for candidates at given size.
-fn estimate_required_cost(candidates: &[OrderCandidate], size: f64) -> f64 {
+/// Estimate required USDC for candidates at given size: every BUY leg at the
+/// limit it is signed at, plus its taker fee.
+fn estimate_required_cost(candidates: &[OrderCandidate], size: f64, slippage_enabled: bool) -> f64 {
     candidates
         .iter()
-        .filter(|c| c.side == OrderSide::Buy)
-        .map(|c| c.price * size)
+        .map(|c| leg_cost(c, size, slippage_enabled))
         .sum()
 }
 
+/// USDC reserved for `size` of one candidate — limit plus taker fee for a
+/// BUY, nothing for a SELL (it spends minted inventory).
+pub fn leg_cost(candidate: &OrderCandidate, size: f64, slippage_enabled: bool) -> f64 {
+    if candidate.side != OrderSide::Buy {
+        return 0.0;
+    }
+    let limit = order_limit(candidate, slippage_enabled);
+    (limit + taker_fee(limit, candidate.fee_rate_bps)) * size
+}
+
+/// Limit price a candidate is signed at (slippage applied when enabled).
+pub fn order_limit(candidate: &OrderCandidate, slippage_enabled: bool) -> f64 {
+    if slippage_enabled {
+        apply_slippage(candidate.price, candidate.side)
+    } else {
+        candidate.price
+    }
+}
+
 /// Prepare batch orders from candidates (port of prepareBatchOrdersSync).
 pub fn prepare_batch_orders(
     candidates: &[OrderCandidate],
     size: f64,
     config: &ExecutorConfig,
 ) -> Vec<OrderToSign> {
+    // GTD expirations are stamped here, right before signing
+    let now_secs = get_epoch_ms() / 1000;
     candidates
         .iter()
         .map(|candidate| {
-            let price = if config.slippage_enabled {
-                apply_slippage(candidate.price, candidate.side)
-            } else {
-                candidate.price
-            };
+            let price = order_limit(candidate, config.slippage_enabled);
 
             // Calculate maker/taker amounts (USDC 6 decimals)
             let size_rounded = round_to_decimals(size, 2);
@@ -428,6 +611,15 @@ pub fn prepare_batch_orders(
             let usdc_rounded = round_to_decimals(usdc_raw, 4);
 
             let (maker_amount, taker_amount) = match candidate.side {
+                // FOK / FAK BUY is a market order: the CLOB takes USDC with at
+                // most 2 decimals and shares with at most 4
+                OrderSide::Buy if candidate.order_type.is_immediate() => {
+                    let usdc = floor_to_decimals(usdc_raw, 2);
+                    let shares = floor_to_decimals(usdc / price, 4);
+                    let maker = (usdc * DECIMALS).round() as u64;
+                    let taker = (shares * DECIMALS).round() as u64;
+                    (maker.to_string(), taker.to_string())
+                }
                 OrderSide::Buy => {
                     // BUY: Maker = USDC, Taker = Asset
                     let maker = (usdc_rounded * DECIMALS).round() as u64;
@@ -452,7 +644,8 @@ pub fn prepare_batch_orders(
                 taker_amount,
                 side: candidate.side.as_u8(),
                 neg_risk: candidate.neg_risk,
-                fee_rate_bps: 0,
+                fee_rate_bps: candidate.fee_rate_bps,
+                expiration: config.order_types.expiration(candidate.order_type, now_secs),
             }
         })
         .collect()
@@ -498,6 +691,12 @@ fn round_to_decimals(value: f64, decimals: u32) -> f64 {
     (value * multiplier).round() / multiplier
 }
 
+/// Round down to `decimals` places (tolerating float noise just below a step).
+fn floor_to_decimals(value: f64, decimals: u32) -> f64 {
+    let multiplier = 10_f64.powi(decimals as i32);
+    (value * multiplier + 1e-9).floor() / multiplier
+}
+
 fn generate_salt() -> String {
     use rand::Rng;
     let mut rng = rand::thread_rng();
@@ -506,7 +705,7 @@ fn generate_salt() -> String {
     random.to_string()
 }
 
-fn get_epoch_ms() -> u64 {
+pub fn get_epoch_ms() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards")
@@ -520,6 +719,23 @@ fn get_epoch_ms() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::state::PriceSlot;
+    use crate::types::signal::{Leg, LegRole};
+
+    /// Baseline config for tests (no slippage, no PnL floor) — override fields with `..test_config()`.
+    fn test_config() -> ExecutorConfig {
+        ExecutorConfig {
+            min_pnl_threshold_percent: 0.0,
+            default_size: 10.0,
+            min_size: DEFAULT_MIN_TRADE_SIZE,
+            slippage_enabled: false,
+            opportunity_timeout_ms: 5000,
+            max_concurrent: DEFAULT_MAX_CONCURRENT_EXECUTIONS,
+            order_types: OrderTypePolicy::default(),
+            maker_address: String::new(),
+            signer_address: String::new(),
+        }
+    }
 
     #[test]
     fn test_apply_slippage_buy_normal() {
@@ -583,6 +799,151 @@ mod tests {
 
         assert!(!state.try_deduct_balance(600.0)); // insufficient
         assert!((state.get_balance() - 500.0).abs() < 1e-10); // unchanged
+
+        // Reservations take what is left, never more
+        assert_eq!(state.reserve_balance(200.0), 200.0);
+        assert_eq!(state.reserve_balance(400.0), 300.0);
+        assert_eq!(state.reserve_balance(50.0), 0.0);
+        state.refund_balance(125.0);
+        assert!((state.get_balance() - 125.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_neg_risk_convert_is_info_only() {
+        let q = PriceSlot {
+            best_bid: 0.45,
+            best_ask: 0.40,
+            best_bid_size: 500.0,
+            best_ask_size: 500.0,
+            ..PriceSlot::default()
+        };
+        let signal = make_leg_signal(
+            StrategyKind::NegRiskConvert,
+            vec![
+                Leg::at_quote(LegRole::Child, OrderSide::Buy, "no".into(), String::new(), &q),
+                Leg::at_quote(LegRole::Child, OrderSide::Sell, "yes".into(), String::new(), &q),
+            ],
+        );
+        let state = ValidationState::new();
+        state.trading_enabled.store(true, Ordering::Relaxed);
+        state.set_balance(1000.0);
+        let config = test_config();
+        assert!(matches!(should_skip(&signal, &state, &config), Err(SkipReason::NotExecutable)));
+    }
+
+    #[test]
+    fn test_order_type_policy_most_specific_rule_wins() {
+        let rule = |strategy, leg_role, order_type| OrderTypeRule { strategy, leg_role, order_type };
+        let policy = OrderTypePolicy {
+            default: OrderType::Gtc,
+            rules: vec![
+                rule(None, Some(LegRole::Child), OrderType::Fak),
+                rule(Some(StrategyKind::LadderBuy), None, OrderType::Fok),
+                rule(Some(StrategyKind::LadderBuy), Some(LegRole::Parent), OrderType::Gtd),
+            ],
+            gtd_ttl_secs: 30,
+        };
+
+        assert_eq!(policy.resolve(StrategyKind::Triangle, LegRole::Parent), OrderType::Gtc);
+        assert_eq!(policy.resolve(StrategyKind::Triangle, LegRole::Child), OrderType::Fak);
+        assert_eq!(policy.resolve(StrategyKind::LadderBuy, LegRole::Child), OrderType::Fok);
+        assert_eq!(policy.resolve(StrategyKind::LadderBuy, LegRole::Parent), OrderType::Gtd);
+
+        assert_eq!(policy.expiration(OrderType::Fok, 1_000), 0);
+        assert_eq!(policy.expiration(OrderType::Gtd, 1_000), 1_090);
+    }
+
+    #[test]
+    fn test_prepare_batch_orders_stamps_gtd_expiration() {
+        let candidate = |order_type| OrderCandidate {
+            token_id: "token".to_string(),
+            market_slug: String::new(),
+            price: 0.40,
+            side: OrderSide::Buy,
+            orderbook_size: Some(100.0),
+            neg_risk: false,
+            fee_rate_bps: 0,
+            order_type,
+        };
+        let config = test_config();
+
+        let now_secs = get_epoch_ms() / 1000;
+        let orders = prepare_batch_orders(&[candidate(OrderType::Fok), candidate(OrderType::Gtd)], 10.0, &config);
+        assert_eq!(orders[0].expiration, 0);
+        let lead = orders[1].expiration - now_secs;
+        assert!((GTD_MIN_LEAD_SECS + DEFAULT_GTD_TTL_SECS..=GTD_MIN_LEAD_SECS + DEFAULT_GTD_TTL_SECS + 1).contains(&lead));
+    }
+
+    #[test]
+    fn test_prepare_immediate_buy_limits_usdc_to_cents() {
+        let candidate = |side, order_type| OrderCandidate {
+            token_id: "token".to_string(),
+            market_slug: String::new(),
+            price: 0.43,
+            side,
+            orderbook_size: Some(100.0),
+            neg_risk: false,
+            fee_rate_bps: 0,
+            order_type,
+        };
+        let config = test_config();
+
+        let orders = prepare_batch_orders(
+            &[
+                candidate(OrderSide::Buy, OrderType::Fok),
+                candidate(OrderSide::Buy, OrderType::Fak),
+                candidate(OrderSide::Buy, OrderType::Gtc),
+                candidate(OrderSide::Sell, OrderType::Fok),
+            ],
+            7.77,
+            &config,
+        );
+        // 0.43 × 7.77 = $3.3411 → $3.34, buying 3.34 / 0.43 = 7.7674 shares
+        for order in &orders[..2] {
+            assert_eq!(order.maker_amount, "3340000");
+            assert_eq!(order.taker_amount, "7767400");
+        }
+        assert_eq!(orders[2].maker_amount, "3341100");
+        assert_eq!(orders[2].taker_amount, "7770000");
+        assert_eq!(orders[3].maker_amount, "7770000");
+        assert_eq!(orders[3].taker_amount, "3341100");
+    }
+
+    #[test]
+    fn test_execution_leases_are_per_group_with_global_cap() {
+        let state = ValidationState::new();
+        let config = ExecutorConfig {
+            max_concurrent: 2,
+            ..test_config()
+        };
+
+        let btc = state.try_begin("btc", 1_000, &config).unwrap();
+        // Same group is locked while in flight; an unrelated group is not
+        assert!(matches!(state.try_begin("btc", 1_000, &config), Err(SkipReason::AlreadySubmitting)));
+        let eth = state.try_begin("eth", 1_000, &config).unwrap();
+        assert!(matches!(state.try_begin("sol", 1_000, &config), Err(SkipReason::ConcurrencyLimit)));
+        assert_eq!(state.in_flight.load(Ordering::Relaxed), 2);
+
+        btc.mark_executed(1_000);
+        drop(btc);
+        drop(eth);
+        assert_eq!(state.in_flight.load(Ordering::Relaxed), 0);
+
+        // Cooldown is per group: btc waits, eth (never marked) is free
+        assert!(matches!(state.execution_blocked("btc", 3_000, &config), Some(SkipReason::CooldownActive)));
+        assert!(state.execution_blocked("eth", 3_000, &config).is_none());
+        assert!(state.execution_blocked("btc", 6_000, &config).is_none());
+
+        // A group the engine dropped loses its cooldown; one still in flight
+        // stays locked until its lease drops
+        state.forget_group("btc");
+        assert!(state.execution_blocked("btc", 3_000, &config).is_none());
+        let eth = state.try_begin("eth", 3_000, &config).unwrap();
+        eth.mark_executed(3_000);
+        state.forget_group("eth");
+        assert!(matches!(state.execution_blocked("eth", 3_000, &config), Some(SkipReason::AlreadySubmitting)));
+        drop(eth);
+        assert!(state.execution_blocked("eth", 3_000, &config).is_none());
     }
 
     // =========================================================================
@@ -630,6 +991,7 @@ mod tests {
             side,
             neg_risk,
             fee_rate_bps,
+            expiration: 0,
         }
     }
 
@@ -649,15 +1011,16 @@ mod tests {
             side,
             orderbook_size: Some(100.0),
             neg_risk,
+            fee_rate_bps: 0,
+            order_type: OrderType::Gtc,
         };
 
         let config = ExecutorConfig {
-            min_pnl_threshold_percent: 0.0,
             default_size: size,
             slippage_enabled: false, // disabled to match Test API (no slippage)
-            opportunity_timeout_ms: 5000,
             maker_address: "0x0000000000000000000000000000000000000000".to_string(),
             signer_address: "0x0000000000000000000000000000000000000000".to_string(),
+            ..test_config()
         };
 
         let mut orders = prepare_batch_orders(&[candidate], size, &config);
@@ -771,6 +1134,196 @@ mod tests {
         println!("✅ Extreme prices: low maker={}, high maker={}", api_low.maker_amount, api_high.maker_amount);
     }
 
+    #[test]
+    fn test_prepare_batch_orders_carries_leg_fee_rate() {
+        let candidate = OrderCandidate {
+            token_id: "token".to_string(),
+            market_slug: String::new(),
+            price: 0.40,
+            side: OrderSide::Buy,
+            orderbook_size: Some(100.0),
+            neg_risk: false,
+            fee_rate_bps: 200,
+            order_type: OrderType::Gtc,
+        };
+        let config = test_config();
+        let orders = prepare_batch_orders(&[candidate], 10.0, &config);
+        assert_eq!(orders[0].fee_rate_bps, 200);
+    }
+
+    fn make_leg_signal(strategy: StrategyKind, legs: Vec<Leg>) -> ArbSignal {
+        ArbSignal {
+            group_key: "g".to_string(),
+            event_slug: String::new(),
+            crypto: String::new(),
+            strategy,
+            profit_abs: 0.05,
+            profit_bps: 500.0,
+            gross_profit_abs: 0.05,
+            gross_profit_bps: 500.0,
+            timestamp_ms: 0,
+            legs,
+            child_index: -1,
+            total_cost: None,
+            total_bid: None,
+            payout: None,
+            mode: None,
+            reason: "",
+        }
+    }
+
+    #[test]
+    fn test_candidates_and_cost_from_legs() {
+        let q = |bid: f64, ask: f64, size: f64| PriceSlot {
+            best_bid: bid,
+            best_ask: ask,
+            best_bid_size: size,
+            best_ask_size: size,
+            ..PriceSlot::default()
+        };
+        let leg = |role, side, id: &str, q: &PriceSlot| Leg::at_quote(role, side, id.to_string(), String::new(), q);
+
+        // Monotone: BUY at the ask, SELL at the bid; cost = ask + (1 - bid)
+        let signal = make_leg_signal(
+            StrategyKind::Monotone,
+            vec![
+                leg(LegRole::Parent, OrderSide::Buy, "buy", &q(0.38, 0.40, 60.0)),
+                leg(LegRole::ParentUpper, OrderSide::Sell, "sell", &q(0.45, 0.47, 30.0)),
+            ],
+        );
+        let candidates = build_order_candidates(&signal, &OrderTypePolicy::default());
+        assert_eq!(candidates.len(), 2);
+        assert_eq!((candidates[0].side, candidates[0].price), (OrderSide::Buy, 0.40));
+        assert_eq!((candidates[1].side, candidates[1].price), (OrderSide::Sell, 0.45));
+        assert_eq!(candidates[1].orderbook_size, Some(30.0));
+        assert!((calculate_total_cost(&signal) - 0.95).abs() < 1e-9);
+        let state = ValidationState::new();
+        state.set_balance(1000.0);
+        state.set_minted_assets("g", HashMap::from([("sell".to_string(), 100.0)]));
+        let config = ExecutorConfig {
+            default_size: 100.0,
+            ..test_config()
+        };
+        assert_eq!(SizeCaps::new(&signal, &candidates, &state, &config).resolve(5.0).unwrap(), 30.0);
+
+        // Parity SELL: one minted set backs both legs
+        let signal = make_leg_signal(
+            StrategyKind::ParitySell,
+            vec![
+                leg(LegRole::Parent, OrderSide::Sell, "yes", &q(0.55, 0.57, 70.0)),
+                leg(LegRole::ParentUpper, OrderSide::Sell, "no", &q(0.50, 0.52, 90.0)),
+            ],
+        );
+        assert_eq!(calculate_total_cost(&signal), 1.0);
+    }
+
+    #[test]
+    fn test_size_caps_take_partial_size_and_name_binding_cap() {
+        let caps = SizeCaps {
+            depth: 12.345,
+            max: 100.0,
+            balance: f64::INFINITY,
+            minted: f64::INFINITY,
+        };
+        // Thin book: trade what is there, rounded down to 0.01
+        assert_eq!(caps.resolve(5.0).unwrap(), 12.34);
+
+        let caps = SizeCaps { balance: 8.0, ..caps };
+        assert_eq!(caps.resolve(5.0).unwrap(), 8.0);
+        assert!(matches!(caps.resolve(10.0), Err(SkipReason::InsufficientBalance)));
+
+        let caps = SizeCaps { minted: 3.0, ..caps };
+        assert!(matches!(caps.resolve(5.0), Err(SkipReason::InsufficientMintedAssets)));
+
+        let caps = SizeCaps { depth: 0.0, ..caps };
+        assert!(matches!(caps.resolve(5.0), Err(SkipReason::InsufficientOrderbookSize)));
+
+        // Floor is configurable
+        let caps = SizeCaps { depth: 2.0, max: 10.0, balance: 50.0, minted: f64::INFINITY };
+        assert_eq!(caps.resolve(1.0).unwrap(), 2.0);
+        assert!(matches!(SizeCaps { max: 0.5, ..caps }.resolve(1.0), Err(SkipReason::InvalidSize)));
+    }
+
+    #[test]
+    fn test_size_caps_from_balance_and_minted() {
+        let q = PriceSlot {
+            best_bid: 0.45,
+            best_ask: 0.40,
+            best_bid_size: 500.0,
+            best_ask_size: 500.0,
+            ..PriceSlot::default()
+        };
+        let signal = make_leg_signal(
+            StrategyKind::Monotone,
+            vec![
+                Leg::at_quote(LegRole::Parent, OrderSide::Buy, "buy".into(), String::new(), &q),
+                Leg::at_quote(LegRole::ParentUpper, OrderSide::Sell, "sell".into(), String::new(), &q),
+            ],
+        );
+        let candidates = build_order_candidates(&signal, &OrderTypePolicy::default());
+        let state = ValidationState::new();
+        state.set_balance(10.0);
+        state.set_minted_assets("g", HashMap::from([("sell".to_string(), 40.0)]));
+        let config = ExecutorConfig {
+            default_size: 100.0,
+            ..test_config()
+        };
+
+        let mut caps = SizeCaps::new(&signal, &candidates, &state, &config);
+        assert_eq!(caps.depth, 500.0);
+        assert_eq!(caps.minted, 40.0);
+        let reserved = caps.reserve(&state, estimate_required_cost(&candidates, 1.0, false));
+        assert!((reserved - 10.0).abs() < 1e-9); // wants 40 × $0.40, only $10 left
+        assert!((caps.balance - 25.0).abs() < 1e-9); // $10 / $0.40 per set
+        state.refund_balance(reserved);
+
+        state.trading_enabled.store(true, Ordering::Relaxed);
+        let result = should_skip(&signal, &state, &config).unwrap();
+        assert_eq!(result.size, 25.0);
+        assert!((result.required_cost - 10.0).abs() < 1e-9);
+        assert!(state.get_balance().abs() < 1e-9);
+        // SELL legs spend minted inventory, never reserved USDC
+        assert!(candidates
+            .iter()
+            .filter(|c| c.side == OrderSide::Sell)
+            .all(|c| leg_cost(c, 25.0, false) == 0.0));
+    }
+
+    #[test]
+    fn test_size_caps_price_slippage_and_fees() {
+        let q = PriceSlot {
+            best_bid: 0.45,
+            best_ask: 0.49,
+            best_bid_size: 500.0,
+            best_ask_size: 500.0,
+            ..PriceSlot::default()
+        };
+        let mut buy = Leg::at_quote(LegRole::Parent, OrderSide::Buy, "buy".into(), String::new(), &q);
+        buy.fee_rate_bps = 200;
+        let signal = make_leg_signal(StrategyKind::Monotone, vec![buy]);
+        let candidates = build_order_candidates(&signal, &OrderTypePolicy::default());
+        let state = ValidationState::new();
+        state.set_balance(10.2);
+        let config = ExecutorConfig {
+            default_size: 100.0,
+            slippage_enabled: true,
+            ..test_config()
+        };
+
+        // Signed at 0.50 (0.49 + one tick) plus 2% × 0.50 fee → $0.51 per set
+        let mut caps = SizeCaps::new(&signal, &candidates, &state, &config);
+        caps.reserve(&state, estimate_required_cost(&candidates, 1.0, true));
+        assert!((caps.balance - 20.0).abs() < 1e-9);
+        state.set_balance(10.2);
+
+        state.trading_enabled.store(true, Ordering::Relaxed);
+        let result = should_skip(&signal, &state, &config).unwrap();
+        assert_eq!(result.size, 20.0);
+        assert!((result.required_cost - 10.2).abs() < 1e-9);
+        // A killed half of the leg gives back half the reservation
+        assert!((leg_cost(&candidates[0], 10.0, true) - 5.1).abs() < 1e-9);
+    }
+
     #[test]
     fn test_slippage_changes_price_but_not_signing_logic() {
         // With slippage enabled, Executor adjusts price → different amounts.
@@ -792,14 +1345,13 @@ mod tests {
             side: OrderSide::Buy,
             orderbook_size: Some(100.0),
             neg_risk: true,
+            fee_rate_bps: 0,
+            order_type: OrderType::Gtc,
         };
         let config_slippage = ExecutorConfig {
-            min_pnl_threshold_percent: 0.0,
             default_size: size,
             slippage_enabled: true,
-            opportunity_timeout_ms: 5000,
-            maker_address: String::new(),
-            signer_address: String::new(),
+            ..test_config()
         };
         let mut exec_orders = prepare_batch_orders(&[candidate], size, &config_slippage);
         let exec_order = &mut exec_orders[0];
@@ -4,10 +4,11 @@
 //! Manages a pool of `WsClient` instances, distributing tokens across
 //! connections and dispatching parsed messages to the callback channel.
 
+use crate::engine::intern::TokenInterner;
 use crate::socket::parser;
 use crate::socket::ws_client::WsClient;
 use crate::types::config::SocketConfig;
-use crate::types::market::TopOfBookUpdate;
+use crate::types::market::TokenUpdate;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Arc;
@@ -25,8 +26,11 @@ pub struct SocketManager {
     /// Shutdown signal receiver (clone for each WsClient).
     shutdown_rx: watch::Receiver<bool>,
 
-    /// Channel to send TopOfBookUpdate to Node.js callback.
-    update_tx: mpsc::UnboundedSender<TopOfBookUpdate>,
+    /// Channel to send interned top-of-book updates to the engine dispatcher.
+    update_tx: mpsc::UnboundedSender<TokenUpdate>,
+
+    /// Token interner shared with the engine.
+    interner: Arc<TokenInterner>,
 
     /// Currently subscribed tokens, keyed by connection_id.
     connections: Arc<Mutex<HashMap<String, Vec<String>>>>,
@@ -48,7 +52,8 @@ impl SocketManager {
     /// Create a new SocketManager.
     pub fn new(
         config: SocketConfig,
-        update_tx: mpsc::UnboundedSender<TopOfBookUpdate>,
+        update_tx: mpsc::UnboundedSender<TokenUpdate>,
+        interner: Arc<TokenInterner>,
     ) -> Self {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
@@ -57,6 +62,7 @@ impl SocketManager {
             shutdown_tx,
             shutdown_rx,
             update_tx,
+            interner,
             connections: Arc::new(Mutex::new(HashMap::new())),
             messages_received: Arc::new(AtomicI64::new(0)),
             last_message_at_ms: Arc::new(AtomicI64::new(0)),
@@ -74,6 +80,11 @@ impl SocketManager {
             return;
         }
 
+        // Intern up front so the parser can resolve every token it will see
+        for token in &token_ids {
+            self.interner.intern(token);
+        }
+
         let max_per_conn = self.config.max_tokens_per_connection();
         let mut conns = self.connections.lock().await;
 
@@ -203,6 +214,7 @@ impl SocketManager {
 
         let shutdown_rx = self.shutdown_rx.clone();
         let update_tx = self.update_tx.clone();
+        let interner = self.interner.clone();
         let messages_received = self.messages_received.clone();
         let last_message_at_ms = self.last_message_at_ms.clone();
 
@@ -216,6 +228,7 @@ impl SocketManager {
 
         // Spawn message parser loop
         let parser_handle = tokio::spawn(async move {
+            let mut updates = Vec::new();
             while let Some((_conn_id, raw_bytes)) = raw_rx.recv().await {
                 // Track metrics
                 messages_received.fetch_add(1, Ordering::Relaxed);
@@ -229,8 +242,9 @@ impl SocketManager {
                 match parser::parse_ws_message(&raw_bytes) {
                     Ok(events) => {
                         for event in &events {
-                            let updates = parser::extract_top_of_book(event);
-                            for update in updates {
+                            updates.clear();
+                            parser::extract_token_updates(event, &interner, &mut updates);
+                            for &update in &updates {
                                 if update_tx.send(update).is_err() {
                                     warn!("Update channel closed, stopping parser");
                                     return;
@@ -3,8 +3,9 @@
 //! Parses raw WebSocket bytes into `WsEvent` variants and extracts
 //! `TopOfBookUpdate` data. Port of `buffer.service.ts` parsing logic.
 
+use crate::engine::intern::TokenInterner;
 use crate::types::market::{
-    Level, PriceChangeItem, RawWsMessage, TopOfBookUpdate, WsEvent,
+    Level, PriceChangeItem, RawWsMessage, TokenUpdate, TopOfBookUpdate, WsEvent,
 };
 
 // =============================================================================
@@ -107,6 +108,52 @@ pub fn extract_top_of_book(event: &WsEvent) -> Vec<TopOfBookUpdate> {
     }
 }
 
+/// Extract engine-bound `TokenUpdate`s from a `WsEvent` into `out`.
+///
+/// Resolves `asset_id` through the interner once, here, so the engine never
+/// hashes token strings. Tokens that were never interned are dropped.
+pub fn extract_token_updates(event: &WsEvent, interner: &TokenInterner, out: &mut Vec<TokenUpdate>) {
+    match event {
+        WsEvent::Book {
+            asset_id,
+            timestamp,
+            bids,
+            asks,
+            ..
+        } => {
+            let Some(token) = interner.get(asset_id) else {
+                return;
+            };
+            let (best_bid, best_ask, best_bid_size, best_ask_size) =
+                find_best_bid_ask(bids, asks);
+
+            out.push(TokenUpdate {
+                token,
+                best_bid,
+                best_ask,
+                best_bid_size: best_bid_size.is_finite().then_some(best_bid_size),
+                best_ask_size: best_ask_size.is_finite().then_some(best_ask_size),
+                timestamp_ms: normalize_timestamp_ms(*timestamp),
+            });
+        }
+        WsEvent::PriceChange {
+            timestamp, changes, ..
+        } => {
+            let ts_ms = normalize_timestamp_ms(*timestamp);
+            out.extend(changes.iter().filter_map(|change| {
+                Some(TokenUpdate {
+                    token: interner.get(&change.asset_id)?,
+                    best_bid: change.best_bid,
+                    best_ask: change.best_ask,
+                    best_bid_size: None,
+                    best_ask_size: None,
+                    timestamp_ms: ts_ms,
+                })
+            }));
+        }
+    }
+}
+
 // =============================================================================
 // INTERNAL HELPERS
 // =============================================================================
@@ -393,6 +440,31 @@ mod tests {
         assert!(events.is_empty());
     }
 
+    #[test]
+    fn test_extract_token_updates_resolves_ids() {
+        let interner = TokenInterner::new();
+        let a = interner.intern("tokenA");
+        let msg = r#"{
+            "event_type": "price_change",
+            "market": "0xdef456",
+            "timestamp": "1700000001000",
+            "price_changes": [
+                {"asset_id": "tokenA", "best_bid": "0.45", "best_ask": "0.55"},
+                {"asset_id": "unknown", "best_bid": "0.30", "best_ask": "0.70"}
+            ]
+        }"#;
+
+        let events = parse_ws_message(msg.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        extract_token_updates(&events[0], &interner, &mut out);
+
+        // Unknown tokens are dropped, known ones carry the interned id
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].token, a);
+        assert!((out[0].best_ask - 0.55).abs() < 1e-10);
+        assert_eq!(out[0].timestamp_ms, 1700000001000);
+    }
+
     #[test]
     fn test_find_best_bid_ask() {
         let bids = vec![
@@ -157,7 +157,8 @@ impl WsClient {
                             }
                         }
                         Some(Ok(Message::Binary(data))) => {
-                            if msg_tx.send((self.connection_id.clone(), data.to_vec())).is_err() {
+                            let bytes = data.to_vec();
+                            if msg_tx.send((self.connection_id.clone(), bytes)).is_err() {
                                 warn!(conn_id = %self.connection_id, "Message channel closed");
                                 return Ok(());
                             }
@@ -182,7 +183,7 @@ impl WsClient {
                 }
                 // Send periodic ping
                 _ = ping_timer.tick() => {
-                    write.send(Message::Ping(vec![].into())).await?;
+                    write.send(Message::Ping(vec![])).await?;
                 }
                 // Shutdown signal
                 _ = shutdown.changed() => {
@@ -216,7 +217,7 @@ impl WsClient {
             "assets_ids": self.tokens,
         });
         let text = serde_json::to_string(&sub_msg)?;
-        write.send(Message::Text(text.into())).await?;
+        write.send(Message::Text(text)).await?;
         Ok(())
     }
 }
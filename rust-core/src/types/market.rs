@@ -1,3 +1,4 @@
+use crate::engine::intern::TokenId;
 use napi_derive::napi;
 use serde::Deserialize;
 
@@ -42,6 +43,17 @@ pub struct Level {
     pub size: f64,
 }
 
+/// Top-of-book update resolved to an interned token — what the socket hands the engine.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenUpdate {
+    pub token: TokenId,
+    pub best_bid: f64,
+    pub best_ask: f64,
+    pub best_bid_size: Option<f64>,
+    pub best_ask_size: Option<f64>,
+    pub timestamp_ms: i64,
+}
+
 /// A single price change item within a `price_change` event.
 #[derive(Debug, Clone)]
 pub struct PriceChangeItem {
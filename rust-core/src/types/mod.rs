@@ -2,3 +2,4 @@ pub mod config;
 pub mod market;
 pub mod order;
 pub mod signal;
+pub mod strategy;
@@ -5,6 +5,8 @@
 
 use napi_derive::napi;
 
+use crate::types::signal::NapiSignalLeg;
+
 // =============================================================================
 // INTERNAL TYPES (Rust-only, no N-API overhead)
 // =============================================================================
@@ -20,6 +22,7 @@ pub struct OrderToSign {
     pub side: u8, // 0=BUY, 1=SELL
     pub neg_risk: bool,
     pub fee_rate_bps: u32,
+    pub expiration: u64, // Unix seconds, 0 = no expiry (GTD only)
 }
 
 /// Signed order ready for CLOB API payload.
@@ -50,6 +53,8 @@ pub struct OrderCandidate {
     pub side: OrderSide,
     pub orderbook_size: Option<f64>,
     pub neg_risk: bool,
+    pub fee_rate_bps: u32,
+    pub order_type: OrderType,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -74,6 +79,42 @@ impl OrderSide {
     }
 }
 
+/// CLOB time in force.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderType {
+    /// Good-til-cancelled — rests on the book.
+    Gtc,
+    /// Good-til-date — rests until the signed expiration.
+    Gtd,
+    /// Fill-or-kill — fills in full immediately or not at all.
+    Fok,
+    /// Fill-and-kill — fills what it can immediately, cancels the rest.
+    Fak,
+}
+
+impl OrderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderType::Gtc => "GTC",
+            OrderType::Gtd => "GTD",
+            OrderType::Fok => "FOK",
+            OrderType::Fak => "FAK",
+        }
+    }
+
+    /// Fills (or dies) at submission — the CLOB treats it as a market order.
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, OrderType::Fok | OrderType::Fak)
+    }
+
+    /// Parse a CLOB order type name, case-insensitive.
+    pub fn parse(s: &str) -> Option<Self> {
+        [OrderType::Gtc, OrderType::Gtd, OrderType::Fok, OrderType::Fak]
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(s))
+    }
+}
+
 // =============================================================================
 // N-API TYPES (Rust ↔ Node.js)
 // =============================================================================
@@ -85,9 +126,14 @@ pub struct NapiSuccessOrder {
     pub token_id: String,
     pub market_slug: String,
     pub side: String,
+    /// Average matched price (the limit while nothing matched).
     pub price: f64,
+    /// Shares matched at submission (0 while the order rests on the book).
     pub size: f64,
     pub neg_risk: bool,
+    pub order_type: String,
+    /// CLOB order status: `"matched"` | `"live"` | `"delayed"` | `"unmatched"`.
+    pub status: String,
 }
 
 /// Failed order detail — part of TradeResult.
@@ -98,6 +144,7 @@ pub struct NapiFailedOrder {
     pub market_slug: String,
     pub side: String,
     pub price: f64,
+    pub order_type: String,
     pub error_msg: String,
 }
 
@@ -110,7 +157,10 @@ pub struct TradeResult {
     pub successful_orders: Vec<NapiSuccessOrder>,
     pub failed_orders: Vec<NapiFailedOrder>,
     pub total_cost: f64,
+    /// Executed size × the signal's profit per set.
     pub expected_pnl: f64,
+    /// PnL of what filled at submission (resting orders count as unfilled).
+    pub realized_pnl: f64,
     pub latency_us: i64, // Microseconds
     pub signal_group_key: String,
     pub signal_event_slug: String,
@@ -159,6 +209,10 @@ pub struct TradeResult {
     pub signal_triangle_mode: Option<String>,
 
     pub signal_reason: String,
+    /// Every leg of the signal, in order.
+    pub signal_legs: Vec<NapiSignalLeg>,
+    /// Simulated by paper mode — nothing was posted to the CLOB.
+    pub paper: bool,
 }
 
 /// Executor config input from Node.js (init_executor / update_executor_config).
@@ -175,7 +229,55 @@ pub struct NapiExecutorConfigInput {
     pub min_pnl_threshold_percent: f64,
     pub default_size: f64,
     pub slippage_enabled: bool,
+    /// Per-group cooldown after an execution starts.
     pub opportunity_timeout_ms: Option<i64>,
+    /// Batches in flight at once across groups (default 4).
+    pub max_concurrent_executions: Option<u32>,
+    /// Default order type for executor orders: "GTC" | "GTD" | "FOK" | "FAK" (default GTC).
+    pub order_type: Option<String>,
+    /// Per-strategy / per-leg order types — the most specific matching rule wins.
+    pub order_type_rules: Option<Vec<NapiOrderTypeRule>>,
+    /// Lifetime of GTD orders after the exchange's 60s minimum (default 30s).
+    pub gtd_ttl_secs: Option<i64>,
+    /// Smallest size worth trading; thinner opportunities are skipped (default 5).
+    pub min_trade_size: Option<f64>,
+    /// Simulate fills against the live book instead of POSTing (default false).
+    pub paper_trading: Option<bool>,
+    /// Delay before a paper order is matched (default 260ms).
+    pub paper_latency_ms: Option<i64>,
+}
+
+/// Order type for executor orders matching a strategy and/or leg role.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiOrderTypeRule {
+    /// Strategy name (e.g. "ladder_buy"); None = any strategy.
+    pub strategy: Option<String>,
+    /// Leg role: "parent" | "parent_upper" | "child"; None = any leg.
+    pub leg_role: Option<String>,
+    /// "GTC" | "GTD" | "FOK" | "FAK"
+    pub order_type: String,
+}
+
+/// Net paper holding of one token.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiPaperPosition {
+    pub token_id: String,
+    pub market_slug: String,
+    pub size: f64,
+    pub cost: f64,
+}
+
+/// Paper-mode positions and PnL (get_paper_portfolio).
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiPaperPortfolio {
+    pub positions: Vec<NapiPaperPosition>,
+    pub pnl: f64,
+    pub fees_paid: f64,
+    pub trade_count: i64,
+    pub fill_count: i64,
 }
 
 // =============================================================================
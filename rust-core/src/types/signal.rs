@@ -1,29 +1,154 @@
-//! ArbSignal — flat N-API struct emitted from Rust engine to Node.js.
+//! Arbitrage signals — the engine's N-leg `ArbSignal` and its flat N-API projection.
 //!
-//! `RustEngineBridgeService` on the Node.js side converts this flat struct
-//! into the nested `ArbOpportunity` format that `RealExecutionService` expects.
+//! The engine and executor work on `ArbSignal`: a typed strategy plus a list of
+//! legs. `FlatArbSignal` (`ArbSignal` on the JS side) projects it onto the fixed
+//! parent / parent-upper / child layout that `RustEngineBridgeService` converts
+//! into the nested `ArbOpportunity` format.
 
+use crate::types::order::OrderSide;
+use crate::types::strategy::StrategyKind;
 use napi_derive::napi;
 
-/// Arbitrage signal emitted to Node.js via callback.
+// =============================================================================
+// N-LEG SIGNAL (Rust-only)
+// =============================================================================
+
+/// Structural role of a leg — picks its slot in the flat projection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegRole {
+    /// Lower parent (or the only / bought market of a two-market strategy).
+    Parent,
+    /// Upper parent (or the second market / token of a two-leg strategy).
+    ParentUpper,
+    /// Range child or neg-risk outcome.
+    Child,
+}
+
+impl LegRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LegRole::Parent => "parent",
+            LegRole::ParentUpper => "parent_upper",
+            LegRole::Child => "child",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        [LegRole::Parent, LegRole::ParentUpper, LegRole::Child]
+            .into_iter()
+            .find(|r| r.as_str() == s)
+    }
+}
+
+/// One leg of a signal: a token traded at top of book.
+#[derive(Debug, Clone)]
+pub struct Leg {
+    pub role: LegRole,
+    pub side: OrderSide,
+    pub asset_id: String,
+    pub market_slug: String,
+    /// Ask for BUY legs, bid for SELL legs.
+    pub price: f64,
+    /// Top-of-book size at `price`.
+    pub size_available: f64,
+    /// Full top of book when the signal fired.
+    pub best_bid: f64,
+    pub best_ask: f64,
+    pub best_bid_size: f64,
+    pub best_ask_size: f64,
+    pub neg_risk: bool,
+    pub fee_rate_bps: u32,
+    /// Range child / outcome index (-1 for parents).
+    pub child_index: i32,
+}
+
+/// Arbitrage signal emitted by the engine and consumed by the executor.
+#[derive(Debug, Clone)]
+pub struct ArbSignal {
+    pub group_key: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub strategy: StrategyKind,
+
+    /// Net of every leg's taker fee — engine thresholds apply to these.
+    pub profit_abs: f64,
+    pub profit_bps: f64,
+    /// Before taker fees.
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
+    pub timestamp_ms: i64,
+
+    /// Every leg to trade: parents first, then children by bound / outcome.
+    pub legs: Vec<Leg>,
+    /// Range child / market the signal is about (-1 for none).
+    pub child_index: i32,
+
+    // Set context — total cost / bid of the set and what it pays out
+    pub total_cost: Option<f64>,
+    pub total_bid: Option<f64>,
+    pub payout: Option<f64>,
+    pub mode: Option<&'static str>, // "BUY" | "SELL" | "CONVERT"
+
+    pub reason: &'static str,
+}
+
+impl ArbSignal {
+    /// Size fillable on every leg at once: min of `size_available` over the legs.
+    pub fn max_executable_size(&self) -> f64 {
+        self.legs
+            .iter()
+            .fold(f64::INFINITY, |min, l| min.min(l.size_available))
+            .max(0.0)
+    }
+
+    /// Legs with `role`, in order.
+    pub fn legs_with_role(&self, role: LegRole) -> impl Iterator<Item = &Leg> {
+        self.legs.iter().filter(move |l| l.role == role)
+    }
+
+    /// Sum of `price` over the `Child` legs, skipping unquoted (NaN) sides.
+    pub fn children_sum(&self, price: fn(&Leg) -> f64) -> f64 {
+        self.legs_with_role(LegRole::Child)
+            .map(price)
+            .filter(|p| !p.is_nan())
+            .sum()
+    }
+}
+
+// =============================================================================
+// FLAT N-API PROJECTION
+// =============================================================================
+
+/// Flat projection of an `ArbSignal` for Node.js.
 ///
 /// Flat layout for optimal N-API serialization.
 /// Contains all fields needed by `RealExecutionService.buildOrderCandidates()`
-/// and `calculateTotalCost()`.
-#[napi(object)]
+/// and `calculateTotalCost()`; `legs` carries the full N-leg form.
+#[napi(object, js_name = "ArbSignal")]
 #[derive(Debug, Clone)]
-pub struct ArbSignal {
+pub struct FlatArbSignal {
     // ── Group metadata ──
     pub group_key: String,
     pub event_slug: String,
     pub crypto: String,
-    pub strategy: String, // "POLYMARKET_TRIANGLE_BUY" | "SELL_PARENT_BUY_CHILDREN" | "BUY_PARENT_SELL_CHILDREN"
+    /// `StrategyKind::signal_name`, e.g. "POLYMARKET_TRIANGLE_BUY" | "POLYMARKET_LADDER_SELL"
+    /// | "POLYMARKET_NEG_RISK_CONVERT".
+    pub strategy: String,
 
     // ── Profit ──
+    /// Net of every leg's taker fee — engine thresholds apply to these.
     pub profit_abs: f64,
     pub profit_bps: f64,
+    /// Before taker fees.
+    pub gross_profit_abs: f64,
+    pub gross_profit_bps: f64,
     pub timestamp_ms: i64,
 
+    // ── Depth ──
+    /// Size fillable on every leg at top of book: min of ask sizes on BUY legs
+    /// and bid sizes on SELL legs (0 when any leg size is unknown).
+    pub max_executable_size: f64,
+
     // ── Parent Lower (YES token — all strategies) ──
     pub parent_asset_id: String,
     pub parent_market_slug: String,
@@ -32,6 +157,7 @@ pub struct ArbSignal {
     pub parent_best_bid_size: Option<f64>,
     pub parent_best_ask_size: Option<f64>,
     pub parent_neg_risk: bool,
+    pub parent_fee_rate_bps: u32,
 
     // ── Parent Upper ──
     // For Triangle: NO token prices; For Range arb: YES token prices
@@ -42,6 +168,7 @@ pub struct ArbSignal {
     pub parent_upper_best_bid_size: Option<f64>,
     pub parent_upper_best_ask_size: Option<f64>,
     pub parent_upper_neg_risk: bool,
+    pub parent_upper_fee_rate_bps: u32,
 
     // ── Range Child ──
     // For Triangle: NO token prices; For Range arb: YES token prices
@@ -52,8 +179,13 @@ pub struct ArbSignal {
     pub child_best_bid_size: Option<f64>,
     pub child_best_ask_size: Option<f64>,
     pub child_neg_risk: bool,
+    pub child_fee_rate_bps: u32,
     pub child_index: i32,
 
+    // ── Further Range Children (brackets spanning several children) ──
+    // Same token side as `child_*`; empty for trio signals
+    pub extra_child_legs: Vec<SignalLeg>,
+
     // ── Aggregates for RealExecutionService.calculateTotalCost() ──
     pub children_sum_ask: f64,
     pub children_sum_bid: f64,
@@ -69,4 +201,204 @@ pub struct ArbSignal {
     pub triangle_mode: Option<String>,
 
     pub reason: String,
+
+    // ── Every leg, in order ──
+    pub legs: Vec<NapiSignalLeg>,
+}
+
+/// One additional range-child leg of a flat signal.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SignalLeg {
+    pub asset_id: String,
+    pub market_slug: String,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub best_bid_size: Option<f64>,
+    pub best_ask_size: Option<f64>,
+    pub neg_risk: bool,
+    pub fee_rate_bps: u32,
+    pub child_index: i32,
+}
+
+/// One leg of a signal, as sent to Node.js.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NapiSignalLeg {
+    pub role: String, // "parent" | "parent_upper" | "child"
+    pub side: String, // "BUY" | "SELL"
+    pub asset_id: String,
+    pub market_slug: String,
+    pub price: f64,
+    pub size_available: f64,
+    pub best_bid: f64,
+    pub best_ask: f64,
+    pub best_bid_size: f64,
+    pub best_ask_size: f64,
+    pub neg_risk: bool,
+    pub fee_rate_bps: u32,
+    pub child_index: i32,
+}
+
+impl From<&Leg> for NapiSignalLeg {
+    fn from(leg: &Leg) -> Self {
+        Self {
+            role: leg.role.as_str().to_string(),
+            side: leg.side.as_str().to_string(),
+            asset_id: leg.asset_id.clone(),
+            market_slug: leg.market_slug.clone(),
+            price: leg.price,
+            size_available: leg.size_available,
+            best_bid: leg.best_bid,
+            best_ask: leg.best_ask,
+            best_bid_size: leg.best_bid_size,
+            best_ask_size: leg.best_ask_size,
+            neg_risk: leg.neg_risk,
+            fee_rate_bps: leg.fee_rate_bps,
+            child_index: leg.child_index,
+        }
+    }
+}
+
+impl From<&Leg> for SignalLeg {
+    fn from(leg: &Leg) -> Self {
+        Self {
+            asset_id: leg.asset_id.clone(),
+            market_slug: leg.market_slug.clone(),
+            best_bid: Some(leg.best_bid),
+            best_ask: Some(leg.best_ask),
+            best_bid_size: Some(leg.best_bid_size),
+            best_ask_size: Some(leg.best_ask_size),
+            neg_risk: leg.neg_risk,
+            fee_rate_bps: leg.fee_rate_bps,
+            child_index: leg.child_index,
+        }
+    }
+}
+
+impl From<&ArbSignal> for FlatArbSignal {
+    /// First `Parent` / `ParentUpper` / `Child` leg fill the fixed slots (empty
+    /// when the strategy has none); further children go to `extra_child_legs`.
+    fn from(signal: &ArbSignal) -> Self {
+        let parent = signal.legs_with_role(LegRole::Parent).next().map(SignalLeg::from);
+        let upper = signal.legs_with_role(LegRole::ParentUpper).next().map(SignalLeg::from);
+        let mut children = signal.legs_with_role(LegRole::Child);
+        let child = children.next().map(SignalLeg::from);
+        let extra_child_legs: Vec<SignalLeg> = children.map(SignalLeg::from).collect();
+        let empty = SignalLeg {
+            asset_id: String::new(),
+            market_slug: String::new(),
+            best_bid: None,
+            best_ask: None,
+            best_bid_size: None,
+            best_ask_size: None,
+            neg_risk: false,
+            fee_rate_bps: 0,
+            child_index: -1,
+        };
+        let parent = parent.unwrap_or_else(|| empty.clone());
+        let upper = upper.unwrap_or_else(|| empty.clone());
+        let child = child.unwrap_or(empty);
+
+        Self {
+            group_key: signal.group_key.clone(),
+            event_slug: signal.event_slug.clone(),
+            crypto: signal.crypto.clone(),
+            strategy: signal.strategy.signal_name().to_string(),
+
+            profit_abs: signal.profit_abs,
+            profit_bps: signal.profit_bps,
+            gross_profit_abs: signal.gross_profit_abs,
+            gross_profit_bps: signal.gross_profit_bps,
+            timestamp_ms: signal.timestamp_ms,
+            max_executable_size: signal.max_executable_size(),
+
+            parent_best_bid_flat: parent.best_bid,
+            parent_best_ask_flat: parent.best_ask,
+            parent_asset_id: parent.asset_id,
+            parent_market_slug: parent.market_slug,
+            parent_best_bid: parent.best_bid,
+            parent_best_ask: parent.best_ask,
+            parent_best_bid_size: parent.best_bid_size,
+            parent_best_ask_size: parent.best_ask_size,
+            parent_neg_risk: parent.neg_risk,
+            parent_fee_rate_bps: parent.fee_rate_bps,
+
+            parent_upper_best_bid_flat: upper.best_bid,
+            parent_upper_best_ask_flat: upper.best_ask,
+            parent_upper_asset_id: upper.asset_id,
+            parent_upper_market_slug: upper.market_slug,
+            parent_upper_best_bid: upper.best_bid,
+            parent_upper_best_ask: upper.best_ask,
+            parent_upper_best_bid_size: upper.best_bid_size,
+            parent_upper_best_ask_size: upper.best_ask_size,
+            parent_upper_neg_risk: upper.neg_risk,
+            parent_upper_fee_rate_bps: upper.fee_rate_bps,
+
+            child_asset_id: child.asset_id,
+            child_market_slug: child.market_slug,
+            child_best_bid: child.best_bid,
+            child_best_ask: child.best_ask,
+            child_best_bid_size: child.best_bid_size,
+            child_best_ask_size: child.best_ask_size,
+            child_neg_risk: child.neg_risk,
+            child_fee_rate_bps: child.fee_rate_bps,
+            child_index: signal.child_index,
+            extra_child_legs,
+
+            children_sum_ask: signal.children_sum(|l| l.best_ask),
+            children_sum_bid: signal.children_sum(|l| l.best_bid),
+
+            triangle_total_cost: signal.total_cost,
+            triangle_total_bid: signal.total_bid,
+            triangle_payout: signal.payout,
+            triangle_mode: signal.mode.map(str::to_string),
+
+            reason: signal.reason.to_string(),
+
+            legs: signal.legs.iter().map(NapiSignalLeg::from).collect(),
+        }
+    }
+}
+
+/// Transition reported by an opportunity tracker — `"opened"` | `"updated"` | `"closed"` in JS.
+#[napi(string_enum = "lowercase")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum OpportunityPhase {
+    /// The edge crossed the threshold.
+    Opened,
+    /// The open opportunity reached a new peak edge.
+    Updated,
+    /// The edge fell below threshold, a leg lost its quote, or the structure
+    /// or strategy went away.
+    Closed,
+}
+
+/// Opportunity lifecycle event — one per transition of a strategy's edge on
+/// one structure across the threshold, plus one per new peak while open.
+///
+/// `timestamp_ms` is when the transition happened (the close time for
+/// `"closed"`); `profit_*` is the edge at that moment (NaN when the close was
+/// caused by a leg losing its quote, the last evaluated edge when the
+/// structure was dropped or its strategy disabled).
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct OpportunityEvent {
+    pub phase: OpportunityPhase,
+    pub group_key: String,
+    pub event_slug: String,
+    pub crypto: String,
+    pub strategy: String, // StrategyKind name, e.g. "triangle"
+    pub structure: String, // "trio" | "bracket" | "ladder" | "monotone" | "parity" | "neg_risk"
+    pub structure_index: u32,
+
+    pub opened_at_ms: i64,
+    pub timestamp_ms: i64,
+    pub duration_ms: i64,
+
+    pub peak_profit_abs: f64,
+    pub peak_profit_bps: f64,
+    pub peak_at_ms: i64,
+    pub profit_abs: f64,
+    pub profit_bps: f64,
 }
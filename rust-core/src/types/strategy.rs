@@ -0,0 +1,109 @@
+//! Arbitrage strategy kinds.
+//!
+//! Shared by the engine (per-strategy config, trackers), the signals it emits
+//! and the executor (order-type rules, executability).
+
+/// Arbitrage strategies evaluated by the engine — used to key per-strategy config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StrategyKind {
+    /// Parent lower YES + parent upper NO + range NO (payout = 2)
+    Triangle,
+    /// Parent lower NO + range YES + parent upper YES (payout = 1)
+    Complement,
+    /// Sell parent lower YES, buy range YES + parent upper YES
+    Unbundle,
+    /// Buy parent lower YES, sell range YES + parent upper YES
+    Bundle,
+    /// Buy every range child YES of a complete partition (payout = 1)
+    LadderBuy,
+    /// Sell every range child YES of a complete partition against minted sets
+    LadderSell,
+    /// Buy the under-priced / sell the over-priced YES of two same-kind parents
+    Monotone,
+    /// Buy YES + NO of one market and merge (payout = 1)
+    ParityBuy,
+    /// Mint YES + NO of one market and sell both
+    ParitySell,
+    /// Buy the YES of every outcome of a neg-risk event (payout = 1)
+    NegRiskYesBuy,
+    /// Buy the NO of every outcome of a neg-risk event (payout = outcomes - 1)
+    NegRiskNoBuy,
+    /// Buy one outcome's NO, convert it into every other YES, sell those
+    NegRiskConvert,
+}
+
+impl StrategyKind {
+    pub const COUNT: usize = 12;
+    pub const ALL: [StrategyKind; StrategyKind::COUNT] = [
+        StrategyKind::Triangle,
+        StrategyKind::Complement,
+        StrategyKind::Unbundle,
+        StrategyKind::Bundle,
+        StrategyKind::LadderBuy,
+        StrategyKind::LadderSell,
+        StrategyKind::Monotone,
+        StrategyKind::ParityBuy,
+        StrategyKind::ParitySell,
+        StrategyKind::NegRiskYesBuy,
+        StrategyKind::NegRiskNoBuy,
+        StrategyKind::NegRiskConvert,
+    ];
+
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StrategyKind::Triangle => "triangle",
+            StrategyKind::Complement => "complement",
+            StrategyKind::Unbundle => "unbundle",
+            StrategyKind::Bundle => "bundle",
+            StrategyKind::LadderBuy => "ladder_buy",
+            StrategyKind::LadderSell => "ladder_sell",
+            StrategyKind::Monotone => "monotone",
+            StrategyKind::ParityBuy => "parity_buy",
+            StrategyKind::ParitySell => "parity_sell",
+            StrategyKind::NegRiskYesBuy => "neg_risk_yes_buy",
+            StrategyKind::NegRiskNoBuy => "neg_risk_no_buy",
+            StrategyKind::NegRiskConvert => "neg_risk_convert",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        StrategyKind::ALL.into_iter().find(|k| k.as_str() == s)
+    }
+
+    /// Whether the executor can trade this strategy's signals. CONVERT needs an
+    /// on-chain `convertPositions` between the NO fill and the YES sells, which the
+    /// executor does not perform — its signals are info-only (JS feed, no orders).
+    pub fn is_executable(self) -> bool {
+        !matches!(self, StrategyKind::NegRiskConvert)
+    }
+
+    /// Whether the engine evaluates this strategy out of the box. Only the triangle
+    /// strategies trade by default; every other strategy is opt-in via
+    /// `update_engine_config` so upgrades never start new trading.
+    pub fn enabled_by_default(self) -> bool {
+        matches!(self, StrategyKind::Triangle | StrategyKind::Complement)
+    }
+
+    /// Strategy name carried by signals (`ArbSignal.strategy` on the JS side).
+    pub fn signal_name(self) -> &'static str {
+        match self {
+            StrategyKind::Triangle => "POLYMARKET_TRIANGLE_BUY",
+            StrategyKind::Complement => "POLYMARKET_COMPLEMENT_BUY",
+            StrategyKind::Unbundle => "SELL_PARENT_BUY_CHILDREN",
+            StrategyKind::Bundle => "BUY_PARENT_SELL_CHILDREN",
+            StrategyKind::LadderBuy => "POLYMARKET_LADDER_BUY",
+            StrategyKind::LadderSell => "POLYMARKET_LADDER_SELL",
+            StrategyKind::Monotone => "POLYMARKET_MONOTONE_BUY_SELL",
+            StrategyKind::ParityBuy => "POLYMARKET_PARITY_BUY",
+            StrategyKind::ParitySell => "POLYMARKET_PARITY_SELL",
+            StrategyKind::NegRiskYesBuy => "POLYMARKET_NEG_RISK_YES_BUY",
+            StrategyKind::NegRiskNoBuy => "POLYMARKET_NEG_RISK_NO_BUY",
+            StrategyKind::NegRiskConvert => "POLYMARKET_NEG_RISK_CONVERT",
+        }
+    }
+}
@@ -86,4 +86,7 @@ export class Market extends BaseEntity {
     nullable: true,
   })
   negRiskMarketID: string | null;
+
+  @Column({ name: 'taker_base_fee', type: 'int', nullable: true })
+  takerBaseFee: number | null; // taker fee rate in bps, same for both tokens
 }
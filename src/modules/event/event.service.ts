@@ -284,6 +284,12 @@ export class EventCrawlerService implements OnApplicationBootstrap {
       market.negRiskMarketID ??
       null;
 
+    // Set taker fee rate (bps)
+    market.takerBaseFee =
+      marketData?.takerBaseFee !== undefined
+        ? Number(marketData.takerBaseFee)
+        : market.takerBaseFee ?? null;
+
     await this.marketRepository.save(market);
   }
 }
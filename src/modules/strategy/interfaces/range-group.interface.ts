@@ -20,6 +20,10 @@ export interface MarketRangeDescriptor {
   role: 'parent' | 'child' | 'unknown';
   /** Whether this is a negRisk market (uses different exchange contract) */
   negRisk?: boolean;
+  /** Neg-risk market id shared by every outcome of the event */
+  negRiskMarketId?: string;
+  /** Taker fee rate in bps, one entry per clobTokenIds entry */
+  feeRateBps?: number[];
 }
 
 export interface RangeGroup {
@@ -33,3 +37,11 @@ export interface RangeGroup {
   unmatched: MarketRangeDescriptor[];
   overridesApplied: string[];
 }
+
+/** Every live outcome of one neg-risk event, across all groups */
+export interface NegRiskEvent {
+  negRiskMarketId: string;
+  eventSlug?: string;
+  crypto?: string;
+  outcomes: MarketRangeDescriptor[];
+}
@@ -4,6 +4,7 @@ import { Repository } from 'typeorm';
 import { Market } from '../../database/entities/market.entity';
 import {
   MarketRangeDescriptor,
+  NegRiskEvent,
   RangeBoundary,
   RangeGroup,
   RangeKind,
@@ -60,6 +61,37 @@ export class MarketStructureService {
     return Array.from(this.cache.values());
   }
 
+  /**
+   * Neg-risk events of the cached groups, bucketed by neg-risk market id.
+   * Only active, unexpired markets are loaded, so each event lists the
+   * outcomes that can still resolve YES.
+   */
+  getNegRiskEvents(): NegRiskEvent[] {
+    const events = new Map<string, NegRiskEvent>();
+    for (const group of this.cache.values()) {
+      const descriptors = [
+        ...group.children,
+        ...group.parents,
+        ...group.unmatched,
+      ];
+      for (const descriptor of descriptors) {
+        if (!descriptor.negRisk || !descriptor.negRiskMarketId) continue;
+
+        const event = events.get(descriptor.negRiskMarketId) || {
+          negRiskMarketId: descriptor.negRiskMarketId,
+          eventSlug: descriptor.eventSlug,
+          crypto: group.crypto,
+          outcomes: [],
+        };
+        if (!event.outcomes.some((o) => o.marketId === descriptor.marketId)) {
+          event.outcomes.push(descriptor);
+        }
+        events.set(descriptor.negRiskMarketId, event);
+      }
+    }
+    return Array.from(events.values());
+  }
+
   /**
    * Calculate groupKey from market (public method for external use)
    */
@@ -213,13 +245,14 @@ export class MarketStructureService {
       this.parseRange(market.question, 'question') ||
       this.parseRange(market.slug, 'slug');
 
+    const clobTokenIds = Array.isArray(market.clobTokenIds)
+      ? market.clobTokenIds
+      : [];
     const descriptor: MarketRangeDescriptor = {
       marketId: market.marketId,
       slug: market.slug,
       question: market.question,
-      clobTokenIds: Array.isArray(market.clobTokenIds)
-        ? market.clobTokenIds
-        : [],
+      clobTokenIds,
       type: market.type,
       eventSlug: market.event?.slug,
       eventId: market.eventId,
@@ -229,6 +262,11 @@ export class MarketStructureService {
       parsedFrom: parsed?.source,
       role: this.kindToRole(parsed?.kind || 'unknown'),
       negRisk: market.negRisk ?? undefined,
+      negRiskMarketId: market.negRiskMarketID ?? undefined,
+      feeRateBps:
+        market.takerBaseFee != null
+          ? clobTokenIds.map(() => market.takerBaseFee as number)
+          : undefined,
     };
 
     const applied: string[] = [];
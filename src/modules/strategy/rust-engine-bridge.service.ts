@@ -13,7 +13,7 @@ import { loadPolymarketConfig } from '../../common/services/polymarket-onchain.c
 import { PolymarketOnchainService } from '../../common/services/polymarket-onchain.service';
 import { RedisService } from '../../common/services/redis.service';
 import { WORKER_USDC_BALANCE_KEY } from '../worker/worker-cron.service';
-import { RangeGroup, MarketRangeDescriptor } from './interfaces/range-group.interface';
+import { RangeGroup, MarketRangeDescriptor, NegRiskEvent } from './interfaces/range-group.interface';
 
 /**
  * Rust TradeResult — received from Rust executor via onTradeResult callback.
@@ -26,19 +26,23 @@ export interface RustTradeResult {
         tokenId: string;
         marketSlug: string;
         side: string;
-        price: number;
-        size: number;
+        price: number; // average matched price (the limit while nothing matched)
+        size: number; // shares matched at submission (0 while resting)
         negRisk: boolean;
+        orderType: string; // "GTC" | "GTD" | "FOK" | "FAK"
+        status: string; // "matched" | "live" | "delayed" | "unmatched"
     }>;
     failedOrders: Array<{
         tokenId: string;
         marketSlug: string;
         side: string;
         price: number;
+        orderType: string;
         errorMsg: string;
     }>;
     totalCost: number;
-    expectedPnl: number;
+    expectedPnl: number; // executed size × signal profit per set
+    realizedPnl: number; // PnL of what filled at submission
     latencyUs: number;
     signalGroupKey: string;
     signalEventSlug: string;
@@ -87,6 +91,26 @@ export interface RustTradeResult {
     signalTriangleMode: string | null;
 
     signalReason: string;
+
+    // Every leg of the signal, in order
+    signalLegs: Array<{
+        role: string; // "parent" | "parent_upper" | "child"
+        side: string; // "BUY" | "SELL"
+        assetId: string;
+        marketSlug: string;
+        price: number;
+        sizeAvailable: number;
+        bestBid: number;
+        bestAsk: number;
+        bestBidSize: number;
+        bestAskSize: number;
+        negRisk: boolean;
+        feeRateBps: number;
+        childIndex: number;
+    }>;
+
+    // Simulated by the Rust paper executor — nothing was posted
+    paper: boolean;
 }
 
 /**
@@ -94,7 +118,8 @@ export interface RustTradeResult {
  *
  * When `RUN_MODE=rust`, this service:
  * 1. Loads the rust-core native module
- * 2. Pushes market structure to Rust via updateMarketStructure()
+ * 2. Pushes market structure to Rust via updateMarketStructure() and
+ *    updateNegRiskEvents()
  * 3. Initializes the Rust executor (signer + HTTP client + validation)
  * 4. Registers onTradeResult() callback to receive trade results
  * 5. Pushes USDC balance updates to Rust via updateBalance()
@@ -277,18 +302,45 @@ export class RustEngineBridgeService
         // Convert RangeGroup[] to Rust N-API input format
         const napiGroups = groups.map((g) => this.convertGroupToNapi(g));
 
-        const trioCount = this.rustCore.updateMarketStructure(napiGroups);
+        const report = this.rustCore.updateMarketStructure(napiGroups);
+        const trioCount = report.totalTrios;
+
+        // Neg-risk events span groups — replaced as a whole on every sync
+        const negRiskEvents = this.marketStructureService
+            .getNegRiskEvents()
+            .map((e) => this.convertNegRiskEventToNapi(e));
+        const negRiskCount = this.rustCore.updateNegRiskEvents(negRiskEvents);
 
         // Rebuild descriptor cache for signal conversion
         this.rebuildDescriptorCache(groups);
 
         this.logger.log(
-            `Rust engine synced: ${groups.length} groups, ${trioCount} trios`,
+            `Rust engine synced: ${groups.length} groups, ${trioCount} trios, ${negRiskCount}/${negRiskEvents.length} neg-risk events`,
         );
+        this.logStructureDiagnostics(report.groups);
 
         return trioCount;
     }
 
+    /**
+     * Warn about market builder input the Rust engine could not use.
+     */
+    private logStructureDiagnostics(groups: any[]): void {
+        for (const g of groups) {
+            const issues: string[] = [
+                ...g.rejectedMarkets.map((r: any) => `rejected ${r.role} ${r.marketId}: ${r.reason}`),
+                ...g.boundGaps.map((b: any) => `gap ${b.lowerMarketId} → ${b.upperMarketId} (${b.from} → ${b.to})`),
+                ...g.boundOverlaps.map((b: any) => `overlap ${b.lowerMarketId} / ${b.upperMarketId} (${b.from} > ${b.to})`),
+                ...g.duplicateTokens.map((t: any) => `duplicate token ${t.tokenId} in ${t.marketIds.join(', ')}`),
+                ...g.parentsWithoutTrio.map((p: any) => `parent ${p.marketId} in no trio: ${p.reason}`),
+                ...g.notes,
+            ];
+            if (issues.length > 0) {
+                this.logger.warn(`Structure issues in ${g.groupKey}: ${issues.join('; ')}`);
+            }
+        }
+    }
+
     /**
      * Called by MarketService when groups are cleaned up.
      */
@@ -323,7 +375,7 @@ export class RustEngineBridgeService
     }
 
     // =========================================================================
-    // CONVERSION: RangeGroup → Rust N-API input
+    // CONVERSION: RangeGroup / NegRiskEvent → Rust N-API input
     // =========================================================================
 
     private convertGroupToNapi(group: RangeGroup): any {
@@ -333,26 +385,38 @@ export class RustEngineBridgeService
             crypto: group.crypto || '',
             children: group.children
                 .filter((c) => c.clobTokenIds?.length >= 2)
-                .map((c) => ({
-                    marketId: c.marketId,
-                    slug: c.slug,
-                    clobTokenIds: c.clobTokenIds,
-                    boundsLower: c.bounds?.lower ?? undefined,
-                    boundsUpper: c.bounds?.upper ?? undefined,
-                    kind: c.kind || 'unknown',
-                    negRisk: c.negRisk ?? false,
-                })),
+                .map((c) => this.convertDescriptorToNapi(c)),
             parents: group.parents
                 .filter((p) => p.clobTokenIds?.length >= 2)
-                .map((p) => ({
-                    marketId: p.marketId,
-                    slug: p.slug,
-                    clobTokenIds: p.clobTokenIds,
-                    boundsLower: p.bounds?.lower ?? undefined,
-                    boundsUpper: p.bounds?.upper ?? undefined,
-                    kind: p.kind || 'unknown',
-                    negRisk: p.negRisk ?? false,
-                })),
+                .map((p) => this.convertDescriptorToNapi(p)),
+        };
+    }
+
+    private convertDescriptorToNapi(d: MarketRangeDescriptor): any {
+        return {
+            marketId: d.marketId,
+            slug: d.slug,
+            clobTokenIds: d.clobTokenIds,
+            boundsLower: d.bounds?.lower ?? undefined,
+            boundsUpper: d.bounds?.upper ?? undefined,
+            kind: d.kind || 'unknown',
+            negRisk: d.negRisk ?? false,
+            // Per-token taker fee; the engine nets it out of every edge
+            feeRateBps: d.feeRateBps,
+        };
+    }
+
+    private convertNegRiskEventToNapi(event: NegRiskEvent): any {
+        return {
+            eventId: event.negRiskMarketId,
+            eventSlug: event.eventSlug || '',
+            crypto: event.crypto || undefined,
+            outcomes: event.outcomes.map((o) => ({
+                marketId: o.marketId,
+                slug: o.slug,
+                clobTokenIds: o.clobTokenIds,
+                feeRateBps: o.feeRateBps,
+            })),
         };
     }
 
@@ -425,6 +489,26 @@ export class RustEngineBridgeService
                     process.env.ARB_COOLDOWN_MS || '20000',
                     10,
                 ),
+                maxConcurrentExecutions: parseInt(
+                    process.env.REAL_TRADING_MAX_CONCURRENT || '4',
+                    10,
+                ),
+                orderType: process.env.REAL_TRADING_ORDER_TYPE || undefined,
+                orderTypeRules: this.parseOrderTypeRules(
+                    process.env.REAL_TRADING_ORDER_TYPE_RULES,
+                ),
+                gtdTtlSecs: parseInt(
+                    process.env.REAL_TRADING_GTD_TTL_SECS || '30',
+                    10,
+                ),
+                minTradeSize: parseFloat(
+                    process.env.REAL_TRADE_MIN_SIZE || '5',
+                ),
+                paperTrading: process.env.RUST_PAPER_TRADING === 'true',
+                paperLatencyMs: parseInt(
+                    process.env.PAPER_TRADE_LATENCY_MS || '260',
+                    10,
+                ),
             });
 
             // Register trade result callback
@@ -457,7 +541,8 @@ export class RustEngineBridgeService
             );
             this.logger.log(
                 `   trading=${this.tradingEnabled}, size=${process.env.REAL_TRADE_SIZE || '10'}, ` +
-                `pnl_threshold=${process.env.REAL_TRADING_MIN_PNL_PERCENT || '1'}%`,
+                `pnl_threshold=${process.env.REAL_TRADING_MIN_PNL_PERCENT || '1'}%, ` +
+                `paper=${process.env.RUST_PAPER_TRADING === 'true'}`,
             );
         } catch (error) {
             this.logger.error(
@@ -470,6 +555,23 @@ export class RustEngineBridgeService
         }
     }
 
+    /**
+     * Parse executor order type rules from a JSON array, e.g.
+     * `[{"strategy":"ladder_buy","orderType":"FOK"},{"legRole":"child","orderType":"FAK"}]`.
+     */
+    private parseOrderTypeRules(
+        raw: string | undefined,
+    ): Array<{ strategy?: string; legRole?: string; orderType: string }> | undefined {
+        if (!raw) return undefined;
+        try {
+            const rules = JSON.parse(raw);
+            return Array.isArray(rules) ? rules : undefined;
+        } catch (err) {
+            this.logger.warn(`Invalid REAL_TRADING_ORDER_TYPE_RULES: ${err.message}`);
+            return undefined;
+        }
+    }
+
     /**
      * Read USDC balance from Redis (or fallback) and push to Rust executor.
      */
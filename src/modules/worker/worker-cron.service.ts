@@ -453,6 +453,10 @@ export class WorkerCronService implements OnModuleInit, OnModuleDestroy {
             marketData?.neg_risk_market_id ??
             market.negRiskMarketID ??
             null;
+        market.takerBaseFee =
+            marketData?.takerBaseFee !== undefined
+                ? Number(marketData.takerBaseFee)
+                : market.takerBaseFee ?? null;
 
         await this.marketRepository.save(market);
     }
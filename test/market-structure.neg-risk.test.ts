@@ -0,0 +1,65 @@
+/**
+ * Unit tests for MarketStructureService - neg-risk event bucketing
+ */
+
+import { MarketStructureService } from '../src/modules/strategy/market-structure.service';
+import {
+  MarketRangeDescriptor,
+  RangeGroup,
+} from '../src/modules/strategy/interfaces/range-group.interface';
+
+function outcome(
+  marketId: string,
+  negRiskMarketId?: string,
+  negRisk = true,
+): MarketRangeDescriptor {
+  return {
+    marketId,
+    slug: marketId,
+    question: marketId,
+    clobTokenIds: [`${marketId}_yes`, `${marketId}_no`],
+    eventSlug: 'eth-price',
+    bounds: {},
+    kind: 'range',
+    role: 'child',
+    negRisk,
+    negRiskMarketId,
+  };
+}
+
+function group(groupKey: string, descriptors: MarketRangeDescriptor[]): RangeGroup {
+  return {
+    groupKey,
+    crypto: 'ETH',
+    children: descriptors.slice(0, 1),
+    parents: [],
+    unmatched: descriptors.slice(1),
+    overridesApplied: [],
+  };
+}
+
+describe('MarketStructureService - neg-risk events', () => {
+  it('should bucket every neg-risk outcome by its neg-risk market id', () => {
+    const service = new MarketStructureService({} as any);
+    (service as any).cache = new Map([
+      ['a', group('a', [outcome('m1', '0xneg'), outcome('m2', '0xneg')])],
+      ['b', group('b', [outcome('m3', '0xneg'), outcome('m4', undefined, false)])],
+    ]);
+
+    const events = service.getNegRiskEvents();
+
+    expect(events).toHaveLength(1);
+    expect(events[0].negRiskMarketId).toBe('0xneg');
+    expect(events[0].crypto).toBe('ETH');
+    expect(events[0].outcomes.map((o) => o.marketId)).toEqual(['m1', 'm2', 'm3']);
+  });
+
+  it('should skip markets that are not neg-risk', () => {
+    const service = new MarketStructureService({} as any);
+    (service as any).cache = new Map([
+      ['a', group('a', [outcome('m1', '0xneg', false), outcome('m2')])],
+    ]);
+
+    expect(service.getNegRiskEvents()).toEqual([]);
+  });
+});
@@ -0,0 +1,120 @@
+/**
+ * Unit tests for RustEngineBridgeService - structure push (fee rates, neg-risk events)
+ */
+
+import { RustEngineBridgeService } from '../src/modules/strategy/rust-engine-bridge.service';
+import {
+  MarketRangeDescriptor,
+  NegRiskEvent,
+  RangeGroup,
+} from '../src/modules/strategy/interfaces/range-group.interface';
+
+function descriptor(
+  marketId: string,
+  bounds: { lower?: number; upper?: number },
+  kind: MarketRangeDescriptor['kind'],
+  role: 'parent' | 'child',
+  feeRateBps?: number[],
+): MarketRangeDescriptor {
+  return {
+    marketId,
+    slug: marketId,
+    question: marketId,
+    clobTokenIds: [`${marketId}_yes`, `${marketId}_no`],
+    bounds,
+    kind,
+    role,
+    feeRateBps,
+  };
+}
+
+describe('RustEngineBridgeService - structure push', () => {
+  const group: RangeGroup = {
+    groupKey: 'eth',
+    eventSlug: 'eth-price',
+    crypto: 'ETH',
+    children: [
+      descriptor('r2800', { lower: 2800, upper: 2900 }, 'range', 'child', [200, 200]),
+      descriptor('r2900', { lower: 2900, upper: 3000 }, 'range', 'child'),
+    ],
+    parents: [descriptor('above-2800', { lower: 2800 }, 'above', 'parent', [150, 150])],
+    unmatched: [],
+    overridesApplied: [],
+  };
+
+  const negRiskEvent: NegRiskEvent = {
+    negRiskMarketId: '0xneg',
+    eventSlug: 'eth-price',
+    crypto: 'ETH',
+    outcomes: [group.children[0], group.children[1]],
+  };
+
+  let updateMarketStructure: jest.Mock;
+  let updateNegRiskEvents: jest.Mock;
+
+  function sync(): void {
+    updateMarketStructure = jest.fn(() => ({ totalTrios: 0, groups: [] }));
+    updateNegRiskEvents = jest.fn(() => 1);
+    const marketStructureService = {
+      getAllGroups: () => [group],
+      getNegRiskEvents: () => [negRiskEvent],
+    };
+    const bridge = new RustEngineBridgeService(
+      marketStructureService as any,
+      {} as any,
+      {} as any,
+    );
+    (bridge as any).rustCore = { updateMarketStructure, updateNegRiskEvents };
+    (bridge as any).isActive = true;
+
+    bridge.syncMarketStructure();
+  }
+
+  function syncedGroup(): any {
+    sync();
+    expect(updateMarketStructure).toHaveBeenCalledTimes(1);
+    const [napiGroups] = updateMarketStructure.mock.calls[0] as any[];
+    return napiGroups[0];
+  }
+
+  it('should send each descriptor fee rate to the engine', () => {
+    const napi = syncedGroup();
+
+    expect(napi.children[0].feeRateBps).toEqual([200, 200]);
+    expect(napi.parents[0].feeRateBps).toEqual([150, 150]);
+  });
+
+  it('should leave the fee rate unset when the market has none', () => {
+    const napi = syncedGroup();
+
+    expect(napi.children[1].feeRateBps).toBeUndefined();
+  });
+
+  it('should push every neg-risk event with its outcome fee rates', () => {
+    sync();
+
+    expect(updateNegRiskEvents).toHaveBeenCalledTimes(1);
+    const [events] = updateNegRiskEvents.mock.calls[0] as any[];
+    expect(events).toEqual([
+      {
+        eventId: '0xneg',
+        eventSlug: 'eth-price',
+        crypto: 'ETH',
+        outcomes: [
+          {
+            marketId: 'r2800',
+            slug: 'r2800',
+            clobTokenIds: ['r2800_yes', 'r2800_no'],
+            feeRateBps: [200, 200],
+          },
+          {
+            marketId: 'r2900',
+            slug: 'r2900',
+            clobTokenIds: ['r2900_yes', 'r2900_no'],
+            feeRateBps: undefined,
+          },
+        ],
+      },
+    ]);
+  });
+});
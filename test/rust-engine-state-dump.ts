@@ -165,7 +165,7 @@ const napiGroups = groups.map(g => convertGroupToNapi(g));
 let trioCount = 0;
 
 try {
-    trioCount = rustCore.updateMarketStructure(napiGroups);
+    trioCount = rustCore.updateMarketStructure(napiGroups).totalTrios;
     console.log(`✅ Market structure updated: ${trioCount} trios created`);
 } catch (err: any) {
     console.error('❌ updateMarketStructure failed:', err.message);
@@ -147,7 +147,7 @@ if (bootstrapData.allGroups) {
 }
 
 const napiGroups = groups.map(convertGroupToNapi);
-const trioCount = rustCore.updateMarketStructure(napiGroups);
+const trioCount = rustCore.updateMarketStructure(napiGroups).totalTrios;
 console.log(`  Groups fed: ${groups.length}`);
 console.log(`  Trios created: ${trioCount}`);
 